@@ -0,0 +1,339 @@
+# Texts of the launcher window. Every message here needs a counterpart in the other
+# catalogs under locales/, taking the same variables.
+
+## Main window
+
+signed-in-as = Signed in as: { $name }
+not-signed-in = Not signed in
+login = Login
+logout = Logout
+install-game = Install
+update-game = Update
+repair-game = Repair
+uninstall-game = Uninstall
+play-game = Play Game
+maps = Maps
+friends = Friends
+friends-with-new = Friends ({ $count } new)
+notifications = Notifications
+settings = Settings
+no-runtime = No game player is available for { $platform }, the game cannot be started here
+
+## Buttons used on several screens
+
+close = Close
+dismiss = Dismiss
+retry = Retry
+clear = Clear
+back = Back
+refresh = Refresh
+loading = Loading...
+cancel = Cancel
+apply = Apply
+browse = Browse
+play = Play
+search = Search
+previous = Previous
+next = Next
+
+## Notifications
+
+using-system-player = Using the Flash Player found at { $path }.
+session-expired = Your website session has expired.
+session-expired-banner = Your website session has expired, sign in again to keep playing with your account.
+sign-in-again = Sign in again
+theme-low-contrast = Theme "{ $theme }" has low contrast for { $pair } ({ $ratio }:1)
+signing-in = Signing in as { $name }...
+old-builds-removed = Removed { $count } old game { $count ->
+        [one] build
+       *[other] builds
+    }.
+services-down = Not responding: { $services }
+players-detected = Found { $count } Flash { $count ->
+        [one] Player
+       *[other] Players
+    }, using { $path }.
+no-player-detected = No Flash Player found on this system.
+added-to-app-menu = Added the launcher to the app menu, pb2:// links now open it.
+settings-applied = Settings applied.
+signed-out = Signed out.
+new-message = New message from { $from }: { $subject }
+unread-messages = You have { $count } unread { $count ->
+        [one] message
+       *[other] messages
+    }.
+level-info = Info
+level-success = Success
+level-warning = Warning
+level-error = Error
+age-just-now = just now
+age-seconds = { $count } s ago
+age-minutes = { $count } min ago
+age-hours = { $count } h ago
+
+## Server status
+
+service-website = Website
+service-game-server = Game server
+service-updates = Updates
+service-latency = { $ms } ms
+service-down = down ({ $reason })
+players-online = { $count } { $count ->
+        [one] player
+       *[other] players
+    } online
+active-servers = { $count } active { $count ->
+        [one] server
+       *[other] servers
+    }
+checking-status = Checking server status...
+checking = Checking...
+check-now = Check now
+
+## Friends and messages
+
+social-title = Friends and messages
+no-friends = No friends added on the website yet.
+online = Online
+offline = Offline
+last-seen-at = Last seen { $when }
+inbox = Inbox
+inbox-with-unread = Inbox ({ $count } unread)
+no-messages = No messages.
+message-new = New
+nothing-loaded = Nothing loaded yet.
+
+## Maps
+
+search-maps = Search maps by name or author
+loading-maps = Loading maps...
+no-maps-found = No maps found.
+maps-page = Page { $page }
+recently-played = Recently played
+no-recent-maps = Maps started from here are listed here.
+map-author = by { $author }
+map-not-rated = Not rated
+
+## Player profile
+
+profile-rank = Rank
+profile-xp = XP
+profile-kills-deaths = Kills / Deaths
+profile-kill-death-ratio = K/D
+profile-clan = Clan
+profile-last-seen = Last seen
+profile-empty = No profile data available for this account.
+profile-not-loaded = Profile not loaded yet.
+
+## Sign in
+
+username = Username
+password = Password
+submit = Submit
+welcome-back = Welcome back, { $name } !
+signed-in-with-website-password = Signed in with password for game website
+signed-in-with-launcher-password = Signed in with password for standalone launcher
+signed-in-with-md5-password = Signed with md5 password for game website
+no-connection = No connection to game server.
+
+## Settings
+
+language = Language
+language-system = System language
+ui-scale = Interface scale (%)
+ui-scale-hint = Ctrl+Plus and Ctrl+Minus change the scale as well, Ctrl+0 resets it.
+theme = Theme
+reload-themes = Reload themes
+default-runtime = Default runtime
+scrollbar-width = Scrollbar width
+scrollbar-margin = Scrollbar margin
+scroller-width = Scroller width
+text-font = Text font
+news-font = News font
+fonts-hint = Fonts that are not installed fall back to the bundled Noto Sans. In the settings file a font can also be a list, such as "Verdana, DejaVu Sans".
+website = Website
+player-mirror = Player mirror
+ruffle-mirror = Ruffle mirror
+wine-binary = Wine binary
+wine-binary-hint = Used by the Flash Player in Wine runtime. For Proton, enter the files/bin/wine of the Proton install.
+proxy = Proxy
+proxy-hint = Leave the proxy empty to use the system proxy, e.g. http://proxy:8080 or socks5://proxy:1080.
+connect-timeout = Connect timeout (s)
+read-timeout = Read timeout (s)
+retries = Retries
+builds-kept = Game builds kept
+builds-kept-hint = Older builds are removed after a download, 0 keeps all of them. The selected build is always kept.
+update-check-interval = Check for game updates every (min)
+news-refresh-interval = Refresh news every (min)
+intervals-hint = 0 turns the background check off. Checks pause while the game runs.
+install-dir = Install directory
+install-dir-default = Next to the launcher
+choose-install-dir = Choose game install directory
+use-launcher-dir = Use launcher directory
+flash-player = Flash Player
+flash-player-default = Downloaded by the launcher
+choose-flash-player = Choose Flash Player
+detect = Detect
+use-downloaded = Use downloaded
+remember-password = Remember password
+show-tray-icon = Show tray icon
+hide-to-tray = Hide to tray while the game runs
+app-menu = App menu
+add-to-app-menu = Add to app menu
+app-menu-hint = Also makes pb2:// links on the website open the launcher.
+reset-to-defaults = Reset to defaults
+
+## Game builds and players
+
+latest-build = Latest build
+pinned-build = Build { $build }
+runtime-wine = Flash Player in Wine
+starting-game = Starting Plazma Burst 2 build { $build }...
+starting-game-on-map = Starting Plazma Burst 2 build { $build } on { $map }...
+game-started = Plazma Burst 2 started.
+game-up-to-date = PB2 is up to date.
+downloading-game = Downloading PB2...
+game-downloaded = PB2 downloaded successfully.
+updating-game = PB2 update available. Downloading...
+game-updated = PB2 updated successfully.
+installing-game = Installing PB2...
+checking-game-update = Looking for changed game files...
+repairing-game = Checking the game files...
+uninstalling-game = Removing the game files...
+install-step-player = Step 1 of 2: getting the player...
+install-step-game = Step 2 of 2: downloading the game...
+repairing-player = The player is missing or damaged, downloading it again...
+repairing-build = Game build { $build } is damaged, downloading it again...
+damaged-build-removed = Game build { $build } was damaged and is no longer published, it was removed.
+game-files-intact = All game files are intact.
+game-files-repaired = Fixed { $count } { $count ->
+        [one] problem
+       *[other] problems
+    } with the game files.
+game-uninstalled = PB2 uninstalled.
+install-state-not-installed = PB2 is not installed.
+install-state-installed = PB2 build { $build } is installed.
+install-state-damaged = { $count } game { $count ->
+        [one] file is
+       *[other] files are
+    } missing or damaged, Repair fixes it.
+uninstall-title = Uninstall PB2
+uninstall-question = Remove the game builds and the players the launcher downloaded from { $dir }? Settings and the launcher itself stay.
+uninstall-forget-sign-in = Also forget the saved sign in
+update-checking = Checking for game updates...
+update-current = PB2 is up to date, checked { $when }.
+update-available = PB2 build { $build } is available, Update installs it.
+update-check-failed = Could not check for game updates: { $reason }
+news-new = New on the website: { $date }
+using-player = Using the Flash Player at { $path }.
+player-exists = Flashplayer already exists in the same directory as the launcher.
+downloading-player = Downloading Flashplayer...
+player-downloaded = Flashplayer downloaded successfully.
+ruffle-exists = Ruffle is already installed.
+downloading-ruffle = Downloading Ruffle...
+ruffle-downloaded = Ruffle downloaded successfully.
+windows-player-exists = Windows Flashplayer already exists.
+downloading-windows-player = Downloading Windows Flashplayer...
+windows-player-downloaded = Windows Flashplayer downloaded successfully.
+setting-up-wine-prefix = Setting up the Wine prefix...
+wine-prefix-ready = Wine prefix is ready.
+download-progress = { $step } { $received } of { $total } MB
+download-progress-unknown = { $step } { $received } MB
+
+## Tray menu
+
+tray-show-window = Show launcher
+tray-play = Play
+tray-play-as-guest = Play as guest
+tray-check-for-updates = Check for updates
+tray-latest-news = Latest news
+tray-quit = Quit
+
+## Errors, shown with the underlying cause appended
+
+error-request-timed-out = Request timed out
+error-no-connection = No connection to game server
+error-server-error = Server returned an error
+error-request-failed = Request failed
+error-invalid-header = Invalid request header
+error-file-operation = File operation failed
+error-invalid-address = { $host ->
+        [website] Website
+        [player] Player mirror
+       *[ruffle] Ruffle mirror
+    } address { $address } is not a valid http(s) URL
+error-invalid-proxy = Invalid proxy address { $proxy }
+error-http-client = Failed to set up HTTP client
+error-sign-in = Failed to send sign in request
+error-sign-in-status = Sign in request failed with status { $status }
+error-game-sign-in = Failed to send standalone launcher sign in request
+error-load-news = Failed to load news
+error-load-news-page = Failed to load news page { $page }
+error-load-maps = Failed to load maps
+error-load-profile = Failed to load player profile
+error-load-friends = Failed to load friends
+error-load-messages = Failed to load messages
+error-read-session = Failed to read saved session
+error-session-damaged = Session file is damaged
+error-save-session = Failed to save session
+error-remove-session = Failed to remove saved session
+error-save-credentials = Failed to write auth file
+error-remove-credentials = Failed to remove saved credentials
+error-read-settings = Failed to read settings
+error-settings-damaged = Settings file is damaged
+error-save-settings = Failed to save settings
+error-settings-newer = Settings were written by a newer launcher (version { $version })
+error-read-themes = Failed to read themes
+error-themes-damaged = Themes file is damaged
+error-read-recent-maps = Failed to read recent maps
+error-recent-maps-damaged = Recent maps file is damaged
+error-save-recent-maps = Failed to save recent maps
+error-launcher-path = Failed to get current executable path
+error-launcher-dir = Unable to get parent of Launcher EXE path
+error-swf-path = Failed to convert SWF file path to string
+error-instance-check = Failed to check for a running launcher
+error-tray-icon = Failed to show the tray icon
+error-tray-linux-only = The tray icon is only available on Linux
+error-home-dir = Cannot find the home directory
+error-launcher-executable = Cannot find the launcher executable
+error-add-to-menu = Failed to add the launcher to the app menu
+error-remove-from-menu = Failed to remove the launcher from the app menu
+error-app-menu-linux-only = Adding the launcher to the app menu is only supported on Linux
+error-unknown-argument = Unknown argument { $argument }
+error-not-a-link = { $link } is not a link the launcher can open
+error-create-install-dir = Failed to create install directory
+error-create-build-dir = Failed to create game build directory
+error-get-time = Failed to get PB2 time
+error-save-time = Failed to save PB2 time
+error-download-game = Failed to download PB2 swf
+error-not-installed = Game is not installed yet, press Install first
+error-build-damaged = Game build { $build } is damaged, press Repair
+error-build-gone = Game build { $build } is no longer installed, pick another build
+error-list-builds = Failed to list game builds
+error-move-build = Failed to move the installed game build
+error-remove-build = Failed to remove game build { $build }
+error-remove = Failed to remove { $path }
+error-start-game = Failed to start game process
+error-no-player = Flashplayer is not available for { $platform }
+error-no-ruffle = Ruffle is not available for { $platform }
+error-download-player = Failed to download Flashplayer
+error-unpack-player = Failed to unpack Flashplayer
+error-download-ruffle = Failed to download Ruffle
+error-unpack-ruffle = Failed to unpack Ruffle
+error-ruffle-damaged = Ruffle archive is damaged
+error-unpack-disk-image = Failed to unpack the disk image
+error-disk-image-damaged = Flash Player disk image is damaged
+error-not-downloaded = { $name } is not downloaded yet, press Repair
+error-download-damaged = { $name } download is damaged, press Repair
+error-cannot-read = Cannot read { $path }
+error-not-a-program = { $path } is not a program
+error-not-executable = { $path } is not executable
+error-make-executable = Failed to make the player executable
+error-record-size = Failed to record the size of { $path }
+error-no-wine = Wine binary { $wine } was not found, install Wine or set its path in Settings
+error-create-wine-dir = Failed to create Wine directory
+error-create-wine-prefix = Failed to create Wine prefix
+error-wine-prefix = Failed to set up the Wine prefix
+error-wineboot-failed = Failed to set up the Wine prefix, wineboot exited with { $status }
+service-timed-out = timed out
+service-unreachable = unreachable
@@ -0,0 +1,344 @@
+# Russian texts of the launcher window, message for message the same as locales/en-US.
+
+## Main window
+
+signed-in-as = Вы вошли как: { $name }
+not-signed-in = Вход не выполнен
+login = Войти
+logout = Выйти
+install-game = Установить
+update-game = Обновить
+repair-game = Восстановить
+uninstall-game = Удалить
+play-game = Играть
+maps = Карты
+friends = Друзья
+friends-with-new = Друзья (новых: { $count })
+notifications = Уведомления
+settings = Настройки
+no-runtime = Для { $platform } нет проигрывателя, игру здесь не запустить
+
+## Buttons used on several screens
+
+close = Закрыть
+dismiss = Скрыть
+retry = Повторить
+clear = Очистить
+back = Назад
+refresh = Обновить
+loading = Загрузка...
+cancel = Отмена
+apply = Применить
+browse = Обзор
+play = Играть
+search = Найти
+previous = Назад
+next = Вперёд
+
+## Notifications
+
+using-system-player = Используется Flash Player из { $path }.
+session-expired = Сессия на сайте истекла.
+session-expired-banner = Сессия на сайте истекла, войдите снова, чтобы играть под своей учётной записью.
+sign-in-again = Войти снова
+theme-low-contrast = В теме «{ $theme }» низкий контраст для { $pair } ({ $ratio }:1)
+signing-in = Вход как { $name }...
+old-builds-removed = { $count ->
+        [one] Удалена { $count } старая сборка игры.
+        [few] Удалено { $count } старые сборки игры.
+       *[many] Удалено { $count } старых сборок игры.
+    }
+services-down = Не отвечают: { $services }
+players-detected = { $count ->
+        [one] Найден { $count } Flash Player
+       *[other] Найдено Flash Player: { $count }
+    }, используется { $path }.
+no-player-detected = Flash Player в системе не найден.
+added-to-app-menu = Лаунчер добавлен в меню приложений, ссылки pb2:// теперь открываются в нём.
+settings-applied = Настройки применены.
+signed-out = Вы вышли.
+new-message = Новое сообщение от { $from }: { $subject }
+unread-messages = { $count ->
+        [one] У вас { $count } непрочитанное сообщение.
+        [few] У вас { $count } непрочитанных сообщения.
+       *[many] У вас { $count } непрочитанных сообщений.
+    }
+level-info = Инфо
+level-success = Готово
+level-warning = Внимание
+level-error = Ошибка
+age-just-now = только что
+age-seconds = { $count } с назад
+age-minutes = { $count } мин назад
+age-hours = { $count } ч назад
+
+## Server status
+
+service-website = Сайт
+service-game-server = Игровой сервер
+service-updates = Обновления
+service-latency = { $ms } мс
+service-down = недоступен ({ $reason })
+players-online = { $count ->
+        [one] { $count } игрок в сети
+        [few] { $count } игрока в сети
+       *[many] { $count } игроков в сети
+    }
+active-servers = { $count ->
+        [one] { $count } активный сервер
+        [few] { $count } активных сервера
+       *[many] { $count } активных серверов
+    }
+checking-status = Проверка серверов...
+checking = Проверка...
+check-now = Проверить
+
+## Friends and messages
+
+social-title = Друзья и сообщения
+no-friends = На сайте ещё нет добавленных друзей.
+online = В сети
+offline = Не в сети
+last-seen-at = Был в сети { $when }
+inbox = Входящие
+inbox-with-unread = Входящие (непрочитанных: { $count })
+no-messages = Сообщений нет.
+message-new = Новое
+nothing-loaded = Пока ничего не загружено.
+
+## Maps
+
+search-maps = Поиск карт по названию или автору
+loading-maps = Загрузка карт...
+no-maps-found = Карты не найдены.
+maps-page = Страница { $page }
+recently-played = Недавние
+no-recent-maps = Здесь появятся карты, запущенные из лаунчера.
+map-author = автор { $author }
+map-not-rated = Без оценки
+
+## Player profile
+
+profile-rank = Ранг
+profile-xp = Опыт
+profile-kills-deaths = Убийства / Смерти
+profile-kill-death-ratio = У/С
+profile-clan = Клан
+profile-last-seen = Был в сети
+profile-empty = Для этой учётной записи нет данных профиля.
+profile-not-loaded = Профиль ещё не загружен.
+
+## Sign in
+
+username = Имя пользователя
+password = Пароль
+submit = Войти
+welcome-back = С возвращением, { $name }!
+signed-in-with-website-password = Вход с паролем от сайта игры
+signed-in-with-launcher-password = Вход с паролем от отдельного лаунчера
+signed-in-with-md5-password = Вход с md5-паролем от сайта игры
+no-connection = Нет связи с игровым сервером.
+
+## Settings
+
+language = Язык
+language-system = Язык системы
+ui-scale = Масштаб интерфейса (%)
+ui-scale-hint = Масштаб также меняют Ctrl+Плюс и Ctrl+Минус, Ctrl+0 сбрасывает его.
+theme = Тема
+reload-themes = Перезагрузить темы
+default-runtime = Проигрыватель по умолчанию
+scrollbar-width = Ширина полосы прокрутки
+scrollbar-margin = Отступ полосы прокрутки
+scroller-width = Ширина ползунка
+text-font = Шрифт текста
+news-font = Шрифт новостей
+fonts-hint = Вместо неустановленных шрифтов используется встроенный Noto Sans. В файле настроек шрифт можно задать и списком, например "Verdana, DejaVu Sans".
+website = Сайт
+player-mirror = Зеркало Flash Player
+ruffle-mirror = Зеркало Ruffle
+wine-binary = Программа Wine
+wine-binary-hint = Используется для Flash Player в Wine. Для Proton укажите files/bin/wine из установки Proton.
+proxy = Прокси
+proxy-hint = Оставьте прокси пустым, чтобы использовать системный, например http://proxy:8080 или socks5://proxy:1080.
+connect-timeout = Тайм-аут подключения (с)
+read-timeout = Тайм-аут чтения (с)
+retries = Повторы
+builds-kept = Хранить сборок игры
+builds-kept-hint = Старые сборки удаляются после загрузки, 0 хранит все. Выбранная сборка хранится всегда.
+update-check-interval = Проверять обновления игры каждые (мин)
+news-refresh-interval = Обновлять новости каждые (мин)
+intervals-hint = 0 отключает фоновую проверку. Пока идёт игра, проверки приостановлены.
+install-dir = Папка установки
+install-dir-default = Рядом с лаунчером
+choose-install-dir = Выберите папку для установки игры
+use-launcher-dir = Папка лаунчера
+flash-player = Flash Player
+flash-player-default = Скачанный лаунчером
+choose-flash-player = Выберите Flash Player
+detect = Найти
+use-downloaded = Скачанный
+remember-password = Запомнить пароль
+show-tray-icon = Значок в трее
+hide-to-tray = Скрывать в трей во время игры
+app-menu = Меню приложений
+add-to-app-menu = Добавить в меню приложений
+app-menu-hint = Ссылки pb2:// на сайте тоже будут открывать лаунчер.
+reset-to-defaults = Сбросить настройки
+
+## Game builds and players
+
+latest-build = Последняя сборка
+pinned-build = Сборка { $build }
+runtime-wine = Flash Player в Wine
+starting-game = Запуск Plazma Burst 2, сборка { $build }...
+starting-game-on-map = Запуск Plazma Burst 2, сборка { $build }, карта { $map }...
+game-started = Plazma Burst 2 запущена.
+game-up-to-date = PB2 последней версии.
+downloading-game = Загрузка PB2...
+game-downloaded = PB2 загружена.
+updating-game = Доступно обновление PB2. Загрузка...
+game-updated = PB2 обновлена.
+installing-game = Установка PB2...
+checking-game-update = Поиск изменённых файлов игры...
+repairing-game = Проверка файлов игры...
+uninstalling-game = Удаление файлов игры...
+install-step-player = Шаг 1 из 2: получение проигрывателя...
+install-step-game = Шаг 2 из 2: загрузка игры...
+repairing-player = Проигрыватель отсутствует или повреждён, загружаем заново...
+repairing-build = Сборка игры { $build } повреждена, загружаем заново...
+damaged-build-removed = Сборка игры { $build } повреждена и больше не публикуется, она удалена.
+game-files-intact = Все файлы игры в порядке.
+game-files-repaired = { $count ->
+        [one] Исправлена { $count } проблема с файлами игры.
+        [few] Исправлено { $count } проблемы с файлами игры.
+       *[many] Исправлено { $count } проблем с файлами игры.
+    }
+game-uninstalled = PB2 удалена.
+install-state-not-installed = PB2 не установлена.
+install-state-installed = Установлена сборка PB2 { $build }.
+install-state-damaged = { $count ->
+        [one] { $count } файл игры отсутствует или повреждён, «Восстановить» исправит это.
+        [few] { $count } файла игры отсутствуют или повреждены, «Восстановить» исправит это.
+       *[many] { $count } файлов игры отсутствуют или повреждены, «Восстановить» исправит это.
+    }
+uninstall-title = Удаление PB2
+uninstall-question = Удалить сборки игры и загруженные лаунчером проигрыватели из { $dir }? Настройки и сам лаунчер останутся.
+uninstall-forget-sign-in = Также забыть сохранённый вход
+update-checking = Проверка обновлений игры...
+update-current = PB2 последней версии, проверено { $when }.
+update-available = Доступна сборка PB2 { $build }, «Обновить» установит её.
+update-check-failed = Не удалось проверить обновления игры: { $reason }
+news-new = Новое на сайте: { $date }
+using-player = Используется Flash Player из { $path }.
+player-exists = Flash Player уже лежит рядом с лаунчером.
+downloading-player = Загрузка Flash Player...
+player-downloaded = Flash Player загружен.
+ruffle-exists = Ruffle уже установлен.
+downloading-ruffle = Загрузка Ruffle...
+ruffle-downloaded = Ruffle загружен.
+windows-player-exists = Flash Player для Windows уже загружен.
+downloading-windows-player = Загрузка Flash Player для Windows...
+windows-player-downloaded = Flash Player для Windows загружен.
+setting-up-wine-prefix = Настройка префикса Wine...
+wine-prefix-ready = Префикс Wine готов.
+download-progress = { $step } { $received } из { $total } МБ
+download-progress-unknown = { $step } { $received } МБ
+
+## Tray menu
+
+tray-show-window = Открыть лаунчер
+tray-play = Играть
+tray-play-as-guest = Играть гостем
+tray-check-for-updates = Проверить обновления
+tray-latest-news = Последние новости
+tray-quit = Выйти
+
+## Errors, shown with the underlying cause appended
+
+error-request-timed-out = Время ожидания запроса истекло
+error-no-connection = Нет связи с сервером игры
+error-server-error = Сервер вернул ошибку
+error-request-failed = Запрос не выполнен
+error-invalid-header = Недопустимый заголовок запроса
+error-file-operation = Ошибка работы с файлом
+error-invalid-address = { $host ->
+        [website] Адрес сайта
+        [player] Адрес зеркала проигрывателя
+       *[ruffle] Адрес зеркала Ruffle
+    } { $address } не является http(s)-адресом
+error-invalid-proxy = Недопустимый адрес прокси { $proxy }
+error-http-client = Не удалось настроить HTTP-клиент
+error-sign-in = Не удалось отправить запрос на вход
+error-sign-in-status = Запрос на вход завершился с кодом { $status }
+error-game-sign-in = Не удалось отправить запрос на вход через лаунчер
+error-load-news = Не удалось загрузить новости
+error-load-news-page = Не удалось загрузить страницу новостей { $page }
+error-load-maps = Не удалось загрузить карты
+error-load-profile = Не удалось загрузить профиль игрока
+error-load-friends = Не удалось загрузить друзей
+error-load-messages = Не удалось загрузить сообщения
+error-read-session = Не удалось прочитать сохранённую сессию
+error-session-damaged = Файл сессии повреждён
+error-save-session = Не удалось сохранить сессию
+error-remove-session = Не удалось удалить сохранённую сессию
+error-save-credentials = Не удалось сохранить данные входа
+error-remove-credentials = Не удалось удалить сохранённые данные входа
+error-read-settings = Не удалось прочитать настройки
+error-settings-damaged = Файл настроек повреждён
+error-save-settings = Не удалось сохранить настройки
+error-settings-newer = Настройки записаны более новым лаунчером (версия { $version })
+error-read-themes = Не удалось прочитать темы
+error-themes-damaged = Файл тем повреждён
+error-read-recent-maps = Не удалось прочитать недавние карты
+error-recent-maps-damaged = Файл недавних карт повреждён
+error-save-recent-maps = Не удалось сохранить недавние карты
+error-launcher-path = Не удалось определить путь к лаунчеру
+error-launcher-dir = Не удалось определить папку лаунчера
+error-swf-path = Путь к SWF-файлу игры содержит недопустимые символы
+error-instance-check = Не удалось проверить, запущен ли уже лаунчер
+error-tray-icon = Не удалось показать значок в трее
+error-tray-linux-only = Значок в трее доступен только в Linux
+error-home-dir = Не удалось найти домашнюю папку
+error-launcher-executable = Не удалось найти исполняемый файл лаунчера
+error-add-to-menu = Не удалось добавить лаунчер в меню приложений
+error-remove-from-menu = Не удалось убрать лаунчер из меню приложений
+error-app-menu-linux-only = Добавить лаунчер в меню приложений можно только в Linux
+error-unknown-argument = Неизвестный аргумент { $argument }
+error-not-a-link = { $link } — не ссылка, которую может открыть лаунчер
+error-create-install-dir = Не удалось создать папку установки
+error-create-build-dir = Не удалось создать папку сборки игры
+error-get-time = Не удалось узнать время сборки PB2
+error-save-time = Не удалось сохранить время сборки PB2
+error-download-game = Не удалось загрузить SWF-файл PB2
+error-not-installed = Игра ещё не установлена, сначала нажмите «Установить»
+error-build-damaged = Сборка игры { $build } повреждена, нажмите «Восстановить»
+error-build-gone = Сборка игры { $build } больше не установлена, выберите другую
+error-list-builds = Не удалось получить список сборок игры
+error-move-build = Не удалось перенести установленную сборку игры
+error-remove-build = Не удалось удалить сборку игры { $build }
+error-remove = Не удалось удалить { $path }
+error-start-game = Не удалось запустить игру
+error-no-player = Flash Player недоступен для { $platform }
+error-no-ruffle = Ruffle недоступен для { $platform }
+error-download-player = Не удалось загрузить Flash Player
+error-unpack-player = Не удалось распаковать Flash Player
+error-download-ruffle = Не удалось загрузить Ruffle
+error-unpack-ruffle = Не удалось распаковать Ruffle
+error-ruffle-damaged = Архив Ruffle повреждён
+error-unpack-disk-image = Не удалось распаковать образ диска
+error-disk-image-damaged = Образ диска Flash Player повреждён
+error-not-downloaded = { $name } ещё не загружен, нажмите «Восстановить»
+error-download-damaged = Загрузка { $name } повреждена, нажмите «Восстановить»
+error-cannot-read = Не удалось прочитать { $path }
+error-not-a-program = { $path } не является программой
+error-not-executable = { $path } не является исполняемым файлом
+error-make-executable = Не удалось сделать проигрыватель исполняемым
+error-record-size = Не удалось записать размер { $path }
+error-no-wine = Wine { $wine } не найден, установите Wine или укажите путь к нему в настройках
+error-create-wine-dir = Не удалось создать папку Wine
+error-create-wine-prefix = Не удалось создать префикс Wine
+error-wine-prefix = Не удалось настроить префикс Wine
+error-wineboot-failed = Не удалось настроить префикс Wine, wineboot завершился с { $status }
+service-timed-out = нет ответа
+service-unreachable = недоступен
@@ -1,16 +1,40 @@
 use std::fs;
 use std::fs::File;
+use std::future::Future;
 use std::io::Write;
-use std::path::PathBuf;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::Child;
 use std::sync::Arc;
-use cfg_if::cfg_if;
-use iced::widget::{Button, Column, Container, Row, scrollable, Scrollable, Text, TextInput, Tooltip};
-use iced::{Alignment, Element, Length, Task, Theme};
+use std::time::{Duration, Instant};
+use iced::widget::{self, Button, Checkbox, Column, Container, PickList, ProgressBar, Row, scrollable, Scrollable, Slider, Text, text_input, TextInput, tooltip, Tooltip};
+use iced::{event, keyboard, window, Alignment, Element, Event, Font, Length, Subscription, Task, Theme};
 use iced::advanced::graphics::text::cosmic_text::Command;
 use iced::futures::channel::mpsc;
 use iced::futures::{channel, SinkExt};
 use regex::Regex;
-use crate::{Direction, Message, SCROLLABLE_ID, styles};
+use crate::{Direction, Endpoints, Message, SCROLLABLE_ID, USERNAME_INPUT_ID, styles};
+use crate::error::{Context, ErrorKind, LauncherError};
+use crate::notifications::{self, Level, Notifications, Progress, Reporter};
+use crate::settings::{Setting, Settings, REFRESH_INTERVALS, SETTINGS_FILE_NAME, UI_SCALES};
+use crate::theme::{self, Palette, THEMES_FILE_NAME};
+use crate::profile::{self, PlayerProfile};
+use crate::session::{self, Session, SESSION_FILE_NAME};
+use crate::http::{HttpOptions, Profile};
+use crate::status::{self, Health, StatusReport, STATUS_INTERVAL};
+use crate::maps::{self, MapBrowser, MapEntry, RecentMaps, RECENT_MAPS_FILE_NAME};
+use crate::social::{self, Social, SOCIAL_INTERVAL};
+use crate::versions::{self, GameVersion, UpdateStatus, VersionChoice};
+use crate::runtime::{self, GameRuntime, RuntimeKind};
+use crate::platform::get_platform;
+use crate::desktop::{self, Link};
+use crate::fonts;
+use crate::instance;
+use crate::tray::{self, TrayAction, TrayIcon};
+use crate::i18n::{self, LanguageChoice};
+use crate::shortcuts::{self, Shortcut};
+use crate::scheduler::{self, Job};
+use crate::install::{self, InstallAction, InstallState};
 
 
 use serde::{Deserialize, Serialize};
@@ -27,15 +51,27 @@ enum LoginMethod {
 }
 
 impl LoginMethod {
-    fn message_suffix(&self) -> &'static str {
-        match self {
-            LoginMethod::GameWebsite => "\r\n(Signed in with password for game website)",
-            LoginMethod::StandaloneLauncher => "\r\n(Signed in with password for standalone launcher)",
-            LoginMethod::MD5Password => "\r\n(Signed with md5 password for game website)",
-        }
+    fn message_suffix(&self) -> String {
+        let method = match self {
+            LoginMethod::GameWebsite => t!("signed-in-with-website-password"),
+            LoginMethod::StandaloneLauncher => t!("signed-in-with-launcher-password"),
+            LoginMethod::MD5Password => t!("signed-in-with-md5-password"),
+        };
+        format!("\r\n({})", method)
     }
 }
 
+// Screen the window shows, the ones drawn over the others first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Screen {
+    NotificationHistory,
+    Social,
+    Settings,
+    Maps,
+    Uninstall,
+    Login,
+    News,
+}
 
 pub struct LauncherMainWindow {
     scrollable_direction: Direction,
@@ -54,30 +90,503 @@ pub struct LauncherMainWindow {
     password_visible: bool,
     has_signed_in: bool,
     signed_in_as: String,
-
+    profile: Option<PlayerProfile>,
+    loading_profile: bool,
+    social: Option<Social>, // friends and inbox, only for website sign ins
+    loading_social: bool,
+    show_social: bool,
+    session: Session,
+    session_expired: bool, // the website signed us out, asks the user to sign in again
+    server_status: Option<StatusReport>,
+    checking_status: bool,
+    map_browser: Option<MapBrowser>, // open while the map browser is shown
+    recent_maps: RecentMaps,
+    versions: Vec<GameVersion>, // installed game builds, newest first
+    update_status: Option<(Result<UpdateStatus, LauncherError>, Instant)>, // last update check and when it finished
+    checking_updates: bool,
+    install_state: InstallState,
+    install_running: Option<InstallAction>,
+    install_progress: Option<String>, // last step the running action reported
+    download_progress: Option<Progress>, // bytes of the download that step is waiting for
+    uninstall_prompt: Option<bool>, // asking to confirm Uninstall, with whether to forget the sign in too
+    settings: Settings,
+    settings_draft: Option<Settings>, // edited copy while the Settings screen is open
+    palettes: Vec<Palette>, // built-in palettes followed by the user's own
+    error: Option<(LauncherError, Option<Message>)>, // shown in the error banner, with the message that retries it
+    notifications: Notifications,
+    show_notification_history: bool,
+    instance_requests: instance::Requests, // links from later starts of the launcher
+    tray: Option<TrayIcon>, // shown while the tray icon setting is on
+    games_running: usize,
+    hidden_to_tray: bool, // hidden while the game runs, shown again when it exits
 }
 
 impl LauncherMainWindow {
     fn new() -> Self {
-        LauncherMainWindow {
+        let settings = launcher_dir().and_then(|dir| Settings::load(&dir.join(SETTINGS_FILE_NAME)));
+        let mut window = LauncherMainWindow {
             scrollable_direction: Direction::Vertical,
             scrollbar_width: 10,
             scrollbar_margin: 0,
             scroller_width: 10,
             current_scroll_offset: scrollable::RelativeOffset::START,
             alignment: scrollable::Alignment::Start,
-            news_pages_count: crate::get_news_pages_count().unwrap_or_default(),
+            news_pages_count: 0,
             current_page: 0,
             loading_page: false,
-            news: Option::from(crate::get_news_and_dates_by_page_number(0).unwrap_or_default()),
+            news: None,
             show_login_form: false,
             username: String::new(),
             password: String::new(),
             password_visible: false,
             has_signed_in: false,
             signed_in_as: String::new(),
+            profile: None,
+            loading_profile: false,
+            social: None,
+            loading_social: false,
+            show_social: false,
+            session: Session::new(Endpoints::default(), &HttpOptions::default()).expect("default HTTP options are valid"),
+            session_expired: false,
+            server_status: None,
+            checking_status: false,
+            map_browser: None,
+            recent_maps: RecentMaps::default(),
+            versions: Vec::new(),
+            update_status: None,
+            checking_updates: false,
+            install_state: InstallState::NotInstalled,
+            install_running: None,
+            install_progress: None,
+            download_progress: None,
+            uninstall_prompt: None,
+            settings: Settings::default(),
+            settings_draft: None,
+            palettes: Palette::built_in(),
+            error: None,
+            notifications: Notifications::default(),
+            show_notification_history: false,
+            instance_requests: instance::Requests::default(),
+            tray: None,
+            games_running: 0,
+            hidden_to_tray: false,
+        };
+        match settings {
+            Ok(settings) => window.apply_settings(settings),
+            Err(err) => window.report_error(err, None),
+        }
+        window.reload_palettes();
+        match launcher_dir().and_then(|dir| RecentMaps::load(&dir.join(RECENT_MAPS_FILE_NAME))) {
+            Ok(recent_maps) => window.recent_maps = recent_maps,
+            Err(err) => window.report_error(err, None),
+        }
+        window.reload_versions();
+        window.restore_session();
+        window
+    }
+
+    // Shows the window, also when it was hidden to the tray or minimized, and raises it
+    fn show_window(&mut self) -> Task<Message> {
+        self.hidden_to_tray = false;
+        window::get_latest().and_then(|id| {
+            Task::batch([window::change_mode(id, window::Mode::Windowed), window::minimize(id, false), window::gain_focus(id)])
+        })
+    }
+
+    // Shows the tray icon while the setting is on, a desktop without a tray only gets a warning
+    fn update_tray(&mut self) {
+        if !self.settings.tray_icon {
+            self.tray = None;
+            return;
+        }
+        if self.tray.is_some() {
+            return;
+        }
+        match tray::show() {
+            Ok(tray) => self.tray = Some(tray),
+            Err(err) => {
+                self.notifications.push(Level::Warning, err.to_string());
+            }
+        }
+    }
+
+    // Starts the game as `credentials`, or as guest. `retry` runs again when it fails
+    fn start_game(&mut self, credentials: Option<(String, String)>, map: Option<MapEntry>, retry: Message) -> Task<Message> {
+        let install_dir = match self.install_dir() {
+            Ok(dir) => dir,
+            Err(err) => {
+                self.report_error(err, None);
+                return Task::none();
+            }
+        };
+        let Some(runtime) = self.game_runtime() else {
+            self.report_error(no_runtime_error(), None);
+            return Task::none();
+        };
+        let version = self.settings.game_version.clone();
+        // Tells the window when the game is up and when it exits, in that order
+        let (game_events, running) = mpsc::unbounded();
+        Task::batch([
+            Task::run(running, |started| if started { Message::GameStarted } else { Message::GameExited }),
+            with_reporter(move |reporter| async move {
+                let game = start_game_process(&install_dir, runtime, version, credentials, map, &reporter).await?;
+                watch_game(game, game_events);
+                Ok(())
+            }, move |result| match result {
+                Ok(()) => Message::PlayGameFinished(Ok(())),
+                Err(err) => Message::ErrorOccurred(err, Some(Box::new(retry.clone()))),
+            }),
+        ])
+    }
+
+    /// Window opening the `pb2://` links the launcher was started with, and later the ones
+    /// forwarded through `requests`.
+    pub fn start(links: Vec<String>, requests: instance::Requests) -> (Self, Task<Message>) {
+        let mut window = Self::new();
+        window.instance_requests = requests;
+        let mut tasks = vec![window.load_news(0)];
+        tasks.extend(links.into_iter().map(|link| window.update(Message::OpenLink(link))));
+        (window, Task::batch(tasks))
+    }
+
+    fn screen(&self) -> Screen {
+        if self.show_notification_history {
+            Screen::NotificationHistory
+        } else if self.show_social && self.has_signed_in {
+            Screen::Social
+        } else if self.settings_draft.is_some() {
+            Screen::Settings
+        } else if self.map_browser.is_some() {
+            Screen::Maps
+        } else if self.uninstall_prompt.is_some() {
+            Screen::Uninstall
+        } else if self.show_login_form {
+            Screen::Login
+        } else {
+            Screen::News
+        }
+    }
+
+    // Runs a keyboard shortcut on the screen that is shown
+    fn handle_shortcut(&mut self, shortcut: Shortcut) -> Task<Message> {
+        let screen = self.screen();
+        match shortcut {
+            Shortcut::FocusNext => widget::focus_next(),
+            Shortcut::FocusPrevious => widget::focus_previous(),
+            Shortcut::Cancel => match screen {
+                Screen::NotificationHistory => self.update(Message::ToggleNotificationHistory),
+                Screen::Social => self.update(Message::ToggleSocial),
+                Screen::Settings => self.update(Message::CancelSettings),
+                Screen::Maps => self.update(Message::CloseMaps),
+                Screen::Uninstall => self.update(Message::CancelUninstall),
+                Screen::Login => self.update(Message::LoginCancel),
+                Screen::News if self.error.is_some() => self.update(Message::DismissError),
+                Screen::News if self.session_expired => self.update(Message::DismissSessionExpired),
+                Screen::News => Task::none(),
+            },
+            Shortcut::NextPage | Shortcut::PreviousPage => {
+                let forward = shortcut == Shortcut::NextPage;
+                match (screen, &self.map_browser) {
+                    // Same limits as the Previous and Next buttons
+                    (Screen::Maps, Some(browser)) if !browser.loading => {
+                        let page = if forward {
+                            (!browser.results.is_empty()).then_some(browser.page + 1)
+                        } else {
+                            browser.page.checked_sub(1)
+                        };
+                        page.map_or_else(Task::none, |page| self.update(Message::MapsPageChanged(page)))
+                    }
+                    (Screen::News, _) if !self.loading_page => {
+                        let page = if forward { self.current_page.checked_add(1) } else { self.current_page.checked_sub(1) };
+                        match page {
+                            Some(page) if page < self.news_pages_count => self.update(Message::PageChanged(page)),
+                            _ => Task::none(),
+                        }
+                    }
+                    _ => Task::none(),
+                }
+            }
+            // Open settings are never thrown away, the other screens make way for the form
+            Shortcut::OpenLogin if self.has_signed_in || screen == Screen::Settings => Task::none(),
+            Shortcut::OpenLogin => {
+                self.show_notification_history = false;
+                self.map_browser = None;
+                self.uninstall_prompt = None;
+                self.update(Message::LoginPressed)
+            }
+            Shortcut::Refresh => match (screen, &self.map_browser) {
+                (Screen::Social, _) => self.update(Message::RefreshSocial),
+                (Screen::Maps, Some(browser)) => {
+                    let page = browser.page;
+                    self.update(Message::MapsPageChanged(page))
+                }
+                (Screen::News, _) => Task::batch([
+                    self.update(Message::ReloadNews),
+                    self.update(Message::RefreshProfile),
+                    self.update(Message::CheckServerStatus),
+                ]),
+                _ => Task::none(),
+            },
+            Shortcut::ZoomIn => self.set_ui_scale(self.settings.ui_scale.saturating_add(UI_SCALE_STEP)),
+            Shortcut::ZoomOut => self.set_ui_scale(self.settings.ui_scale.saturating_sub(UI_SCALE_STEP)),
+            Shortcut::ResetZoom => self.set_ui_scale(Settings::default().ui_scale),
+        }
+    }
+
+    // Scales the interface right away and keeps it for the next start
+    fn set_ui_scale(&mut self, scale: u16) -> Task<Message> {
+        let scale = scale.clamp(*UI_SCALES.start(), *UI_SCALES.end());
+        self.settings.ui_scale = scale;
+        if let Some(draft) = &mut self.settings_draft {
+            draft.ui_scale = scale;
+        }
+        if let Err(err) = launcher_dir().and_then(|dir| self.settings.save(&dir.join(SETTINGS_FILE_NAME))) {
+            self.report_error(err, None);
+        }
+        Task::none()
+    }
+
+    // Player Play starts the game with, picked per account. Falls back to another runtime
+    // when the picked one has no player for this platform, `None` when none has.
+    fn runtime(&self) -> Option<RuntimeKind> {
+        let preferred = self.settings.runtime_for(self.has_signed_in.then_some(self.signed_in_as.as_str()));
+        RuntimeKind::pick(preferred, &get_platform(), &self.settings)
+    }
+
+    fn game_runtime(&self) -> Option<Box<dyn GameRuntime>> {
+        self.runtime().map(|kind| kind.runtime(&self.settings))
+    }
+
+    // Before the projector is downloaded, a Flash Player already on the system is used instead
+    fn use_system_player(&mut self) {
+        if self.runtime() != Some(RuntimeKind::FlashPlayer) || self.settings.player_path.is_some() {
+            return;
+        }
+        let downloaded = self.install_dir().is_ok_and(|dir| RuntimeKind::FlashPlayer.runtime(&Settings::default()).verify(&dir).is_ok());
+        if downloaded {
+            return;
+        }
+        let Some(player) = runtime::detect_flash_players().into_iter().next() else {
+            return;
+        };
+        self.notifications.push(Level::Info, t!("using-system-player", path = player.display().to_string()));
+        self.settings.player_path = Some(player);
+        if let Err(err) = launcher_dir().and_then(|dir| self.settings.save(&dir.join(SETTINGS_FILE_NAME))) {
+            self.report_error(err, None);
+        }
+    }
+
+    // Installed builds and the state of the game files, after anything that may have changed them
+    fn reload_versions(&mut self) {
+        let runtime = self.game_runtime();
+        let reloaded = self.install_dir().and_then(|dir| Ok((versions::installed(&dir)?, install::state(&dir, runtime.as_deref())?)));
+        match reloaded {
+            Ok((versions, state)) => {
+                self.versions = versions;
+                self.install_state = state;
+            }
+            Err(err) => self.report_error(err, None),
+        }
+    }
+
+    // Runs an action on the game files, one at a time
+    fn run_install_action(&mut self, action: InstallAction) -> Task<Message> {
+        if self.install_running.is_some() {
+            return Task::none();
+        }
+        if action != InstallAction::Uninstall {
+            self.use_system_player();
+        }
+        let session = self.session.clone();
+        let install_dir = match self.install_dir() {
+            Ok(dir) => dir,
+            Err(err) => {
+                self.report_error(err, None);
+                return Task::none();
+            }
+        };
+        let (keep, pinned) = (self.settings.keep_versions, self.settings.game_version.clone());
+        let Some(runtime) = self.game_runtime() else {
+            self.report_error(no_runtime_error(), None);
+            return Task::none();
+        };
+        self.install_running = Some(action);
+        self.install_progress = None;
+        self.download_progress = None;
+        with_reporter(move |reporter| async move {
+            match action {
+                InstallAction::Install => install::install(&session, &install_dir, runtime.as_ref(), &reporter).await?,
+                InstallAction::Update => install::update(&session, &install_dir, runtime.as_ref(), &reporter).await?,
+                InstallAction::Repair => install::repair(&session, &install_dir, runtime.as_ref(), &reporter).await?,
+                InstallAction::Uninstall => {
+                    install::uninstall(&install_dir)?;
+                    reporter.success(t!("game-uninstalled"));
+                    return Ok(());
+                }
+            }
+            let removed = versions::cleanup(&install_dir, keep, pinned.as_deref())?;
+            if !removed.is_empty() {
+                reporter.info(t!("old-builds-removed", count = removed.len()));
+            }
+            Ok(())
+        }, move |result| Message::InstallActionFinished(action, result))
+    }
+
+    // Picks up the website session of the last run, when credentials are remembered
+    fn restore_session(&mut self) {
+        if !self.settings.remember_password {
+            self.session = self.new_session();
+            return;
+        }
+        let restored = launcher_dir().and_then(|dir| {
+            Session::restore(self.settings.endpoints.clone(), &self.settings.http, &dir.join(SESSION_FILE_NAME))
+        });
+        match restored {
+            Ok(session) => self.session = session,
+            Err(err) => {
+                self.report_error(err, None);
+                self.session = self.new_session();
+            }
+        }
+    }
+
+    // Session without cookies for the configured website and connection settings
+    fn new_session(&mut self) -> Session {
+        match Session::new(self.settings.endpoints.clone(), &self.settings.http) {
+            Ok(session) => session,
+            Err(err) => {
+                // Unusable proxy settings: connect like the defaults do until they are fixed
+                self.report_error(err, None);
+                Session::new(self.settings.endpoints.clone(), &HttpOptions::default()).expect("default HTTP options are valid")
+            }
+        }
+    }
+
+    // Drops the cookies in memory and on disk
+    fn reset_session(&mut self) -> Result<(), LauncherError> {
+        self.session = self.new_session();
+        launcher_dir().and_then(|dir| session::forget(&dir.join(SESSION_FILE_NAME)))
+    }
+
+    // Brings the sign in state in line with what the website says about our session
+    fn check_session(&mut self, website_login: Option<String>) {
+        match website_login {
+            Some(login) if !self.has_signed_in => {
+                // Restored from the last run: the password is only known if it was remembered
+                if let Some((username, password)) = launcher_dir().ok().and_then(|dir| read_auth_file(&dir.join(AUTH_FILE_NAME))) {
+                    if username == login {
+                        self.password = password;
+                    }
+                }
+                self.username = login.clone();
+                self.signed_in_as = login;
+                self.has_signed_in = true;
+            }
+            None if self.session.has_cookies() => {
+                if self.has_signed_in {
+                    self.username = self.signed_in_as.clone();
+                }
+                self.has_signed_in = false;
+                self.signed_in_as.clear();
+                self.profile = None;
+                self.social = None;
+                self.session_expired = true;
+                self.notifications.log(Level::Warning, t!("session-expired"));
+                if let Err(err) = self.reset_session() {
+                    self.report_error(err, None);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_settings(&mut self, settings: Settings) {
+        self.scrollbar_width = settings.scrollbar_width;
+        self.scrollbar_margin = settings.scrollbar_margin;
+        self.scroller_width = settings.scroller_width;
+        i18n::set_language(settings.language);
+        self.settings = settings;
+        self.update_tray();
+    }
 
+    fn reload_palettes(&mut self) {
+        let custom = launcher_dir().and_then(|dir| theme::load_custom_palettes(&dir.join(THEMES_FILE_NAME)));
+        let mut palettes = Palette::built_in();
+        match custom {
+            Ok(custom) => {
+                for palette in &custom {
+                    for (pair, ratio) in palette.contrast_issues() {
+                        self.notifications.push(
+                            Level::Warning,
+                            t!("theme-low-contrast", theme = &palette.name, pair = pair, ratio = format!("{:.1}", ratio)),
+                        );
+                    }
+                }
+                // A custom palette with a built-in name replaces it
+                palettes.retain(|built_in| custom.iter().all(|palette| palette.name != built_in.name));
+                palettes.extend(custom);
+            }
+            Err(err) => self.report_error(err, Some(Message::ReloadThemes)),
         }
+        self.palettes = palettes;
+    }
+
+    // Palette picked in settings, previewing the Settings screen choice while it is open
+    fn palette(&self) -> &Palette {
+        let name = match &self.settings_draft {
+            Some(draft) => &draft.theme,
+            None => &self.settings.theme,
+        };
+        self.palettes
+            .iter()
+            .find(|palette| &palette.name == name)
+            .or_else(|| self.palettes.iter().find(|palette| palette.name == theme::DARK))
+            .unwrap_or(&self.palettes[0])
+    }
+
+    fn install_dir(&self) -> Result<PathBuf, LauncherError> {
+        launcher_dir().map(|dir| self.settings.install_dir(&dir))
+    }
+
+    // Credentials handed to the game: this session's sign in, or the remembered one
+    fn game_credentials(&self) -> Option<(String, String)> {
+        if self.has_signed_in {
+            return Some((self.signed_in_as.clone(), self.password.clone()));
+        }
+        if !self.settings.remember_password {
+            return None;
+        }
+        launcher_dir().ok().and_then(|dir| read_auth_file(&dir.join(AUTH_FILE_NAME)))
+    }
+
+    // Loads news page `page`, which becomes the current page once it arrives
+    fn load_news(&mut self, page: u8) -> Task<Message> {
+        self.loading_page = true;
+        let session = self.session.clone();
+        Task::perform(async move {
+            crate::fetch_news(&session, page).await
+        }, move |result| Message::NewsLoaded(page, result))
+    }
+
+    // Loads the map browser page currently selected
+    fn load_maps(&mut self) -> Task<Message> {
+        let Some(browser) = &mut self.map_browser else {
+            return Task::none();
+        };
+        browser.loading = true;
+        let session = self.session.clone();
+        let query = browser.query.clone();
+        let page = browser.page;
+        Task::perform(async move {
+            maps::fetch_maps(&session, &query, page).await
+        }, Message::MapsLoaded)
+    }
+
+    fn report_error(&mut self, err: LauncherError, retry: Option<Message>) {
+        eprintln!("{}", err);
+        self.notifications.log(Level::Error, err.to_string());
+        let retry = retry.filter(|_| err.is_retryable());
+        self.error = Some((err, retry));
     }
 
     fn parse_and_create_elements<'a>(&'a self, html_text: &'a str) -> Column<'a, Message> {
@@ -102,12 +611,12 @@ impl LauncherMainWindow {
 
             // Add text before the link
             if start > last_end {
-                elements = elements.push(Text::new(&html_text[last_end..start]).font(iced::Font::with_name("Segoe UI Emoji")));
+                elements = elements.push(Text::new(&html_text[last_end..start]).font(styles::named_font(&self.settings.text_font)));
             }
 
             // Create a clickable element for the link
-            let button = Button::new(Text::new(text).font(iced::Font::with_name("Segoe UI Emoji")))
-                .style(styles::transparent_button_hyperlink_style(&self.theme()))
+            let button = Button::new(Text::new(text).font(styles::named_font(&self.settings.text_font)))
+                .style(styles::transparent_button_hyperlink_style(self.palette()))
                 .on_press(Message::LinkClicked(url.clone()));
 
             let tooltip_button = Tooltip::new(button,
@@ -121,7 +630,7 @@ impl LauncherMainWindow {
 
         // Add any remaining text after the last link
         if last_end < html_text.len() {
-            elements = elements.push(Text::new(&html_text[last_end..]).font(iced::Font::with_name("Segoe UI Emoji")));
+            elements = elements.push(Text::new(&html_text[last_end..]).font(styles::named_font(&self.settings.text_font)));
         }
 
         elements
@@ -153,87 +662,501 @@ impl LauncherMainWindow {
 
             Message::SubmitLogin => {
                 self.show_login_form = false;
-                let message = format!("Username: {}, Password: {}", self.username, self.password);
                 let username = self.username.clone();
                 let password = self.password.clone();
-                let (sender, mut receiver) = std::sync::mpsc::channel();
-
+                // A new sign in starts from fresh cookies, so accounts never mix
+                self.session = self.new_session();
+                self.session_expired = false;
+                let session = self.session.clone();
 
-                tokio::spawn(async move {
-                    let (fetch_result, has_signed_in) = handle_login(&username, &password).await;
+                with_reporter(move |reporter| async move {
+                    reporter.info(t!("signing-in", name = &username));
+                    let (fetch_result, has_signed_in) = handle_login(&session, &username, &password).await?;
 
-                    let message_level = if has_signed_in {
-                        rfd::MessageLevel::Info
+                    if has_signed_in {
+                        reporter.success(fetch_result);
                     } else {
-                        rfd::MessageLevel::Error
-                    };
-
-                    println!("{}", fetch_result);
-                    rfd::MessageDialog::new()
-                        .set_title(&message)
-                        .set_description(&fetch_result)
-                        .set_level(message_level)
-                        .show();
-                    sender.send(has_signed_in).unwrap();
-                });
-                let has_signed_in = receiver.recv().unwrap();
-                self.has_signed_in = has_signed_in;
-                self.signed_in_as = self.username.clone();
-
-                if (self.has_signed_in)
-                {
-                    let exe_path = match std::env::current_exe() {
-                        Ok(path) => path,
-                        Err(err) => {
-                            eprintln!("Failed to get current executable path: {}", err);
-                            return Task::none();
-                        }
-                    };
-
-                    let auth_file_path = exe_path
-                        .parent()
-                        .expect("Unable to get parent of Launcher EXE path.")
-                        .join("Plazma Burst 2.auth")
-                        .canonicalize()
-                        .unwrap_or_else(|_| {
-                            eprintln!("Failed to get canonical path to auth file.");
-                            PathBuf::from("Plazma Burst 2.auth") // Fallback
-                        });
-
-                    let auth_file_path = auth_file_path
-                        .to_str()
-                        .map(|s| s.trim_start_matches(r"\\?\"))
-                        .unwrap_or_else(|| {
-                            eprintln!("Failed to convert auth file path to string.");
-                            "Plazma Burst 2.auth"
-                        })
-                        .to_string();
-
+                        reporter.error(fetch_result);
+                    }
+                    Ok((has_signed_in, username))
+                }, |result| match result {
+                    Ok((has_signed_in, username)) => Message::LoginCompleted(has_signed_in, username),
+                    Err(err) => Message::ErrorOccurred(err, Some(Box::new(Message::SubmitLogin))),
+                })
+            }
 
-                    if let Err(e) = write_auth_file(&auth_file_path, &self.username, &self.password) {
-                        eprintln!("Failed to write auth file: {}", e);
+            Message::PlayGamePressed => {
+                let credentials = self.game_credentials();
+                self.start_game(credentials, None, Message::PlayGamePressed)
+            }
+            Message::PlayAsGuestPressed => self.start_game(None, None, Message::PlayAsGuestPressed),
+            Message::OpenMaps => {
+                self.map_browser = Some(MapBrowser::default());
+                self.load_maps()
+            }
+            Message::CloseMaps => {
+                self.map_browser = None;
+                Task::none()
+            }
+            Message::MapQueryChanged(query) => {
+                if let Some(browser) = &mut self.map_browser {
+                    browser.query = query;
+                }
+                Task::none()
+            }
+            Message::SearchMaps => self.update(Message::MapsPageChanged(0)),
+            Message::MapsPageChanged(page) => {
+                if let Some(browser) = &mut self.map_browser {
+                    browser.page = page;
+                }
+                self.load_maps()
+            }
+            Message::MapsLoaded(result) => {
+                let Some(browser) = &mut self.map_browser else {
+                    return Task::none();
+                };
+                browser.loading = false;
+                match result {
+                    Ok(maps) => browser.results = maps,
+                    Err(err) => {
+                        let page = browser.page;
+                        self.report_error(err, Some(Message::MapsPageChanged(page)));
                     }
                 }
-
-
+                Task::none()
+            }
+            Message::PlayMap(map) => {
+                self.recent_maps.record(map.clone());
+                if let Err(err) = launcher_dir().and_then(|dir| self.recent_maps.save(&dir.join(RECENT_MAPS_FILE_NAME))) {
+                    self.report_error(err, None);
+                }
+                let credentials = self.game_credentials();
+                let retry = Message::PlayMap(map.clone());
+                self.start_game(credentials, Some(map), retry)
+            }
+            Message::OpenLink(link) => match Link::parse(&link) {
+                Ok(link) => self.update(link.message()),
+                Err(err) => {
+                    self.report_error(err, None);
+                    Task::none()
+                }
+            },
+            Message::InstanceActivated(links) => {
+                let tasks: Vec<_> = links.into_iter().map(|link| self.update(Message::OpenLink(link))).collect();
+                Task::batch([self.show_window(), Task::batch(tasks)])
+            }
+            Message::ShortcutPressed(shortcut) => self.handle_shortcut(shortcut),
+            Message::TrayActionSelected(action) => match action {
+                TrayAction::ShowWindow => self.show_window(),
+                TrayAction::Play => self.update(Message::PlayGamePressed),
+                TrayAction::PlayAsGuest => self.update(Message::PlayAsGuestPressed),
+                TrayAction::CheckForUpdates => Task::batch([self.show_window(), self.update(Message::CheckForUpdates)]),
+                TrayAction::LatestNews => Task::batch([self.show_window(), self.update(Message::ReloadNews)]),
+                TrayAction::Quit => window::get_latest().and_then(window::close),
+            },
+            Message::GameStarted => {
+                self.games_running += 1;
+                if !self.settings.hide_to_tray || self.tray.is_none() || self.hidden_to_tray {
+                    return Task::none();
+                }
+                self.hidden_to_tray = true;
+                window::get_latest().and_then(|id| window::change_mode(id, window::Mode::Hidden))
+            }
+            Message::GameExited => {
+                self.games_running = self.games_running.saturating_sub(1);
+                if self.games_running > 0 || !self.hidden_to_tray {
+                    return Task::none();
+                }
+                self.show_window()
+            }
+            Message::PlayGameFinished(result) => {
+                if let Err(err) = result {
+                    self.report_error(err, Some(Message::PlayGamePressed));
+                }
                 Task::none()
             }
 
-            Message::PlayGamePressed => {
-                tokio::spawn(async move {
-                    start_game_process().await;
-                });
+            // Removing the game files is asked for first
+            Message::InstallActionPressed(InstallAction::Uninstall) => {
+                self.uninstall_prompt = Some(false);
+                Task::none()
+            }
+            Message::InstallActionPressed(action) => self.run_install_action(action),
+            Message::InstallActionFinished(action, result) => {
+                self.install_running = None;
+                self.install_progress = None;
+                self.download_progress = None;
+                match result {
+                    Ok(()) => match action {
+                        InstallAction::Install | InstallAction::Update => {
+                            self.update_status = Some((Ok(UpdateStatus::UpToDate), Instant::now()));
+                        }
+                        InstallAction::Repair => {}
+                        InstallAction::Uninstall => {
+                            self.update_status = Some((Ok(UpdateStatus::NotInstalled), Instant::now()));
+                            // The pinned build is gone along with the others
+                            self.settings.game_version = None;
+                            if let Err(err) = launcher_dir().and_then(|dir| self.settings.save(&dir.join(SETTINGS_FILE_NAME))) {
+                                self.report_error(err, None);
+                            }
+                        }
+                    },
+                    Err(err) => self.report_error(err, Some(Message::InstallActionPressed(action))),
+                }
+                self.reload_versions();
+                Task::none()
+            }
+            Message::UninstallForgetSignIn(forget) => {
+                if let Some(forget_sign_in) = &mut self.uninstall_prompt {
+                    *forget_sign_in = forget;
+                }
+                Task::none()
+            }
+            Message::ConfirmUninstall => {
+                let Some(forget_sign_in) = self.uninstall_prompt.take() else {
+                    return Task::none();
+                };
+                let signed_out = if forget_sign_in { self.update(Message::Logout) } else { Task::none() };
+                Task::batch([signed_out, self.run_install_action(InstallAction::Uninstall)])
+            }
+            Message::CancelUninstall => {
+                self.uninstall_prompt = None;
+                Task::none()
+            }
+            Message::RuntimeSelected(runtime) => {
+                // Signed in accounts remember their own runtime, guests change the default
+                if self.has_signed_in {
+                    self.settings.account_runtimes.insert(self.signed_in_as.clone(), runtime);
+                } else {
+                    self.settings.runtime = runtime;
+                }
+                if let Some(draft) = &mut self.settings_draft {
+                    draft.runtime = self.settings.runtime;
+                    draft.account_runtimes = self.settings.account_runtimes.clone();
+                }
+                if let Err(err) = launcher_dir().and_then(|dir| self.settings.save(&dir.join(SETTINGS_FILE_NAME))) {
+                    self.report_error(err, None);
+                }
+                // The install state covers the player of the picked runtime
+                self.reload_versions();
+                Task::none()
+            }
+            Message::GameVersionSelected(choice) => {
+                let version = choice.into_setting();
+                if let Some(draft) = &mut self.settings_draft {
+                    draft.game_version = version.clone();
+                }
+                self.settings.game_version = version;
+                if let Err(err) = launcher_dir().and_then(|dir| self.settings.save(&dir.join(SETTINGS_FILE_NAME))) {
+                    self.report_error(err, None);
+                }
+                Task::none()
+            }
+            Message::ReloadNews => self.load_news(self.current_page),
+            Message::NewsLoaded(page, result) => {
+                self.loading_page = false;
+                match result {
+                    Ok((count, news, website_login)) => {
+                        self.current_page = page;
+                        self.news_pages_count = count;
+                        self.news = Some(news);
+                        self.check_session(website_login);
+                    }
+                    Err(err) if page == self.current_page => self.report_error(err, Some(Message::ReloadNews)),
+                    Err(err) => self.report_error(err, Some(Message::PageChanged(page))),
+                }
+                Task::none()
+            }
+            Message::RefreshNews => {
+                let session = self.session.clone();
+                let page = self.current_page;
+                Task::perform(async move {
+                    crate::fetch_news(&session, page).await
+                }, move |result| Message::NewsRefreshed(page, result))
+            }
+            Message::NewsRefreshed(page, result) => {
+                match result {
+                    // The user switched pages meanwhile, the page shown was loaded just now or is on its way
+                    Ok(_) if page != self.current_page || self.loading_page => {}
+                    Ok((count, news, website_login)) => {
+                        // Only the first page has the latest news
+                        let known = self.news.as_ref().and_then(|news| news.first());
+                        if page == 0 && known.is_some() && known != news.first() {
+                            if let Some((date, _)) = news.first() {
+                                self.notifications.push(Level::Info, t!("news-new", date = date));
+                            }
+                        }
+                        self.news_pages_count = count;
+                        self.news = Some(news);
+                        self.check_session(website_login);
+                    }
+                    Err(err) => {
+                        eprintln!("{}", err);
+                        self.notifications.log(Level::Warning, err.to_string());
+                    }
+                }
+                Task::none()
+            }
+            Message::CheckForUpdates => {
+                if self.checking_updates {
+                    return Task::none();
+                }
+                let install_dir = match self.install_dir() {
+                    Ok(dir) => dir,
+                    Err(err) => {
+                        self.update_status = Some((Err(err), Instant::now()));
+                        return Task::none();
+                    }
+                };
+                self.checking_updates = true;
+                let session = self.session.clone();
+                Task::perform(async move {
+                    install::check_for_update(&session, &install_dir).await
+                }, Message::UpdateChecked)
+            }
+            Message::UpdateChecked(result) => {
+                self.checking_updates = false;
+                match &result {
+                    // Announced once per build, not on every check while it waits
+                    Ok(UpdateStatus::Available(build))
+                        if !matches!(&self.update_status, Some((Ok(UpdateStatus::Available(known)), _)) if known == build) =>
+                    {
+                        self.notifications.push(Level::Info, t!("update-available", build = build));
+                    }
+                    Err(err) => self.notifications.log(Level::Warning, t!("update-check-failed", reason = err.to_string())),
+                    _ => {}
+                }
+                self.update_status = Some((result, Instant::now()));
+                Task::none()
+            }
+            Message::CheckServerStatus => {
+                if self.checking_status {
+                    return Task::none();
+                }
+                self.checking_status = true;
+                let session = self.session.clone();
+                Task::perform(async move {
+                    status::check(&session).await
+                }, Message::ServerStatusChecked)
+            }
+            Message::ServerStatusChecked(report) => {
+                self.checking_status = false;
+                // Outages are recorded when they start, not on every check while they last
+                let was_up = self.server_status.as_ref().is_none_or(StatusReport::all_up);
+                if was_up && !report.all_up() {
+                    let down: Vec<String> = report
+                        .services
+                        .iter()
+                        .filter(|status| matches!(status.health, Health::Down(_)))
+                        .map(|status| status.service.label())
+                        .collect();
+                    self.notifications.log(Level::Warning, t!("services-down", services = down.join(", ")));
+                }
+                self.server_status = Some(report);
+                Task::none()
+            }
+            Message::ErrorOccurred(err, retry) => {
+                self.report_error(err, retry.map(|retry| *retry));
+                Task::none()
+            }
+            Message::RetryPressed => {
+                match self.error.take() {
+                    Some((_, Some(retry))) => self.update(retry),
+                    _ => Task::none(),
+                }
+            }
+            Message::DismissError => {
+                self.error = None;
+                Task::none()
+            }
+            Message::ReloadThemes => {
+                self.reload_palettes();
+                Task::none()
+            }
+            Message::OpenSettings => {
+                self.settings_draft = Some(self.settings.clone());
+                Task::none()
+            }
+            Message::SettingChanged(setting) => {
+                if let Some(draft) = &mut self.settings_draft {
+                    draft.apply(setting);
+                }
+                Task::none()
+            }
+            Message::BrowseInstallDir => {
+                let title = t!("choose-install-dir");
+                Task::perform(async move {
+                    rfd::AsyncFileDialog::new()
+                        .set_title(title)
+                        .pick_folder()
+                        .await
+                        .map(|folder| folder.path().to_path_buf())
+                }, Message::InstallDirPicked)
+            }
+            Message::InstallDirPicked(folder) => {
+                // Nothing changes when the dialog was cancelled
+                if let (Some(draft), Some(folder)) = (&mut self.settings_draft, folder) {
+                    draft.apply(Setting::InstallDir(Some(folder)));
+                }
+                Task::none()
+            }
+            Message::BrowsePlayer => {
+                let title = t!("choose-flash-player");
+                Task::perform(async move {
+                    rfd::AsyncFileDialog::new()
+                        .set_title(title)
+                        .pick_file()
+                        .await
+                        .map(|file| file.path().to_path_buf())
+                }, Message::PlayerPicked)
+            }
+            Message::PlayerPicked(path) => {
+                let (Some(draft), Some(path)) = (&mut self.settings_draft, path) else {
+                    return Task::none();
+                };
+                // Only a player that can be started is saved
+                match runtime::validate_player(&path) {
+                    Ok(_) => draft.apply(Setting::PlayerPath(Some(path))),
+                    Err(err) => self.report_error(err, None),
+                }
+                Task::none()
+            }
+            Message::DetectPlayers => {
+                let players = runtime::detect_flash_players();
+                match (&mut self.settings_draft, players.first()) {
+                    (Some(draft), Some(player)) => {
+                        draft.apply(Setting::PlayerPath(Some(player.clone())));
+                        self.notifications.push(Level::Success, t!("players-detected", count = players.len(), path = player.display().to_string()));
+                    }
+                    (_, None) => {
+                        self.notifications.push(Level::Warning, t!("no-player-detected"));
+                    }
+                    (None, Some(_)) => {}
+                }
+                Task::none()
+            }
+            Message::AddToAppMenu => {
+                match desktop::integrate() {
+                    Ok(_) => {
+                        self.notifications.push(Level::Success, t!("added-to-app-menu"));
+                    }
+                    Err(err) => self.report_error(err, None),
+                }
                 Task::none()
             }
+            Message::ResetSettings => {
+                self.settings_draft = Some(Settings::default());
+                Task::none()
+            }
+            Message::CancelSettings => {
+                self.settings_draft = None;
+                Task::none()
+            }
+            Message::ApplySettings => {
+                let Some(mut settings) = self.settings_draft.take() else {
+                    return Task::none();
+                };
+                settings.normalize();
+                if let Err(err) = settings.endpoints.validate() {
+                    // Keep the form open so the address can be corrected
+                    self.notifications.push(Level::Error, err.to_string());
+                    self.settings_draft = Some(settings);
+                    return Task::none();
+                }
 
-            Message::DownloadGamePressed => {
-                tokio::spawn(async move {
-                    handle_download_game().await;
+                let saved = launcher_dir().and_then(|dir| {
+                    settings.save(&dir.join(SETTINGS_FILE_NAME))?;
+                    let auth_file = dir.join(AUTH_FILE_NAME);
+                    if !settings.remember_password && auth_file.exists() {
+                        fs::remove_file(&auth_file).context(t!("error-remove-credentials"))?;
+                    }
+                    if !settings.remember_password {
+                        session::forget(&dir.join(SESSION_FILE_NAME))?;
+                    }
+                    Ok(())
                 });
+                if let Err(err) = saved {
+                    self.report_error(err, None);
+                }
+
+                let endpoints_changed = settings.endpoints != self.settings.endpoints;
+                let http_changed = settings.http != self.settings.http;
+                self.apply_settings(settings);
+                // The install directory or the player may have changed
+                self.reload_versions();
+                let news = if endpoints_changed {
+                    // Cookies belong to the old website
+                    self.session = self.new_session();
+                    self.current_page = 0;
+                    self.load_news(0)
+                } else if http_changed {
+                    self.restore_session();
+                    self.load_news(self.current_page)
+                } else {
+                    Task::none()
+                };
+                self.notifications.push(Level::Success, t!("settings-applied"));
+                news
+            }
+            Message::Notify(level, text) => {
+                if self.install_running.is_some() && level != Level::Error {
+                    self.install_progress = Some(text.clone());
+                    self.download_progress = None;
+                }
+                self.notifications.push(level, text);
+                Task::none()
+            }
+            Message::DownloadProgress(progress) => {
+                if self.install_running.is_some() {
+                    self.download_progress = Some(progress);
+                }
+                Task::none()
+            }
+            Message::DismissNotification(id) => {
+                self.notifications.dismiss(id);
+                Task::none()
+            }
+            Message::NotificationTick(now) => {
+                self.notifications.expire(now);
+                Task::none()
+            }
+            Message::ToggleNotificationHistory => {
+                self.show_notification_history = !self.show_notification_history;
+                Task::none()
+            }
+            Message::ClearNotificationHistory => {
+                self.notifications.clear_history();
                 Task::none()
             }
             Message::LoginPressed => {
                 self.show_login_form = true;
+                self.session_expired = false;
+                text_input::focus(USERNAME_INPUT_ID.clone())
+            }
+            Message::Logout => {
+                self.has_signed_in = false;
+                self.signed_in_as.clear();
+                self.password.clear();
+                self.profile = None;
+                self.social = None;
+                self.session_expired = false;
+                let removed = self.reset_session().and_then(|_| {
+                    let auth_file = launcher_dir()?.join(AUTH_FILE_NAME);
+                    if auth_file.exists() {
+                        fs::remove_file(&auth_file).context(t!("error-remove-credentials"))?;
+                    }
+                    Ok(())
+                });
+                match removed {
+                    Ok(()) => {
+                        self.notifications.push(Level::Info, t!("signed-out"));
+                    }
+                    Err(err) => self.report_error(err, None),
+                }
+                Task::none()
+            }
+            Message::DismissSessionExpired => {
+                self.session_expired = false;
                 Task::none()
             }
             Message::LoginCancel => {
@@ -246,20 +1169,89 @@ impl LauncherMainWindow {
 
                     self.signed_in_as = username;
 
-
+                    if self.settings.remember_password {
+                        let written = launcher_dir().and_then(|dir| {
+                            write_auth_file(&dir.join(AUTH_FILE_NAME), &self.username, &self.password)
+                                .context(t!("error-save-credentials"))?;
+                            self.session.save(&dir.join(SESSION_FILE_NAME))
+                        });
+                        if let Err(err) = written {
+                            self.report_error(err, None);
+                        }
+                    }
+                    Task::batch([self.update(Message::RefreshProfile), self.update(Message::RefreshSocial)])
                 } else {
                     self.signed_in_as.clear();
+                    self.profile = None;
+                    self.social = None;
+                    // Tells an outage apart from wrong credentials
+                    self.update(Message::CheckServerStatus)
+                }
+            }
+            Message::RefreshProfile => {
+                if !self.has_signed_in || self.loading_profile {
+                    return Task::none();
+                }
+                self.loading_profile = true;
+                let session = self.session.clone();
+                let login = self.signed_in_as.clone();
+                let password = self.password.clone();
+                Task::perform(async move {
+                    profile::fetch_profile(&session, &login, &password).await
+                }, Message::ProfileLoaded)
+            }
+            Message::ProfileLoaded(result) => {
+                self.loading_profile = false;
+                match result {
+                    Ok(profile) => self.profile = Some(profile),
+                    Err(err) => self.report_error(err, Some(Message::RefreshProfile)),
                 }
                 Task::none()
             }
-            Message::PageChanged(page_number) if page_number != self.current_page => {
-                self.loading_page = true;
-                self.current_page = page_number;
-                self.news = crate::get_news_and_dates_by_page_number(page_number).ok();
-                self.loading_page = false;
+            Message::ToggleSocial => {
+                self.show_social = !self.show_social;
+                if self.show_social && self.social.is_none() {
+                    return self.update(Message::RefreshSocial);
+                }
                 Task::none()
-
-            },
+            }
+            Message::RefreshSocial => {
+                // Friends and messages need the website session, a standalone launcher sign in has none
+                if !self.has_signed_in || !self.session.has_cookies() || self.loading_social {
+                    return Task::none();
+                }
+                self.loading_social = true;
+                let session = self.session.clone();
+                Task::perform(async move {
+                    social::fetch_social(&session).await
+                }, Message::SocialLoaded)
+            }
+            Message::SocialLoaded(result) => {
+                self.loading_social = false;
+                match result {
+                    Ok(Some(social)) => {
+                        match &self.social {
+                            Some(previous) => {
+                                for message in social.inbox.new_since(&previous.inbox) {
+                                    self.notifications.push(Level::Info, t!("new-message", from = &message.from, subject = &message.subject));
+                                }
+                            }
+                            None => {
+                                let unread = social.inbox.unread_count();
+                                if unread > 0 {
+                                    self.notifications.push(Level::Info, t!("unread-messages", count = unread));
+                                }
+                            }
+                        }
+                        self.social = Some(social);
+                    }
+                    // The pages were served to a guest
+                    Ok(None) => self.check_session(None),
+                    Err(err) => self.report_error(err, Some(Message::RefreshSocial)),
+                }
+                Task::none()
+            }
+            Message::PageChanged(page_number) if page_number != self.current_page => self.load_news(page_number),
             Message::SwitchDirection(direction) => {
                 self.current_scroll_offset = scrollable::RelativeOffset::START;
                 self.scrollable_direction = direction;
@@ -319,7 +1311,162 @@ impl LauncherMainWindow {
     }
 
     pub fn view(&self) -> Element<Message> {
+        let mut page = Column::new();
+        if let Some((err, retry)) = &self.error {
+            page = page.push(self.error_banner(err, retry.is_some()));
+        }
+        if self.session_expired {
+            page = page.push(self.session_expired_banner());
+        }
+        if self.show_notification_history {
+            page = page.push(self.notification_history());
+        } else if self.show_social && self.has_signed_in {
+            page = page.push(self.social_panel());
+        } else {
+            page = page.push(self.content_view());
+        }
+        page.push(self.toasts()).into()
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        let mut subscriptions = Vec::new();
+        // Only tick while there is a toast to expire
+        if self.notifications.has_active() {
+            subscriptions.push(iced::time::every(Duration::from_millis(500)).map(Message::NotificationTick));
+        }
+        // First check right after start, then on the regular interval
+        let status_interval = if self.server_status.is_some() { STATUS_INTERVAL } else { Duration::from_secs(1) };
+        subscriptions.push(iced::time::every(status_interval).map(|_| Message::CheckServerStatus));
+        if self.has_signed_in && self.session.has_cookies() {
+            subscriptions.push(iced::time::every(SOCIAL_INTERVAL).map(|_| Message::RefreshSocial));
+        }
+        // Paused while the game runs, the update check runs again soon after it exits
+        if self.games_running == 0 {
+            if self.settings.update_check_interval > 0 {
+                let interval = Duration::from_secs(u64::from(self.settings.update_check_interval) * 60);
+                subscriptions.push(
+                    scheduler::every(Job::UpdateCheck, scheduler::FIRST_UPDATE_CHECK, interval).map(|_| Message::CheckForUpdates),
+                );
+            }
+            if self.settings.news_refresh_interval > 0 {
+                let interval = Duration::from_secs(u64::from(self.settings.news_refresh_interval) * 60);
+                subscriptions.push(scheduler::every(Job::NewsRefresh, interval, interval).map(|_| Message::RefreshNews));
+            }
+        }
+        subscriptions.push(Subscription::run_with_id("instance", self.instance_requests.stream()).map(Message::InstanceActivated));
+        if let Some(tray) = &self.tray {
+            subscriptions.push(Subscription::run_with_id("tray", tray.actions()).map(Message::TrayActionSelected));
+        }
+        subscriptions.push(event::listen_with(|event, status, _| match event {
+            Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
+                shortcuts::shortcut(&key, modifiers, status == event::Status::Captured).map(Message::ShortcutPressed)
+            }
+            _ => None,
+        }));
+        Subscription::batch(subscriptions)
+    }
+
+    fn toasts(&self) -> Element<Message> {
+        self.notifications
+            .active()
+            .fold(Column::new().spacing(5).padding([0, 20]), |column, notification| {
+                let toast = Row::new()
+                    .spacing(10)
+                    .push(Text::new(notification.text.as_str()).width(Length::Fill))
+                    .push(Button::new(Text::new(t!("close"))).on_press(Message::DismissNotification(notification.id)));
+                column.push(
+                    Container::new(toast)
+                        .padding(10)
+                        .width(Length::Fill)
+                        .style(styles::notification_style(self.palette(), notification.level)),
+                )
+            })
+            .into()
+    }
+
+    fn notification_history(&self) -> Element<Message> {
+        let now = Instant::now();
+        let entries = self.notifications.history().fold(Column::new().spacing(5), |column, notification| {
+            column.push(
+                Row::new()
+                    .spacing(10)
+                    .push(Text::new(notification.level.label()).width(80)
+                        .color(styles::notification_color(self.palette(), notification.level)))
+                    .push(Text::new(notification.text.as_str()).width(Length::Fill))
+                    .push(Text::new(notifications::age(notification.created, now))),
+            )
+        });
+
+        let buttons = Row::new()
+            .spacing(10)
+            .push(Button::new(Text::new(t!("clear"))).on_press(Message::ClearNotificationHistory))
+            .push(Button::new(Text::new(t!("back"))).on_press(Message::ToggleNotificationHistory));
+
+        Container::new(
+            Column::new()
+                .spacing(10)
+                .push(Text::new(t!("notifications")).size(25))
+                .push(Scrollable::new(entries).width(Length::Fill).height(Length::Fill))
+                .push(buttons),
+        )
+        .padding(20)
+        .into()
+    }
+
+    fn error_banner<'a>(&self, err: &'a LauncherError, can_retry: bool) -> Element<'a, Message> {
+        let mut banner = Row::new()
+            .spacing(10)
+            .push(Text::new(err.to_string()).width(Length::Fill));
+        if can_retry {
+            banner = banner.push(Button::new(Text::new(t!("retry"))).on_press(Message::RetryPressed));
+        }
+        banner = banner.push(Button::new(Text::new(t!("dismiss"))).on_press(Message::DismissError));
+
+        Container::new(banner)
+            .padding(10)
+            .width(Length::Fill)
+            .style(styles::error_banner_style(self.palette()))
+            .into()
+    }
+
+    fn session_expired_banner(&self) -> Element<Message> {
+        let banner = Row::new()
+            .spacing(10)
+            .push(Text::new(t!("session-expired-banner")).width(Length::Fill))
+            .push(Button::new(Text::new(t!("sign-in-again"))).on_press(Message::LoginPressed))
+            .push(Button::new(Text::new(t!("dismiss"))).on_press(Message::DismissSessionExpired));
+
+        Container::new(banner)
+            .padding(10)
+            .width(Length::Fill)
+            .style(styles::notification_style(self.palette(), Level::Warning))
+            .into()
+    }
+
+    fn content_view(&self) -> Element<Message> {
+
 
+        if let Some(draft) = &self.settings_draft {
+            return Container::new(self.create_settings_form(draft))
+                .padding(20)
+                .center_x(Length::Fill)
+                .into();
+        }
+
+        if let Some(browser) = &self.map_browser {
+            return Container::new(self.map_browser_view(browser))
+                .padding(20)
+                .center_x(Length::Fill)
+                .into();
+        }
+
+        if let Some(forget_sign_in) = self.uninstall_prompt {
+            return Container::new(self.uninstall_prompt_view(forget_sign_in))
+                .padding(20)
+                .center_x(Length::Fill)
+                .center_y(Length::Fill)
+                .into();
+        }
 
         if self.show_login_form {
             let login_form = self.create_login_form();
@@ -338,9 +1485,9 @@ impl LauncherMainWindow {
             if let Some(news) = &self.news {
                 for (date, text) in news {
                     let text_date = Text::new(date)
-                        .font(iced::Font::with_name("Verdana"))
+                        .font(styles::named_font(&self.settings.news_font))
                         .size(25)
-                        .color([0.58, 0.75, 0.95])
+                        .color(self.palette().news_date)
                         .width(Length::Fill)
                         .height(Length::Shrink);
 
@@ -360,9 +1507,9 @@ impl LauncherMainWindow {
             let buttons_row = (1..=self.news_pages_count).fold(buttons_row, |row, i| {
                 let mut button = Button::new(Text::new(i.to_string()));
                 if self.current_page == i - 1 {
-                    button = button.style(styles::news_pages_selected_button_style(&self.theme()));
+                    button = button.style(styles::news_pages_selected_button_style(self.palette()));
                 } else {
-                    button = button.style(styles::news_pages_switch_button_style(&self.theme()));
+                    button = button.style(styles::news_pages_switch_button_style(self.palette()));
                 }
                 if !self.loading_page {
                     button = button.on_press(Message::PageChanged(i - 1));
@@ -374,11 +1521,18 @@ impl LauncherMainWindow {
                 buttons_row,
                 scrollable::Direction::Horizontal(
                     scrollable::Properties::new()
-                        .scroller_width(10)
+                        .scroller_width(self.scroller_width)
                 )).width(Length::Fill).height(70);
 
 
-        let scrollable_content = Scrollable::new(content)
+        let scrollable_content = Scrollable::with_direction(
+                content,
+                scrollable::Direction::Vertical(
+                    scrollable::Properties::new()
+                        .width(self.scrollbar_width)
+                        .margin(self.scrollbar_margin)
+                        .scroller_width(self.scroller_width)
+                ))
             .width(Length::Fill)
             .height(Length::Fill)
             .id(SCROLLABLE_ID.clone())
@@ -390,48 +1544,387 @@ impl LauncherMainWindow {
             .push(scrollable_content)
             .push(scrollable_buttons);
 
-            println!("{}", self.has_signed_in);
             let signed_in_text = if self.has_signed_in {
-                Text::new(format!("Signed in as: {}", self.signed_in_as)).font(iced::Font::with_name("Segoe UI Emoji"))
+                Text::new(t!("signed-in-as", name = &self.signed_in_as)).font(styles::named_font(&self.settings.text_font))
             } else {
-                Text::new("Not signed in").font(iced::Font::with_name("Segoe UI Emoji"))
+                Text::new(t!("not-signed-in")).font(styles::named_font(&self.settings.text_font))
             };
 
-        let login_button = Button::new(Text::new("Login")).on_press(Message::LoginPressed);
-
-        let download_game_button = Button::new(Text::new("Download Game"))
-                .on_press(Message::DownloadGamePressed);
+        let login_button = if self.has_signed_in {
+            Button::new(Text::new(t!("logout"))).on_press(Message::Logout)
+        } else {
+            Button::new(Text::new(t!("login"))).on_press(Message::LoginPressed)
+        };
 
-        let play_game_button = Button::new(Text::new("Play Game"))
+        let play_game_button = Button::new(Text::new(t!("play-game")))
                 .on_press(Message::PlayGamePressed);
 
+        let runtime = self.runtime();
+        let runtime_picker = PickList::new(RuntimeKind::supported(&get_platform(), &self.settings), runtime, Message::RuntimeSelected);
+
+        // Picking an older build rolls back to it until Latest is picked again
+        let mut version_choices = vec![VersionChoice::Latest];
+        version_choices.extend(self.versions.iter().map(|version| VersionChoice::Pinned(version.id.clone())));
+        let version_picker = PickList::new(
+            version_choices,
+            Some(VersionChoice::from_setting(self.settings.game_version.as_deref())),
+            Message::GameVersionSelected,
+        );
+
+        let maps_button = Button::new(Text::new(t!("maps")))
+                .on_press(Message::OpenMaps);
+
+        let unread = self.social.as_ref().map_or(0, |social| social.inbox.unread_count());
+        let social_button = Button::new(Text::new(if unread > 0 { t!("friends-with-new", count = unread) } else { t!("friends") }))
+                .on_press(Message::ToggleSocial);
+
+        let notifications_button = Button::new(Text::new(t!("notifications")))
+                .on_press(Message::ToggleNotificationHistory);
+
+        let settings_button = Button::new(Text::new(t!("settings")))
+                .on_press(Message::OpenSettings);
+
         let button_row = Row::new()
                 .spacing(10)
                 .push(login_button)
-                .push(download_game_button)
-                .push(play_game_button);
-        let displayed = final_content
-            .push(signed_in_text)
-            .push(button_row);
+                .push(play_game_button)
+                .push_maybe((!self.versions.is_empty()).then_some(version_picker))
+                .push_maybe(runtime.is_some().then_some(runtime_picker))
+                .push(maps_button)
+                .push_maybe((self.has_signed_in && self.session.has_cookies()).then_some(social_button))
+                .push(notifications_button)
+                .push(settings_button);
+        let mut displayed = final_content
+            .push(self.status_bar())
+            .push(self.install_bar())
+            .push_maybe((self.install_state != InstallState::NotInstalled).then(|| self.update_bar()))
+            .push(signed_in_text);
+        if self.has_signed_in {
+            displayed = displayed.push(self.profile_card());
+        }
+        let displayed = displayed
+            .push(button_row)
+            .push_maybe(runtime.is_none().then(|| {
+                Container::new(Text::new(t!("no-runtime", platform = get_platform().to_string())))
+                    .padding([5, 10])
+                    .style(styles::notification_style(self.palette(), Level::Error))
+            }));
 
             Container::new(displayed).padding(20).into()
         }
     }
 
+    fn status_bar(&self) -> Element<Message> {
+        let mut bar = Row::new().spacing(10);
+        match &self.server_status {
+            Some(report) => {
+                for status in &report.services {
+                    let (level, detail) = match &status.health {
+                        Health::Up(latency) => (Level::Success, t!("service-latency", ms = latency.as_millis().to_string())),
+                        Health::Down(reason) => (Level::Error, t!("service-down", reason = reason.to_string())),
+                    };
+                    bar = bar.push(
+                        Container::new(Text::new(format!("{}: {}", status.service.label(), detail)).size(14))
+                            .padding([2, 8])
+                            .style(styles::notification_style(self.palette(), level)),
+                    );
+                }
+                let mut activity = Vec::new();
+                if let Some(players) = report.stats.players_online {
+                    activity.push(t!("players-online", count = players));
+                }
+                if let Some(servers) = report.stats.active_servers {
+                    activity.push(t!("active-servers", count = servers));
+                }
+                if !activity.is_empty() {
+                    bar = bar.push(Text::new(activity.join(", ")).size(14));
+                }
+            }
+            None => bar = bar.push(Text::new(t!("checking-status")).size(14)),
+        }
+
+        let mut check_button = Button::new(Text::new(if self.checking_status { t!("checking") } else { t!("check-now") }).size(14));
+        if !self.checking_status {
+            check_button = check_button.on_press(Message::CheckServerStatus);
+        }
+        bar.push(check_button).into()
+    }
+
+    // State of the game files, with the actions that apply to it
+    fn install_bar(&self) -> Element<'_, Message> {
+        let (level, mut text) = match (self.install_running, &self.install_state) {
+            (Some(action), _) => (Level::Info, self.install_progress.clone().unwrap_or_else(|| action.running_label())),
+            (None, InstallState::NotInstalled) => (Level::Warning, t!("install-state-not-installed")),
+            (None, InstallState::Installed(build)) => (Level::Success, t!("install-state-installed", build = build)),
+            (None, InstallState::Damaged(problems)) => (Level::Error, t!("install-state-damaged", count = problems.len())),
+        };
+        let megabytes = |bytes: u64| format!("{:.1}", bytes as f64 / (1024.0 * 1024.0));
+        let progress = self.download_progress.filter(|_| self.install_running.is_some());
+        if let Some(Progress { received, total }) = progress {
+            text = match total {
+                Some(total) => t!("download-progress", step = text, received = megabytes(received), total = megabytes(total)),
+                None => t!("download-progress-unknown", step = text, received = megabytes(received)),
+            };
+        }
+        let mut state = Column::new().spacing(4).push(Text::new(text).size(14));
+        if let Some(Progress { received, total: Some(total) }) = progress {
+            state = state.push(ProgressBar::new(0.0..=total as f32, received as f32).height(6));
+        }
+        let state = Container::new(state)
+            .padding([2, 8])
+            .style(styles::notification_style(self.palette(), level));
+        // What is damaged shows on hover
+        let state: Element<Message> = match &self.install_state {
+            InstallState::Damaged(problems) if self.install_running.is_none() => {
+                Tooltip::new(state, Text::new(problems.join("\n")).size(14), tooltip::Position::Bottom).into()
+            }
+            _ => state.into(),
+        };
+
+        let actions: &[InstallAction] = match &self.install_state {
+            InstallState::NotInstalled => &[InstallAction::Install],
+            InstallState::Installed(_) => &[InstallAction::Update, InstallAction::Repair, InstallAction::Uninstall],
+            InstallState::Damaged(_) => &[InstallAction::Repair, InstallAction::Uninstall],
+        };
+        actions.iter().fold(Row::new().spacing(10).push(state), |row, action| {
+            let mut button = Button::new(Text::new(action.label()).size(14));
+            if self.install_running.is_none() {
+                button = button.on_press(Message::InstallActionPressed(*action));
+            }
+            row.push(button)
+        })
+        .into()
+    }
+
+    // Confirmation of Uninstall, the saved sign in is only forgotten when asked to
+    fn uninstall_prompt_view(&self, forget_sign_in: bool) -> Element<'_, Message> {
+        let install_dir = self.install_dir().map(|dir| dir.display().to_string()).unwrap_or_default();
+        Column::new()
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .push(Text::new(t!("uninstall-title")).size(24))
+            .push(Text::new(t!("uninstall-question", dir = install_dir)))
+            .push(Checkbox::new(t!("uninstall-forget-sign-in"), forget_sign_in).on_toggle(Message::UninstallForgetSignIn))
+            .push(Row::new()
+                .spacing(10)
+                .push(Button::new(Text::new(t!("uninstall-game"))).on_press(Message::ConfirmUninstall))
+                .push(Button::new(Text::new(t!("cancel"))).on_press(Message::CancelUninstall)))
+            .into()
+    }
+
+    // Result of the last update check, with a button checking again
+    fn update_bar(&self) -> Element<'_, Message> {
+        let status = if self.checking_updates {
+            Some((Level::Info, t!("update-checking")))
+        } else {
+            self.update_status.as_ref().and_then(|(result, checked)| match result {
+                Ok(UpdateStatus::UpToDate) => Some((Level::Success, t!("update-current", when = notifications::age(*checked, Instant::now())))),
+                Ok(UpdateStatus::Available(build)) => Some((Level::Warning, t!("update-available", build = build))),
+                // The install state already says so
+                Ok(UpdateStatus::NotInstalled) => None,
+                Err(err) => Some((Level::Error, t!("update-check-failed", reason = err.to_string()))),
+            })
+        };
+
+        let mut check_button = Button::new(Text::new(if self.checking_updates { t!("checking") } else { t!("check-now") }).size(14));
+        if !self.checking_updates {
+            check_button = check_button.on_press(Message::CheckForUpdates);
+        }
+        // The check only looks, installing the new build is left to Update
+        let update_button = matches!(&self.update_status, Some((Ok(UpdateStatus::Available(_)), _))).then(|| {
+            let mut button = Button::new(Text::new(InstallAction::Update.label()).size(14));
+            if self.install_running.is_none() {
+                button = button.on_press(Message::InstallActionPressed(InstallAction::Update));
+            }
+            button
+        });
+        Row::new()
+            .spacing(10)
+            .push_maybe(status.map(|(level, text)| {
+                Container::new(Text::new(text).size(14))
+                    .padding([2, 8])
+                    .style(styles::notification_style(self.palette(), level))
+            }))
+            .push_maybe(update_button)
+            .push(check_button)
+            .into()
+    }
+
+    fn social_panel(&self) -> Element<Message> {
+        let mut refresh_button = Button::new(Text::new(if self.loading_social { t!("loading") } else { t!("refresh") }));
+        if !self.loading_social {
+            refresh_button = refresh_button.on_press(Message::RefreshSocial);
+        }
+        let header = Row::new()
+            .spacing(10)
+            .push(Text::new(t!("social-title")).size(25).width(Length::Fill))
+            .push(refresh_button)
+            .push(Button::new(Text::new(t!("close"))).on_press(Message::ToggleSocial));
+
+        let mut friends = Column::new().spacing(5).push(Text::new(t!("friends")).size(20));
+        let mut inbox = Column::new().spacing(5);
+        match &self.social {
+            Some(social) => {
+                if social.friends.is_empty() {
+                    friends = friends.push(Text::new(t!("no-friends")));
+                }
+                for friend in &social.friends {
+                    let status: Element<Message> = if friend.online {
+                        Container::new(Text::new(t!("online")).size(14))
+                            .padding([2, 8])
+                            .style(styles::notification_style(self.palette(), Level::Success))
+                            .into()
+                    } else {
+                        let last_seen = friend.last_seen.as_ref().map(|seen| t!("last-seen-at", when = seen));
+                        Text::new(last_seen.unwrap_or_else(|| t!("offline"))).size(14).into()
+                    };
+                    friends = friends.push(Row::new().spacing(10).push(Text::new(friend.login.as_str()).width(Length::Fill)).push(status));
+                }
+
+                inbox = inbox.push(Text::new(t!("inbox-with-unread", count = social.inbox.unread_count())).size(20));
+                if social.inbox.messages.is_empty() {
+                    inbox = inbox.push(Text::new(t!("no-messages")));
+                }
+                for message in &social.inbox.messages {
+                    inbox = inbox.push(Row::new()
+                        .spacing(10)
+                        .push(Text::new(if message.unread { t!("message-new") } else { String::new() }).width(40))
+                        .push(Text::new(message.from.as_str()).width(Length::FillPortion(1)))
+                        .push(Text::new(message.subject.as_str()).width(Length::FillPortion(3)))
+                        .push(Text::new(message.date.clone().unwrap_or_default()).width(120)));
+                }
+            }
+            None => {
+                friends = friends.push(Text::new(if self.loading_social { t!("loading") } else { t!("nothing-loaded") }));
+                inbox = inbox.push(Text::new(t!("inbox")).size(20));
+            }
+        }
+
+        Container::new(Column::new()
+            .spacing(15)
+            .push(header)
+            .push(Row::new()
+                .spacing(30)
+                .push(Scrollable::new(friends).width(Length::FillPortion(1)))
+                .push(Scrollable::new(inbox).width(Length::FillPortion(2)))))
+            .padding(20)
+            .into()
+    }
+
+    fn map_browser_view<'a>(&'a self, browser: &'a MapBrowser) -> Element<'a, Message> {
+        let search = Row::new()
+            .spacing(10)
+            .push(TextInput::new(&t!("search-maps"), &browser.query)
+                .on_input(Message::MapQueryChanged)
+                .on_submit(Message::SearchMaps)
+                .padding(5))
+            .push(Button::new(Text::new(t!("search"))).on_press(Message::SearchMaps))
+            .push(Button::new(Text::new(t!("close"))).on_press(Message::CloseMaps));
+
+        let mut results = Column::new().spacing(5);
+        if browser.loading {
+            results = results.push(Text::new(t!("loading-maps")));
+        } else if browser.results.is_empty() {
+            results = results.push(Text::new(t!("no-maps-found")));
+        } else {
+            for map in &browser.results {
+                results = results.push(map_row(map));
+            }
+        }
+
+        let mut previous_button = Button::new(Text::new(t!("previous")));
+        if browser.page > 0 && !browser.loading {
+            previous_button = previous_button.on_press(Message::MapsPageChanged(browser.page - 1));
+        }
+        let mut next_button = Button::new(Text::new(t!("next")));
+        if !browser.loading && !browser.results.is_empty() {
+            next_button = next_button.on_press(Message::MapsPageChanged(browser.page + 1));
+        }
+        let paging = Row::new()
+            .spacing(10)
+            .push(previous_button)
+            .push(Text::new(t!("maps-page", page = browser.page + 1)))
+            .push(next_button);
+
+        let mut recent = Column::new().spacing(5).push(Text::new(t!("recently-played")).size(20));
+        if self.recent_maps.maps().is_empty() {
+            recent = recent.push(Text::new(t!("no-recent-maps")));
+        }
+        for map in self.recent_maps.maps() {
+            recent = recent.push(map_row(map));
+        }
+
+        let listing = Column::new()
+            .spacing(15)
+            .push(results)
+            .push(paging)
+            .push(recent);
+
+        Column::new()
+            .spacing(10)
+            .max_width(800)
+            .push(Text::new(t!("maps")).size(25))
+            .push(search)
+            .push(Scrollable::new(listing).height(Length::Fill))
+            .into()
+    }
+
+    fn profile_card(&self) -> Element<Message> {
+        let mut refresh_button = Button::new(Text::new(if self.loading_profile { t!("loading") } else { t!("refresh") }));
+        if !self.loading_profile {
+            refresh_button = refresh_button.on_press(Message::RefreshProfile);
+        }
+
+        let details = match &self.profile {
+            Some(profile) if !profile.is_empty() => {
+                let stat = |label: String, value: Option<String>| {
+                    Column::new()
+                        .push(Text::new(label).size(12).color(self.palette().news_date))
+                        .push(Text::new(value.unwrap_or_else(|| "-".to_string())))
+                };
+                let kills_deaths = match (profile.kills, profile.deaths) {
+                    (Some(kills), Some(deaths)) => Some(format!("{} / {}", kills, deaths)),
+                    _ => None,
+                };
+                Row::new()
+                    .spacing(25)
+                    .push(stat(t!("profile-rank"), profile.rank.map(|rank| rank.to_string())))
+                    .push(stat(t!("profile-xp"), profile.xp.map(|xp| xp.to_string())))
+                    .push(stat(t!("profile-kills-deaths"), kills_deaths))
+                    .push(stat(t!("profile-kill-death-ratio"), profile.kill_death_ratio().map(|ratio| format!("{:.2}", ratio))))
+                    .push(stat(t!("profile-clan"), profile.clan.clone()))
+                    .push(stat(t!("profile-last-seen"), profile.last_seen.clone()))
+            }
+            Some(_) => Row::new().push(Text::new(t!("profile-empty"))),
+            None => Row::new().push(Text::new(t!("profile-not-loaded"))),
+        };
+
+        Container::new(
+            Row::new()
+                .spacing(20)
+                .push(Container::new(details).width(Length::Fill))
+                .push(refresh_button),
+        )
+        .padding(10)
+        .width(Length::Fill)
+        .into()
+    }
+
     fn create_login_form(&self) -> Element<Message> {
-        let username_input = TextInput::new("Username", &*self.username)
+        let username_input = TextInput::new(&t!("username"), &self.username)
+            .id(USERNAME_INPUT_ID.clone())
             .on_input(Message::UsernameChanged)
+            .on_submit(Message::SubmitLogin)
             .padding(10);
-        let password_input:TextInput<Message> = TextInput::new("Password", &*self.password)
+        let password_input:TextInput<Message> = TextInput::new(&t!("password"), &self.password)
             .secure(!self.password_visible) // Toggle based on the password_visible state
             .on_input(Message::PasswordChanged)
-            .padding(10)
-            .into(); // Convert to Element<Message> for wrapping in Container
+            .on_submit(Message::SubmitLogin)
+            .padding(10);
 
         let visibility_toggle_button:Button<Message> = Button::new(Text::new(if self.password_visible { "👀" } else { "🙈" })
-            .font(iced::Font::with_name("Segoe UI Emoji")))
-            .on_press(Message::TogglePasswordVisibility)
-            .into(); // Convert to Element<Message> for wrapping in Container
+            .font(Font::with_name(fonts::EMOJI_FAMILY)))
+            .on_press(Message::TogglePasswordVisibility);
 
         // Assuming you want to set a specific height, e.g., 50 pixels
         let password_input_container = Container::new(password_input)
@@ -444,9 +1937,9 @@ impl LauncherMainWindow {
             .push(password_input_container)
             .push(visibility_toggle_button_container);
 
-        let submit_button = Button::new(Text::new("Submit"))
+        let submit_button = Button::new(Text::new(t!("submit")))
             .on_press(Message::SubmitLogin);
-        let cancel_button = Button::new(Text::new("Cancel"))
+        let cancel_button = Button::new(Text::new(t!("cancel")))
             .on_press(Message::LoginCancel);
         Column::new()
             .spacing(10)
@@ -458,296 +1951,317 @@ impl LauncherMainWindow {
             .into()
     }
 
+    fn create_settings_form<'a>(&self, draft: &'a Settings) -> Element<'a, Message> {
+        let themes: Vec<String> = self.palettes.iter().map(|palette| palette.name.clone()).collect();
+        let theme_picker = PickList::new(themes, Some(draft.theme.clone()), |theme| Message::SettingChanged(Setting::Theme(theme)));
+
+        let slider_row = |label: String, range: RangeInclusive<u16>, value: u16, on_change: fn(u16) -> Setting| {
+            Row::new()
+                .spacing(10)
+                .push(Text::new(label).width(180))
+                .push(Slider::new(range, value, move |value| Message::SettingChanged(on_change(value))))
+                .push(Text::new(value.to_string()).width(30))
+        };
+
+        let text_row = |label: String, value: &'a str, on_input: fn(String) -> Setting| {
+            Row::new()
+                .spacing(10)
+                .push(Text::new(label.clone()).width(180))
+                .push(TextInput::new(&label, value)
+                    .on_input(move |value| Message::SettingChanged(on_input(value)))
+                    .padding(5))
+        };
+
+        let font_row = |label: String, value: &str, on_pick: fn(String) -> Setting| {
+            Row::new()
+                .spacing(10)
+                .push(Text::new(label).width(180))
+                .push(PickList::new(fonts::choices(), Some(value.to_string()), move |family| Message::SettingChanged(on_pick(family))))
+        };
+
+        let install_dir = draft
+            .install_dir
+            .as_ref()
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_else(|| t!("install-dir-default"));
+        let install_dir_row = Row::new()
+            .spacing(10)
+            .push(Text::new(t!("install-dir")).width(180))
+            .push(Text::new(install_dir).width(Length::Fill))
+            .push(Button::new(Text::new(t!("browse"))).on_press(Message::BrowseInstallDir))
+            .push(Button::new(Text::new(t!("use-launcher-dir")))
+                .on_press(Message::SettingChanged(Setting::InstallDir(None))));
+
+        let player_path = draft
+            .player_path
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| t!("flash-player-default"));
+        let player_row = Row::new()
+            .spacing(10)
+            .push(Text::new(t!("flash-player")).width(180))
+            .push(Text::new(player_path).width(Length::Fill))
+            .push(Button::new(Text::new(t!("browse"))).on_press(Message::BrowsePlayer))
+            .push(Button::new(Text::new(t!("detect"))).on_press(Message::DetectPlayers))
+            .push(Button::new(Text::new(t!("use-downloaded")))
+                .on_press(Message::SettingChanged(Setting::PlayerPath(None))));
+
+        let tray_icon = Checkbox::new(t!("show-tray-icon"), draft.tray_icon)
+            .on_toggle_maybe(cfg!(target_os = "linux").then_some(|show: bool| Message::SettingChanged(Setting::TrayIcon(show))));
+        let hide_to_tray = Checkbox::new(t!("hide-to-tray"), draft.hide_to_tray)
+            .on_toggle_maybe(draft.tray_icon.then_some(|hide: bool| Message::SettingChanged(Setting::HideToTray(hide))));
+
+        let desktop_row = Row::new()
+            .spacing(10)
+            .push(Text::new(t!("app-menu")).width(180))
+            .push(Button::new(Text::new(t!("add-to-app-menu")))
+                .on_press_maybe(cfg!(all(unix, not(target_os = "macos"))).then_some(Message::AddToAppMenu)))
+            .push(Text::new(t!("app-menu-hint")).size(14));
+
+        let remember_password = Checkbox::new(t!("remember-password"), draft.remember_password)
+            .on_toggle(|remember| Message::SettingChanged(Setting::RememberPassword(remember)));
+
+        let buttons = Row::new()
+            .spacing(10)
+            .push(Button::new(Text::new(t!("apply"))).on_press(Message::ApplySettings))
+            .push(Button::new(Text::new(t!("reset-to-defaults"))).on_press(Message::ResetSettings))
+            .push(Button::new(Text::new(t!("cancel"))).on_press(Message::CancelSettings));
+
+        let form = Column::new()
+            .spacing(10)
+            .max_width(700)
+            .push(Text::new(t!("settings")).size(25))
+            .push(Row::new()
+                .spacing(10)
+                .push(Text::new(t!("language")).width(180))
+                .push(PickList::new(
+                    LanguageChoice::all(),
+                    Some(LanguageChoice::from_setting(draft.language)),
+                    |choice| Message::SettingChanged(Setting::Language(choice.into_setting())),
+                )))
+            .push(Row::new()
+                .spacing(10)
+                .push(Text::new(t!("theme")).width(180))
+                .push(theme_picker)
+                .push(Button::new(Text::new(t!("reload-themes"))).on_press(Message::ReloadThemes)))
+            .push(Row::new()
+                .spacing(10)
+                .push(Text::new(t!("default-runtime")).width(180))
+                .push(PickList::new(RuntimeKind::ALL, Some(draft.runtime), |runtime| Message::SettingChanged(Setting::Runtime(runtime)))))
+            .push(slider_row(t!("ui-scale"), UI_SCALES, draft.ui_scale, Setting::UiScale))
+            .push(Text::new(t!("ui-scale-hint")).size(14))
+            .push(slider_row(t!("scrollbar-width"), 0..=30, draft.scrollbar_width, Setting::ScrollbarWidth))
+            .push(slider_row(t!("scrollbar-margin"), 0..=30, draft.scrollbar_margin, Setting::ScrollbarMargin))
+            .push(slider_row(t!("scroller-width"), 0..=30, draft.scroller_width, Setting::ScrollerWidth))
+            .push(font_row(t!("text-font"), &draft.text_font, Setting::TextFont))
+            .push(font_row(t!("news-font"), &draft.news_font, Setting::NewsFont))
+            .push(Text::new(t!("fonts-hint")).size(14))
+            .push(text_row(t!("website"), &draft.endpoints.website, Setting::Website))
+            .push(text_row(t!("player-mirror"), &draft.endpoints.player_mirror, Setting::PlayerMirror))
+            .push(text_row(t!("ruffle-mirror"), &draft.endpoints.ruffle_mirror, Setting::RuffleMirror))
+            .push(text_row(t!("wine-binary"), &draft.wine_binary, Setting::WineBinary))
+            .push(Text::new(t!("wine-binary-hint")).size(14))
+            .push(text_row(t!("proxy"), &draft.http.proxy, Setting::Proxy))
+            .push(Text::new(t!("proxy-hint")).size(12))
+            .push(slider_row(t!("connect-timeout"), 1..=60, draft.http.connect_timeout, Setting::ConnectTimeout))
+            .push(slider_row(t!("read-timeout"), 1..=120, draft.http.read_timeout, Setting::ReadTimeout))
+            .push(slider_row(t!("retries"), 0..=5, draft.http.retries, Setting::Retries))
+            .push(slider_row(t!("builds-kept"), 0..=10, draft.keep_versions, Setting::KeepVersions))
+            .push(Text::new(t!("builds-kept-hint")).size(14))
+            .push(slider_row(t!("update-check-interval"), REFRESH_INTERVALS, draft.update_check_interval, Setting::UpdateCheckInterval))
+            .push(slider_row(t!("news-refresh-interval"), REFRESH_INTERVALS, draft.news_refresh_interval, Setting::NewsRefreshInterval))
+            .push(Text::new(t!("intervals-hint")).size(14))
+            .push(install_dir_row)
+            .push(player_row)
+            .push(remember_password)
+            .push(tray_icon)
+            .push(hide_to_tray)
+            .push(desktop_row)
+            .push(buttons);
+
+        Scrollable::new(form).into()
+    }
+
+    pub fn scale_factor(&self) -> f64 {
+        f64::from(self.settings.ui_scale) / 100.0
+    }
+
     pub fn theme(&self) -> Theme {
-        Theme::Dark
+        self.palette().to_theme()
     }
 }
 
-fn write_auth_file(path: &str, username: &str, password: &str) -> std::io::Result<()> {
-    println!("{} {} {}",path, username, password);
-    let mut file = File::create(path)?;
-    writeln!(file, "{}", username)?;
-    writeln!(file, "{}", password)?;
-    Ok(())
+fn map_row(map: &MapEntry) -> Row<Message> {
+    let author = map.author.as_ref().map(|author| t!("map-author", author = author)).unwrap_or_default();
+    let rating = map.rating.map(|rating| format!("{:.1} / 5", rating)).unwrap_or_else(|| t!("map-not-rated"));
+    Row::new()
+        .spacing(10)
+        .push(Text::new(map.name.as_str()).width(Length::FillPortion(3)))
+        .push(Text::new(author).width(Length::FillPortion(2)))
+        .push(Text::new(rating).width(90))
+        .push(Button::new(Text::new(t!("play"))).on_press(Message::PlayMap(map.clone())))
 }
 
+fn read_auth_file(path: &Path) -> Option<(String, String)> {
+    let auth_content = fs::read_to_string(path).ok()?;
+    let parts: Vec<&str> = auth_content.lines().collect();
 
-async fn handle_login(username: &String, password: &String) -> (String, bool) {
-    let mut fetch_result = crate::login_website_http_post(&username, &password)
-        .await
-        .expect("Failed to fetch sign in POST request http post");
+    if parts.len() == 2 {
+        Some((parts[0].to_string(), parts[1].to_string()))
+    } else {
+        None
+    }
+}
+
+fn write_auth_file(path: &Path, username: &str, password: &str) -> std::io::Result<()> {
+    session::write_private(path, &format!("{}\n{}\n", username, password))
+}
+
+
+pub(crate) async fn handle_login(session: &Session, username: &String, password: &String) -> Result<(String, bool), LauncherError> {
+    let mut fetch_result = crate::login_website_http_post(session, username, password).await?;
+    // The website greets in English, whatever language the launcher shows
+    let mut has_signed_in = fetch_result.starts_with("Welcome back");
 
     let md5_regex = regex::Regex::new(r"^[a-f0-9]{32}$").unwrap();
 
-    if md5_regex.is_match(&password) && !fetch_result.contains("(") {
-        fetch_result = format!("{} {}", fetch_result, "\r\n(Signed with md5 password for game website)");
-    } else if fetch_result.starts_with("Welcome back") {
-        fetch_result = format!("{} {}", fetch_result, "\r\n(Signed with password for game website)");
+    if md5_regex.is_match(password) && !fetch_result.contains("(") {
+        fetch_result = format!("{} {}", fetch_result, LoginMethod::MD5Password.message_suffix());
+    } else if has_signed_in {
+        fetch_result = format!("{} {}", fetch_result, LoginMethod::GameWebsite.message_suffix());
     }
 
-    if !fetch_result.starts_with("Welcome back") {
-        let fetch_result2 = crate::login_website_http_post_rq_load(&username, &password)
-            .await
-            .expect("Failed to fetch sign in POST request post rq load");
+    if !has_signed_in {
+        let fetch_result2 = crate::login_website_http_post_rq_load(session, username, password).await?;
 
         if fetch_result2.starts_with("x") {
-            fetch_result = format!("Welcome back, {} ! \r\n(Signed in with password for standalone launcher)", username);
+            fetch_result = format!("{} {}", t!("welcome-back", name = username), LoginMethod::StandaloneLauncher.message_suffix());
+            has_signed_in = true;
         }
     }
 
-    let has_signed_in = fetch_result.starts_with("Welcome back");
-
-    (fetch_result, has_signed_in)
+    Ok((fetch_result, has_signed_in))
 }
 
-async fn download_and_save_file(url: &str, file_path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
-    let response = reqwest::get(url).await?;
-    let content = response.bytes().await?;
+pub(crate) async fn download_and_save_file(session: &Session, url: &str, file_path: &PathBuf, reporter: &Reporter) -> Result<(), LauncherError> {
+    let content = download(session, url, reporter).await?;
     let mut file = File::create(file_path)?;
     file.write_all(&content)?;
     Ok(())
 }
 
-// Define an enum to represent supported platforms and their architectures
-#[derive(Debug)]
-enum Platform {
-    Windows(Architecture),
-    MacOS,
-    Linux(Architecture),
-}
-
-#[derive(Debug)]
-enum Architecture {
-    X86_64,
-    I686,
+// Bytes received between two progress reports, so a fast download does not flood the window
+const PROGRESS_STEP: u64 = 256 * 1024;
+
+/// Downloads `url` whole, reporting the bytes received as they arrive.
+pub(crate) async fn download(session: &Session, url: &str, reporter: &Reporter) -> Result<Vec<u8>, LauncherError> {
+    let mut response = session.get(url, Profile::Launcher).await?;
+    let total = response.content_length();
+    let mut content = Vec::with_capacity(total.unwrap_or(0) as usize);
+    let mut reported = 0;
+    reporter.progress(Progress { received: 0, total });
+    while let Some(chunk) = response.chunk().await? {
+        content.extend_from_slice(&chunk);
+        let received = content.len() as u64;
+        if received - reported >= PROGRESS_STEP || Some(received) == total {
+            reporter.progress(Progress { received, total });
+            reported = received;
+        }
+    }
+    Ok(content)
 }
 
-// Define a struct to hold download information
-#[derive(Debug)]
-struct DownloadInfo {
-    url: &'static str,
-    file_name: &'static str,
+const AUTH_FILE_NAME: &str = "Plazma Burst 2.auth";
+
+// Percent Ctrl+Plus and Ctrl+Minus change the interface scale by
+const UI_SCALE_STEP: u16 = 10;
+
+// Runs a background operation, forwarding everything it reports as notifications until it finishes
+fn with_reporter<F>(
+    operation: impl FnOnce(Reporter) -> F,
+    on_finish: impl Fn(F::Output) -> Message + Send + 'static,
+) -> Task<Message>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let (reporter, reports) = Reporter::channel();
+    let (reporter, progress) = reporter.with_progress();
+    Task::batch([
+        Task::run(reports, |(level, text)| Message::Notify(level, text)),
+        Task::run(progress, Message::DownloadProgress),
+        Task::perform(operation(reporter), on_finish),
+    ])
 }
 
-impl Platform {
-    fn get_download_info(&self) -> Option<&'static DownloadInfo> {
-        match self {
-            Platform::Windows(arch) => match arch {
-                Architecture::X86_64 => Some(&DownloadInfo {
-                    url: URL_X86_64_WINDOWS,
-                    file_name: "flashplayer.exe",
-                }),
-                Architecture::I686 => Some(&DownloadInfo {
-                    url: URL_I686_WINDOWS,
-                    file_name: "flashplayer.exe",
-                }),
-                _ => None,
-            },
-            Platform::MacOS => Some(&DownloadInfo {
-                url: URL_MACOS,
-                file_name: "flashplayer.dmg",
-            }),
-            Platform::Linux(arch) => match arch {
-                Architecture::X86_64 => Some(&DownloadInfo {
-                    url: URL_X86_64_LINUX,
-                    file_name: "flashplayer",
-                }),
-                Architecture::I686 => Some(&DownloadInfo {
-                    url: URL_I686_LINUX,
-                    file_name: "flashplayer",
-                }),
-                _ => None,
-            },
-        }
-    }
+// Shown instead of a panic on platforms neither Flash Player nor Ruffle is published for
+fn no_runtime_error() -> LauncherError {
+    LauncherError::platform(t!("no-runtime", platform = get_platform().to_string()))
 }
 
-// Define constants for download URLs for readability
-const URL_X86_64_WINDOWS: &str = "https://github.com/luadebug/PB2GameLauncher/raw/main/flashplayer-x86_64-pc-windows-msvc.exe";
-const URL_I686_WINDOWS: &str = "https://github.com/luadebug/PB2GameLauncher/raw/main/flashplayer-i686-pc-windows-msvc.exe";
-const URL_MACOS: &str = "https://github.com/luadebug/PB2GameLauncher/raw/main/flashplayer_32_sa.dmg";
-const URL_X86_64_LINUX: &str = "https://github.com/luadebug/PB2GameLauncher/raw/main/flashplayer-x86_64-unknown-linux-gnu";
-const URL_I686_LINUX: &str = "https://github.com/luadebug/PB2GameLauncher/raw/main/flashplayer-i686-unknown-linux-gnu";
-
-const PB2_TIME: &str = "https://www.plazmaburst2.com/launcher/time.php";
-
-const PB2_SWF: &str = "https://www.plazmaburst2.com/pb2/pb2_re34.swf";
-
-// Function to get the platform based on compile-time configuration
-fn get_platform() -> Platform {
-    cfg_if! {
-        if #[cfg(target_os = "windows")] {
-            let arch = if cfg!(target_arch = "x86_64") {
-                Architecture::X86_64
-            } else if cfg!(target_arch = "i686") {
-                Architecture::I686
-            } else {
-                panic!("Unsupported architecture"); // Or handle gracefully
-            };
-            Platform::Windows(arch)
-        } else if #[cfg(target_os = "macos")] {
-            Platform::MacOS
-        } else if #[cfg(target_os = "linux")] {
-            let arch = if cfg!(target_arch = "x86_64") {
-                Architecture::X86_64
-            } else if cfg!(target_arch = "i686") {
-                Architecture::I686
-            } else {
-                panic!("Unsupported architecture"); // Or handle gracefully
-            };
-            Platform::Linux(arch)
-        } else {
-            panic!("Unsupported operating system"); // Or handle gracefully
-        }
-    }
+// Directory the launcher executable lives in; game files are kept next to it
+pub(crate) fn launcher_dir() -> Result<PathBuf, LauncherError> {
+    let exe_path = std::env::current_exe().context(t!("error-launcher-path"))?;
+    exe_path
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| LauncherError::new(ErrorKind::Io, t!("error-launcher-dir")))
 }
 
-async fn start_game_process() -> Task<Message> {
-    // Get the current executable's directory
-    let exe_path = match std::env::current_exe() {
-        Ok(path) => path,
-        Err(err) => {
-            eprintln!("Failed to get current executable path: {}", err);
-            return Task::none();
-        }
-    };
+async fn start_game_process(install_dir: &Path, runtime: Box<dyn GameRuntime>, version: Option<String>, credentials: Option<(String, String)>, map: Option<MapEntry>, reporter: &Reporter) -> Result<Child, LauncherError> {
+    let version = versions::resolve(install_dir, version.as_deref())?;
 
-    let swf_file_path = exe_path
-        .parent()
-        .expect("Unable to get parent of Launcher EXE path.")
-        .join("pb2_re34_alt.swf")
+    let swf_file_path = version
+        .game_file()
         .canonicalize()
         .unwrap_or_else(|_| {
             eprintln!("Failed to get canonical path to SWF file.");
-            PathBuf::from("pb2_re34_alt.swf") // Fallback
+            version.game_file() // Fallback
         });
 
     let swf_file_path = swf_file_path
         .to_str()
         .map(|s| s.trim_start_matches(r"\\?\"))
-        .unwrap_or_else(|| {
-            eprintln!("Failed to convert SWF file path to string.");
-            "pb2_re34_alt.swf"
-        })
+        .ok_or_else(|| LauncherError::new(ErrorKind::Io, t!("error-swf-path")))?
         .to_string();
 
-    let auth_file = "Plazma Burst 2.auth";
-
-    let Platform = Platform::get_download_info(&get_platform());
-
-    let flash_player_path = Platform.unwrap().file_name;
+    let myparams = game_parameters(credentials, map.as_ref().map(|map| map.id.as_str()));
 
-    let myparams = if fs::metadata(auth_file).is_ok() {
-        let auth_content = fs::read_to_string(auth_file).unwrap_or_default();
-        let parts: Vec<&str> = auth_content.splitn(2, '\n').collect();
-
-        if parts.len() == 2 {
-            format!("?l={}&p={}&from_standalone=1", parts[0], parts[1])
-        } else {
-            "?l=.guest&p=.guest&from_standalone=1".to_string()
-        }
-    } else {
-        "?l=.guest&p=.guest&from_standalone=1".to_string()
-    };
-
-    println!("display()={}", swf_file_path);
+    match &map {
+        Some(map) => reporter.info(t!("starting-game-on-map", build = &version.id, map = &map.name)),
+        None => reporter.info(t!("starting-game", build = &version.id)),
+    }
 
-    let command = format!("{}{}", swf_file_path, myparams);
-    std::process::Command::new(flash_player_path)
-        .args([command])
-        .spawn()
-        .expect("Failed to start game process");
+    let game = runtime::launch(runtime.as_ref(), install_dir, &swf_file_path, &myparams)?;
 
-    Task::none()
+    reporter.success(t!("game-started"));
+    Ok(game)
 }
 
-async fn handle_download_game() -> Task<Message> {
-
-    let Platform = Platform::get_download_info(&get_platform());
-
-
-
-
-
-    // Check if the download URL is empty
-    if Platform.unwrap().url.is_empty() {
-        eprintln!("Flashplayer download URL not available for your platform.");
-        return Task::none();
-    }
+// Sends `true` now and `false` once the game process exits
+fn watch_game(mut game: Child, running: mpsc::UnboundedSender<bool>) {
+    let _ = running.unbounded_send(true);
+    std::thread::spawn(move || {
+        let _ = game.wait();
+        let _ = running.unbounded_send(false);
+    });
+}
 
-    // Get the current executable's directory
-    let exe_path = match std::env::current_exe() {
-        Ok(path) => path,
-        Err(err) => {
-            eprintln!("Failed to get current executable path: {}", err);
-            return Task::none();
-        }
+// Query string the game reads the account, and optionally the map to start on, from
+pub(crate) fn game_parameters(credentials: Option<(String, String)>, map: Option<&str>) -> String {
+    let mut parameters = match credentials {
+        Some((login, password)) => format!("?l={}&p={}&from_standalone=1", login, password),
+        None => "?l=.guest&p=.guest&from_standalone=1".to_string(),
     };
-
-    // Construct the file path based on the executable directory and filename
-    let file_path = exe_path.parent().expect("Unable to get parent of Launcher EXE path.").join(Platform.unwrap().file_name);
-    let time_file_path = exe_path.parent().expect("Unable to get parent of Launcher EXE path.").join("last_update.v");
-    let swf_file_path = exe_path.parent().expect("Unable to get parent of Launcher EXE path.").join("pb2_re34_alt.swf");
-    // Check if the file already exists before downloading
-    if !std::fs::metadata(&file_path).is_ok() {
-        // Download and save the file if it doesn't exist
-        tokio::spawn(async move {
-            match download_and_save_file(Platform.unwrap().url, &file_path).await {
-                Ok(_) => println!("Flashplayer downloaded successfully."),
-                Err(err) => eprintln!("Failed to download Flashplayer: {}", err),
-            }
-        });
-    } else {
-        println!("Flashplayer already exists in the same directory as the launcher.");
+    if let Some(map) = map {
+        parameters.push('&');
+        parameters.push_str(&maps::launch_parameter(map));
     }
-
-    // Check if the file already exists before downloading
-    if !std::fs::metadata(&time_file_path).is_ok() {
-        tokio::spawn(async move {
-            match download_and_save_file(PB2_TIME, &time_file_path).await {
-                Ok(_) => println!("PB2 time downloaded successfully."),
-                Err(err) => eprintln!("Failed to download PB2 time: {}", err),
-            }
-            match download_and_save_file(PB2_SWF, &swf_file_path).await {
-                Ok(_) => println!("PB2 swf downloaded successfully."),
-                Err(err) => eprintln!("Failed to download PB2 swf: {}", err),
-            }
-        });
-
-    } else {
-        // Check if last_update.v exists
-        if std::fs::metadata(&time_file_path).is_ok() {
-            tokio::spawn(async move {
-                // Read the content of last_update.v
-                let local_time = fs::read_to_string(&time_file_path).unwrap_or_default();
-
-                // Fetch the content from the remote URL
-                let remote_time = reqwest::get(PB2_TIME).await.expect("Failed to get PB2 time").text().await.unwrap_or_default();
-
-                // Compare and decide whether to download
-                if local_time != remote_time {
-                    println!("PB2 update available. Downloading...");
-                    match download_and_save_file(PB2_TIME, &time_file_path).await {
-                        Ok(_) => println!("PB2 time downloaded successfully."),
-                        Err(err) => eprintln!("Failed to download PB2 time: {}", err),
-                    }
-                    match download_and_save_file(PB2_SWF, &swf_file_path).await {
-                        Ok(_) => println!("PB2 swf downloaded successfully."),
-                        Err(err) => eprintln!("Failed to download PB2 swf: {}", err),
-                    }
-                    println!("PB2 updated successfully.");
-                } else {
-                    println!("PB2 is up to date.");
-                }
-            });
-        }
-    }
-
-
-    Task::none()
+    parameters
 }
+
 impl Default for LauncherMainWindow {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
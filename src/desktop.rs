@@ -0,0 +1,191 @@
+//! Desktop integration: the app menu entry with its icon, and the `pb2://` links the website
+//! starts the launcher with.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use reqwest::Url;
+
+use crate::error::{Context, LauncherError};
+use crate::maps::MapEntry;
+use crate::Message;
+
+/// Scheme of the links the launcher opens, e.g. `pb2://play?map=tower`.
+pub const URL_SCHEME: &str = "pb2";
+
+pub const DESKTOP_FILE_NAME: &str = "pb2-launcher.desktop";
+
+pub const ICON_NAME: &str = "pb2-launcher";
+
+const ICON: &[u8] = include_bytes!("../assets/pb2-launcher.svg");
+
+/// What the launcher was started to do, read from its command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Invocation {
+    /// Start the launcher, opening the given `pb2://` links.
+    Run(Vec<String>),
+    /// Add the app menu entry and the link handler, then exit.
+    InstallDesktop,
+    /// Remove what `InstallDesktop` added, then exit.
+    UninstallDesktop,
+    Help,
+}
+
+pub const USAGE: &str = "Usage: PB2Launcher [pb2://play | pb2://play?map=NAME | pb2://login | pb2://maps]
+       PB2Launcher --install-desktop | --uninstall-desktop";
+
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Invocation, LauncherError> {
+    let mut links = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--install-desktop" => return Ok(Invocation::InstallDesktop),
+            "--uninstall-desktop" => return Ok(Invocation::UninstallDesktop),
+            "-h" | "--help" => return Ok(Invocation::Help),
+            link if link.starts_with(&format!("{}:", URL_SCHEME)) => links.push(arg),
+            other => return Err(LauncherError::platform(t!("error-unknown-argument", argument = other)).with_cause(USAGE)),
+        }
+    }
+    Ok(Invocation::Run(links))
+}
+
+/// Action of a `pb2://` link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Link {
+    Play,
+    /// Starts the game on a user map, by the name the game loads it by.
+    PlayMap(String),
+    Login,
+    Maps,
+}
+
+impl Link {
+    pub fn parse(link: &str) -> Result<Link, LauncherError> {
+        let invalid = || LauncherError::platform(t!("error-not-a-link", link = link));
+        let url = Url::parse(link).map_err(|_| invalid())?;
+        if url.scheme() != URL_SCHEME {
+            return Err(invalid());
+        }
+        // `pb2://play` names the action as host, `pb2:play` as path
+        let action = url
+            .host_str()
+            .filter(|host| !host.is_empty())
+            .unwrap_or_else(|| url.path())
+            .trim_matches('/')
+            .to_ascii_lowercase();
+        let map = url.query_pairs().find(|(name, _)| name == "map").map(|(_, value)| value.trim().to_string());
+        match (action.as_str(), map) {
+            ("play", Some(map)) if !map.is_empty() => Ok(Link::PlayMap(map)),
+            ("play", _) => Ok(Link::Play),
+            ("login", _) => Ok(Link::Login),
+            ("maps", _) => Ok(Link::Maps),
+            _ => Err(invalid()),
+        }
+    }
+
+    pub fn message(self) -> Message {
+        match self {
+            Link::Play => Message::PlayGamePressed,
+            Link::PlayMap(id) => Message::PlayMap(MapEntry { name: id.clone(), id, author: None, rating: None }),
+            Link::Login => Message::LoginPressed,
+            Link::Maps => Message::OpenMaps,
+        }
+    }
+}
+
+/// `$XDG_DATA_HOME`, or `~/.local/share` where it is not set.
+pub fn data_dir() -> Result<PathBuf, LauncherError> {
+    if let Some(dir) = env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+    env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join(".local").join("share"))
+        .ok_or_else(|| LauncherError::platform(t!("error-home-dir")))
+}
+
+fn desktop_file(data_dir: &Path) -> PathBuf {
+    data_dir.join("applications").join(DESKTOP_FILE_NAME)
+}
+
+fn icon_file(data_dir: &Path) -> PathBuf {
+    data_dir.join("icons").join("hicolor").join("scalable").join("apps").join(format!("{}.svg", ICON_NAME))
+}
+
+/// Desktop entry starting `executable`, also for `pb2://` links.
+pub fn desktop_entry(executable: &Path) -> String {
+    format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=Plazma Burst 2 Launcher\n\
+         Comment=Download, update and play Plazma Burst 2\n\
+         Exec={} %u\n\
+         Icon={}\n\
+         Terminal=false\n\
+         Categories=Game;ActionGame;\n\
+         MimeType=x-scheme-handler/{};\n",
+        exec_argument(&executable.to_string_lossy()),
+        ICON_NAME,
+        URL_SCHEME
+    )
+}
+
+// Quoted as the Exec key wants it, then escaped once more as any string value.
+// A literal % is doubled, as a single one starts a field code.
+fn exec_argument(argument: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in argument.chars() {
+        match c {
+            '"' | '`' | '$' | '\\' => quoted.push('\\'),
+            '%' => quoted.push('%'),
+            _ => {}
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted.replace('\\', "\\\\")
+}
+
+/// Writes the desktop entry and the icon under `data_dir`, returning the entry's path.
+pub fn install(data_dir: &Path, executable: &Path) -> Result<PathBuf, LauncherError> {
+    let entry = desktop_file(data_dir);
+    let icon = icon_file(data_dir);
+    for dir in [entry.parent(), icon.parent()].into_iter().flatten() {
+        fs::create_dir_all(dir).context(t!("error-add-to-menu"))?;
+    }
+    fs::write(&icon, ICON).context(t!("error-add-to-menu"))?;
+    fs::write(&entry, desktop_entry(executable)).context(t!("error-add-to-menu"))?;
+    Ok(entry)
+}
+
+/// Removes the desktop entry and the icon `install` wrote.
+pub fn uninstall(data_dir: &Path) -> Result<(), LauncherError> {
+    for file in [desktop_file(data_dir), icon_file(data_dir)] {
+        if file.exists() {
+            fs::remove_file(&file).context(t!("error-remove-from-menu"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Makes the installed entry the handler of `pb2://` links and refreshes the app menu.
+///
+/// The tools are part of xdg-utils and desktop-file-utils, a desktop without them still
+/// finds the entry, so failing to run them is not an error.
+pub fn register(data_dir: &Path) {
+    let scheme = format!("x-scheme-handler/{}", URL_SCHEME);
+    let _ = Command::new("xdg-mime").args(["default", DESKTOP_FILE_NAME, &scheme]).status();
+    let _ = Command::new("update-desktop-database").arg(data_dir.join("applications")).status();
+}
+
+/// Adds the launcher to the app menu of a freedesktop desktop, as the running executable.
+pub fn integrate() -> Result<PathBuf, LauncherError> {
+    if !cfg!(all(unix, not(target_os = "macos"))) {
+        return Err(LauncherError::platform(t!("error-app-menu-linux-only")));
+    }
+    let executable = env::current_exe().context(t!("error-launcher-executable"))?;
+    let data_dir = data_dir()?;
+    let entry = install(&data_dir, &executable)?;
+    register(&data_dir);
+    Ok(entry)
+}
@@ -0,0 +1,407 @@
+//! Reads the app bundle out of an Apple disk image, so the macOS projector can be installed
+//! without mounting the image (and tested on any platform).
+//!
+//! Only what the Flash Player image uses is supported: a UDIF image with raw or zlib
+//! compressed chunks, holding an HFS+ (or HFSX) volume.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use flate2::read::ZlibDecoder;
+use regex::Regex;
+
+use crate::error::{Context, ErrorKind, LauncherError};
+
+const SECTOR_SIZE: usize = 512;
+const KOLY_SIZE: usize = 512;
+// How much larger than the image its partition may unpack to; a forged size beyond it is damage
+const MAX_EXPANSION: usize = 64;
+
+// Chunk types of the UDIF block table
+const CHUNK_ZERO_FILL: u32 = 0x0000_0000;
+const CHUNK_RAW: u32 = 0x0000_0001;
+const CHUNK_IGNORE: u32 = 0x0000_0002;
+const CHUNK_ZLIB: u32 = 0x8000_0005;
+const CHUNK_COMMENT: u32 = 0x7fff_fffe;
+const CHUNK_END: u32 = 0xffff_ffff;
+
+// HFS+ catalog records
+const ROOT_FOLDER_ID: u32 = 2;
+const FOLDER_RECORD: i16 = 1;
+const FILE_RECORD: i16 = 2;
+const LEAF_NODE: i8 = -1;
+
+// File type and creator HFS+ marks symbolic links with
+const SYMLINK_TYPE: u32 = u32::from_be_bytes(*b"slnk");
+const SYMLINK_CREATOR: u32 = u32::from_be_bytes(*b"rhap");
+// Owner flag of files whose content lives compressed in an extended attribute
+const UF_COMPRESSED: u8 = 0x20;
+
+/// Unpacks the first app bundle at the top of the disk image `image` into `destination`,
+/// which ends up being the `.app` directory. Returns the bundle's name in the image.
+pub fn extract_app(image: &[u8], destination: &Path) -> Result<String, LauncherError> {
+    let volume = read_hfs_partition(image)?;
+    let volume = Volume::open(&volume)?;
+    let catalog = volume.catalog()?;
+
+    let app = catalog
+        .values()
+        .filter(|entry| entry.parent == ROOT_FOLDER_ID && entry.name.ends_with(".app"))
+        .filter_map(|entry| match entry.kind {
+            EntryKind::Folder(id) => Some((id, entry.name.clone())),
+            EntryKind::File { .. } => None,
+        })
+        .min_by(|a, b| a.1.cmp(&b.1))
+        .ok_or_else(|| damaged("no app bundle in the disk image"))?;
+
+    let _ = fs::remove_dir_all(destination);
+    fs::create_dir_all(destination).context(t!("error-unpack-disk-image"))?;
+    // Folders first so every file has its directory, shallow paths before deep ones
+    let mut entries: Vec<(PathBuf, &Entry)> = catalog
+        .values()
+        .filter_map(|entry| relative_path(&catalog, entry, app.0).map(|path| (path, entry)))
+        .collect();
+    entries.sort_by_key(|(path, entry)| (!matches!(entry.kind, EntryKind::Folder(_)), path.components().count()));
+
+    for (path, entry) in entries {
+        let target = destination.join(&path);
+        match &entry.kind {
+            EntryKind::Folder(_) => fs::create_dir_all(&target).context(t!("error-unpack-disk-image"))?,
+            EntryKind::File { fork, symlink, compressed } => {
+                if *compressed {
+                    return Err(damaged(format!("{} is compressed, which is not supported", path.display())));
+                }
+                let content = volume.read_fork(fork)?;
+                if *symlink {
+                    write_symlink(&content, &target)?;
+                } else {
+                    fs::write(&target, content).context(t!("error-unpack-disk-image"))?;
+                    set_mode(&target, entry.mode)?;
+                }
+            }
+        }
+    }
+    Ok(app.1)
+}
+
+fn damaged(cause: impl std::fmt::Display) -> LauncherError {
+    LauncherError::new(ErrorKind::Io, t!("error-disk-image-damaged")).with_cause(cause)
+}
+
+fn be_u16(data: &[u8], offset: usize) -> Result<u16, LauncherError> {
+    data.get(offset..offset + 2)
+        .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+        .ok_or_else(|| damaged("unexpected end of data"))
+}
+
+fn be_u32(data: &[u8], offset: usize) -> Result<u32, LauncherError> {
+    data.get(offset..offset + 4)
+        .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .ok_or_else(|| damaged("unexpected end of data"))
+}
+
+fn be_u64(data: &[u8], offset: usize) -> Result<u64, LauncherError> {
+    Ok((be_u32(data, offset)? as u64) << 32 | be_u32(data, offset + 4)? as u64)
+}
+
+fn slice(data: &[u8], offset: u64, length: u64) -> Result<&[u8], LauncherError> {
+    let start = usize::try_from(offset).map_err(|_| damaged("offset out of range"))?;
+    let end = start
+        .checked_add(usize::try_from(length).map_err(|_| damaged("length out of range"))?)
+        .ok_or_else(|| damaged("length out of range"))?;
+    data.get(start..end).ok_or_else(|| damaged("unexpected end of data"))
+}
+
+// Decompresses the HFS partition of a UDIF image, using the block tables in its property list
+fn read_hfs_partition(image: &[u8]) -> Result<Vec<u8>, LauncherError> {
+    if image.len() < KOLY_SIZE {
+        return Err(damaged("too small for a disk image"));
+    }
+    let koly = &image[image.len() - KOLY_SIZE..];
+    if &koly[..4] != b"koly" {
+        return Err(damaged("not a UDIF disk image"));
+    }
+    let data_fork_offset = be_u64(koly, 24)?;
+    let plist = slice(image, be_u64(koly, 216)?, be_u64(koly, 224)?)?;
+    let plist = String::from_utf8_lossy(plist);
+
+    let table = partition_tables(&plist)?
+        .into_iter()
+        .find(|(name, _)| name.contains("Apple_HFS"))
+        .map(|(_, table)| table)
+        .ok_or_else(|| damaged("no HFS partition in the disk image"))?;
+
+    if table.get(..4) != Some(b"mish".as_slice()) {
+        return Err(damaged("bad block table"));
+    }
+    let data_offset = be_u64(&table, 24)?
+        .checked_add(data_fork_offset)
+        .ok_or_else(|| damaged("offset out of range"))?;
+    let chunk_count = be_u32(&table, 200)? as usize;
+
+    let size = sectors(be_u64(&table, 16)?)
+        .filter(|size| *size <= image.len().saturating_mul(MAX_EXPANSION))
+        .ok_or_else(|| damaged("partition too large"))?;
+    let mut partition = vec![0u8; size];
+    for chunk in 0..chunk_count {
+        let entry = 204 + chunk * 40;
+        let kind = be_u32(&table, entry)?;
+        let outside = || damaged("chunk outside of the partition");
+        let start = sectors(be_u64(&table, entry + 8)?).ok_or_else(outside)?;
+        let length = sectors(be_u64(&table, entry + 16)?).ok_or_else(outside)?;
+        let offset = data_offset.checked_add(be_u64(&table, entry + 24)?).ok_or_else(outside)?;
+        let compressed = slice(image, offset, be_u64(&table, entry + 32)?)?;
+        let target = start
+            .checked_add(length)
+            .and_then(|end| partition.get_mut(start..end))
+            .ok_or_else(outside)?;
+        match kind {
+            CHUNK_ZERO_FILL | CHUNK_IGNORE | CHUNK_COMMENT => {}
+            CHUNK_RAW => target.copy_from_slice(compressed.get(..length).ok_or_else(|| damaged("short raw chunk"))?),
+            CHUNK_ZLIB => ZlibDecoder::new(compressed).read_exact(target).map_err(damaged)?,
+            CHUNK_END => break,
+            other => return Err(damaged(format!("unsupported chunk compression {:#010x}", other))),
+        }
+    }
+    Ok(partition)
+}
+
+// Bytes in `count` sectors, if that fits in memory at all
+fn sectors(count: u64) -> Option<usize> {
+    usize::try_from(count).ok()?.checked_mul(SECTOR_SIZE)
+}
+
+// Names and block tables of the partitions listed under `blkx`
+fn partition_tables(plist: &str) -> Result<Vec<(String, Vec<u8>)>, LauncherError> {
+    let blkx = plist
+        .find("<key>blkx</key>")
+        .map(|start| &plist[start..])
+        .ok_or_else(|| damaged("no block tables in the disk image"))?;
+    let dict = Regex::new(r"(?s)<dict>(.*?)</dict>").unwrap();
+    let name = Regex::new(r"(?s)<key>Name</key>\s*<string>(.*?)</string>").unwrap();
+    let data = Regex::new(r"(?s)<key>Data</key>\s*<data>(.*?)</data>").unwrap();
+
+    let mut tables = Vec::new();
+    for entry in dict.captures_iter(blkx) {
+        let entry = &entry[1];
+        let (Some(name), Some(data)) = (name.captures(entry), data.captures(entry)) else {
+            continue;
+        };
+        tables.push((name[1].to_string(), base64_decode(&data[1])?));
+    }
+    Ok(tables)
+}
+
+// Property lists wrap base64 over several lines, indented
+fn base64_decode(text: &str) -> Result<Vec<u8>, LauncherError> {
+    let mut bytes = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in text.bytes().filter(|c| !c.is_ascii_whitespace() && *c != b'=') {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return Err(damaged("bad base64 in the property list")),
+        };
+        buffer = buffer << 6 | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+/// Data fork of a file: its size and the allocation block runs holding it.
+#[derive(Debug, Clone)]
+struct Fork {
+    size: u64,
+    extents: Vec<(u32, u32)>,
+}
+
+impl Fork {
+    fn parse(data: &[u8], offset: usize) -> Result<Fork, LauncherError> {
+        let size = be_u64(data, offset)?;
+        let total_blocks = be_u32(data, offset + 12)?;
+        let mut extents = Vec::new();
+        for extent in 0..8 {
+            let start = be_u32(data, offset + 16 + extent * 8)?;
+            let count = be_u32(data, offset + 20 + extent * 8)?;
+            if count > 0 {
+                extents.push((start, count));
+            }
+        }
+        // More than eight runs continue in the extents overflow file, which small images never need
+        if extents.iter().map(|(_, count)| *count).sum::<u32>() < total_blocks {
+            return Err(damaged("fragmented file, which is not supported"));
+        }
+        Ok(Fork { size, extents })
+    }
+}
+
+#[derive(Debug)]
+enum EntryKind {
+    Folder(u32),
+    File { fork: Fork, symlink: bool, compressed: bool },
+}
+
+#[derive(Debug)]
+struct Entry {
+    parent: u32,
+    name: String,
+    mode: u16,
+    kind: EntryKind,
+}
+
+struct Volume<'a> {
+    partition: &'a [u8],
+    block_size: u64,
+    catalog: Fork,
+}
+
+impl<'a> Volume<'a> {
+    fn open(partition: &'a [u8]) -> Result<Volume<'a>, LauncherError> {
+        let header = partition.get(1024..1536).ok_or_else(|| damaged("no HFS+ volume header"))?;
+        if &header[..2] != b"H+" && &header[..2] != b"HX" {
+            return Err(damaged("not an HFS+ volume"));
+        }
+        Ok(Volume {
+            partition,
+            block_size: be_u32(header, 40)? as u64,
+            catalog: Fork::parse(header, 272)?,
+        })
+    }
+
+    fn read_fork(&self, fork: &Fork) -> Result<Vec<u8>, LauncherError> {
+        let mut content = Vec::with_capacity(fork.size as usize);
+        for (start, count) in &fork.extents {
+            content.extend_from_slice(slice(self.partition, *start as u64 * self.block_size, *count as u64 * self.block_size)?);
+        }
+        if (content.len() as u64) < fork.size {
+            return Err(damaged("file extends past its blocks"));
+        }
+        content.truncate(fork.size as usize);
+        Ok(content)
+    }
+
+    // Every folder and file record of the catalog B-tree, by parent folder id and name
+    fn catalog(&self) -> Result<HashMap<(u32, String), Entry>, LauncherError> {
+        let catalog = self.read_fork(&self.catalog)?;
+        let node_size = be_u16(&catalog, 32)? as usize;
+        if node_size == 0 {
+            return Err(damaged("bad catalog"));
+        }
+        let mut entries = HashMap::new();
+        let mut node_number = be_u32(&catalog, 24)? as usize;
+        let mut visited = 0;
+        while node_number != 0 {
+            visited += 1;
+            if visited > catalog.len() / node_size {
+                return Err(damaged("catalog leaves loop"));
+            }
+            let node = catalog
+                .get(node_number * node_size..(node_number + 1) * node_size)
+                .ok_or_else(|| damaged("catalog node out of range"))?;
+            if node[8] as i8 != LEAF_NODE {
+                return Err(damaged("catalog leaf chain is broken"));
+            }
+            let records = be_u16(node, 10)? as usize;
+            for record in 0..records {
+                let offset = be_u16(node, node_size - 2 * (record + 1))? as usize;
+                if let Some(entry) = parse_record(node, offset)? {
+                    entries.insert((entry.parent, entry.name.clone()), entry);
+                }
+            }
+            node_number = be_u32(node, 0)? as usize;
+        }
+        Ok(entries)
+    }
+}
+
+fn parse_record(node: &[u8], offset: usize) -> Result<Option<Entry>, LauncherError> {
+    let key_length = be_u16(node, offset)? as usize;
+    let parent = be_u32(node, offset + 2)?;
+    let name_length = be_u16(node, offset + 6)? as usize;
+    let name_units = (0..name_length)
+        .map(|unit| be_u16(node, offset + 8 + unit * 2))
+        .collect::<Result<Vec<_>, _>>()?;
+    // HFS+ keeps a slash in a name as a colon
+    let name = String::from_utf16_lossy(&name_units).replace(['/', ':'], "_");
+    let data = offset + 2 + key_length;
+
+    let record_type = be_u16(node, data)? as i16;
+    let mode = be_u16(node, data + 42)?;
+    let kind = match record_type {
+        FOLDER_RECORD => EntryKind::Folder(be_u32(node, data + 8)?),
+        FILE_RECORD => EntryKind::File {
+            fork: Fork::parse(node, data + 88)?,
+            symlink: be_u32(node, data + 48)? == SYMLINK_TYPE && be_u32(node, data + 52)? == SYMLINK_CREATOR,
+            compressed: node.get(data + 41).is_some_and(|flags| flags & UF_COMPRESSED != 0),
+        },
+        // Thread records only map ids back to names, the keys above already do
+        _ => return Ok(None),
+    };
+    Ok(Some(Entry { parent, name, mode, kind }))
+}
+
+// Path of `entry` inside the folder `root`, `None` when it is not inside it
+fn relative_path(catalog: &HashMap<(u32, String), Entry>, entry: &Entry, root: u32) -> Option<PathBuf> {
+    let folders: HashMap<u32, &Entry> = catalog
+        .values()
+        .filter_map(|entry| match entry.kind {
+            EntryKind::Folder(id) => Some((id, entry)),
+            EntryKind::File { .. } => None,
+        })
+        .collect();
+    let mut names = vec![entry.name.as_str()];
+    let mut parent = entry.parent;
+    while parent != root {
+        let folder = folders.get(&parent)?;
+        names.push(&folder.name);
+        parent = folder.parent;
+        if names.len() > catalog.len() {
+            return None;
+        }
+    }
+    if let EntryKind::Folder(id) = entry.kind {
+        if id == root {
+            return None;
+        }
+    }
+    Some(names.iter().rev().collect())
+}
+
+fn write_symlink(target: &[u8], link: &Path) -> Result<(), LauncherError> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(String::from_utf8_lossy(target).as_ref(), link).context(t!("error-unpack-disk-image"))
+    }
+    // Bundles only use links inside themselves, a copy of the target is not worth it outside macOS
+    #[cfg(not(unix))]
+    {
+        let _ = (target, link);
+        Ok(())
+    }
+}
+
+fn set_mode(path: &Path, mode: u16) -> Result<(), LauncherError> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = match mode as u32 & 0o777 {
+            0 => 0o644,
+            mode => mode,
+        };
+        fs::set_permissions(path, fs::Permissions::from_mode(mode)).context(t!("error-unpack-disk-image"))?;
+    }
+    #[cfg(not(unix))]
+    let _ = (path, mode);
+    Ok(())
+}
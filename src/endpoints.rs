@@ -0,0 +1,121 @@
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ErrorKind, LauncherError};
+
+// Production hosts, used unless something else is configured
+const PB2_WEBSITE: &str = "https://www.plazmaburst2.com";
+const PLAYER_MIRROR: &str = "https://github.com/luadebug/PB2GameLauncher/raw/main";
+const RUFFLE_MIRROR: &str = "https://github.com/ruffle-rs/ruffle/releases/download/nightly-2024-07-01";
+
+/// Base addresses of every remote service the launcher talks to.
+///
+/// All request URLs are derived from these hosts, so pointing them somewhere
+/// else (a mirror, or the local mock server used by the tests) redirects the whole launcher.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Endpoints {
+    /// Game website, without trailing slash.
+    pub website: String,
+    /// Location the Flash Player projector binaries are downloaded from.
+    pub player_mirror: String,
+    /// Location the Ruffle release archives are downloaded from.
+    pub ruffle_mirror: String,
+}
+
+impl Endpoints {
+    pub fn front_page(&self) -> String {
+        format!("{}/", self.website)
+    }
+
+    pub fn news_page(&self, page_number: u8) -> String {
+        format!("{}/?a=&s=0&pg={}", self.website, page_number)
+    }
+
+    /// Front page as a parsed URL, failing when the configured website is not an http(s) address.
+    pub fn website_url(&self) -> Result<Url, LauncherError> {
+        parse_base(&self.website, "website")
+    }
+
+    /// Checks that every configured host is an absolute http(s) address.
+    pub fn validate(&self) -> Result<(), LauncherError> {
+        self.website_url()?;
+        parse_base(&self.player_mirror, "player")?;
+        parse_base(&self.ruffle_mirror, "ruffle")?;
+        Ok(())
+    }
+
+    pub fn profile_page(&self, login: &str) -> Result<String, LauncherError> {
+        let mut url = self.website_url()?;
+        url.query_pairs_mut().append_pair("a", "").append_pair("s", "7").append_pair("ac", login);
+        Ok(url.to_string())
+    }
+
+    /// Map listing, `page` counting from zero. An empty query lists every map.
+    pub fn maps_page(&self, query: &str, page: u32) -> Result<String, LauncherError> {
+        let mut url = self.website_url()?;
+        url.query_pairs_mut()
+            .append_pair("a", "")
+            .append_pair("s", "9")
+            .append_pair("pg", &page.to_string());
+        if !query.is_empty() {
+            url.query_pairs_mut().append_pair("search", query);
+        }
+        Ok(url.to_string())
+    }
+
+    pub fn friends_page(&self) -> String {
+        format!("{}/?a=&s=10", self.website)
+    }
+
+    pub fn inbox_page(&self) -> String {
+        format!("{}/?a=&s=11", self.website)
+    }
+
+    pub fn server_php(&self) -> String {
+        format!("{}/pb2/server.php", self.website)
+    }
+
+    pub fn launcher_time(&self) -> String {
+        format!("{}/launcher/time.php", self.website)
+    }
+
+    pub fn game_swf(&self) -> String {
+        format!("{}/pb2/pb2_re34.swf", self.website)
+    }
+
+    pub fn player(&self, remote_name: &str) -> String {
+        format!("{}/{}", self.player_mirror, remote_name)
+    }
+
+    pub fn ruffle(&self, archive_name: &str) -> String {
+        format!("{}/{}", self.ruffle_mirror, archive_name)
+    }
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Endpoints {
+            website: PB2_WEBSITE.to_string(),
+            player_mirror: PLAYER_MIRROR.to_string(),
+            ruffle_mirror: RUFFLE_MIRROR.to_string(),
+        }
+    }
+}
+
+// "localhost:8080" parses as a URL with a "localhost" scheme, so the scheme is checked too
+fn parse_base(address: &str, host: &str) -> Result<Url, LauncherError> {
+    let invalid = || LauncherError::new(ErrorKind::Io, t!("error-invalid-address", host = host, address = address));
+    let url = Url::parse(&format!("{}/", address)).map_err(|err| invalid().with_cause(err))?;
+    if !matches!(url.scheme(), "http" | "https") || !url.has_host() {
+        return Err(invalid());
+    }
+    Ok(url)
+}
+
+/// Decoded value of a query parameter in a link scraped from the website, relative or not.
+pub fn query_value(href: &str, key: &str) -> Option<String> {
+    let url = reqwest::Url::parse("http://localhost/").ok()?.join(href).ok()?;
+    let value = url.query_pairs().find(|(name, value)| name == key && !value.is_empty())?.1;
+    Some(value.into_owned())
+}
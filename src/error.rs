@@ -0,0 +1,126 @@
+use std::error::Error;
+use std::fmt;
+
+/// Broad category of a [`LauncherError`], used to pick how it is presented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The website, the player mirror or another remote host could not be reached or answered badly.
+    Network,
+    /// Reading or writing files next to the launcher failed.
+    Io,
+    /// There is no Flash Player build for this operating system or architecture.
+    Platform,
+    /// The game process could not be started.
+    Process,
+}
+
+/// Error type shared by every launcher operation.
+///
+/// It is cheap to clone so it can travel inside a [`crate::Message`] back to the UI,
+/// and keeps a human readable context ("Failed to download PB2 swf") next to the underlying cause.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LauncherError {
+    pub kind: ErrorKind,
+    pub context: String,
+    pub cause: Option<String>,
+}
+
+impl LauncherError {
+    pub fn new(kind: ErrorKind, context: impl Into<String>) -> Self {
+        LauncherError {
+            kind,
+            context: context.into(),
+            cause: None,
+        }
+    }
+
+    pub fn network(context: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Network, context)
+    }
+
+    pub fn platform(context: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Platform, context)
+    }
+
+    pub fn process(context: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Process, context)
+    }
+
+    pub fn with_cause(mut self, cause: impl fmt::Display) -> Self {
+        self.cause = Some(cause.to_string());
+        self
+    }
+
+    /// Whether running the same operation again has a chance to succeed.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.kind, ErrorKind::Network | ErrorKind::Io)
+    }
+}
+
+impl fmt::Display for LauncherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.cause {
+            Some(cause) => write!(f, "{}: {}", self.context, cause),
+            None => write!(f, "{}", self.context),
+        }
+    }
+}
+
+impl Error for LauncherError {}
+
+impl From<reqwest::Error> for LauncherError {
+    fn from(err: reqwest::Error) -> Self {
+        let context = if err.is_timeout() {
+            t!("error-request-timed-out")
+        } else if err.is_connect() {
+            t!("error-no-connection")
+        } else if err.is_status() {
+            t!("error-server-error")
+        } else {
+            t!("error-request-failed")
+        };
+        LauncherError {
+            kind: ErrorKind::Network,
+            context,
+            cause: Some(err.to_string()),
+        }
+    }
+}
+
+impl From<reqwest::header::InvalidHeaderValue> for LauncherError {
+    fn from(err: reqwest::header::InvalidHeaderValue) -> Self {
+        LauncherError {
+            kind: ErrorKind::Network,
+            context: t!("error-invalid-header"),
+            cause: Some(err.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for LauncherError {
+    fn from(err: std::io::Error) -> Self {
+        LauncherError {
+            kind: ErrorKind::Io,
+            context: t!("error-file-operation"),
+            cause: Some(err.to_string()),
+        }
+    }
+}
+
+/// Attaches a description of what was being done to an error, keeping its kind.
+pub trait Context<T> {
+    fn context(self, context: impl Into<String>) -> Result<T, LauncherError>;
+}
+
+impl<T, E: Into<LauncherError>> Context<T> for Result<T, E> {
+    fn context(self, context: impl Into<String>) -> Result<T, LauncherError> {
+        self.map_err(|err| {
+            let err = err.into();
+            LauncherError {
+                kind: err.kind,
+                context: context.into(),
+                cause: Some(err.to_string()),
+            }
+        })
+    }
+}
@@ -0,0 +1,55 @@
+//! Fonts bundled with the launcher, so text and emoji look the same on every system.
+//!
+//! A font setting names a family, or several separated by commas. Text is drawn with the first
+//! one installed and with the bundled Noto Sans when none is. Glyphs missing from that font,
+//! emoji among them, come from the other loaded fonts, the bundled Noto Emoji included.
+
+use std::collections::BTreeSet;
+
+use iced::advanced::graphics::text::font_system;
+use once_cell::sync::Lazy;
+
+/// Family of the bundled text font, Latin and Cyrillic.
+pub const TEXT_FAMILY: &str = "Noto Sans";
+
+/// Family of the bundled emoji font.
+pub const EMOJI_FAMILY: &str = "Noto Emoji";
+
+/// Font files loaded at startup, both under the SIL Open Font License (see `assets/fonts`).
+pub const BUNDLED: [&[u8]; 2] = [
+    include_bytes!("../assets/fonts/NotoSans-Regular.ttf"),
+    include_bytes!("../assets/fonts/NotoEmoji-Regular.ttf"),
+];
+
+// Read once, fonts installed while the launcher runs show up after a restart
+static INSTALLED: Lazy<BTreeSet<String>> = Lazy::new(|| {
+    let mut families: BTreeSet<String> = [TEXT_FAMILY, EMOJI_FAMILY].into_iter().map(str::to_string).collect();
+    if let Ok(mut fonts) = font_system().write() {
+        let faces = fonts.raw().db().faces();
+        families.extend(faces.filter_map(|face| face.families.first().map(|(family, _)| family.clone())));
+    }
+    families
+});
+
+/// Families text can be drawn with: the system's and the bundled ones.
+pub fn installed() -> &'static BTreeSet<String> {
+    &INSTALLED
+}
+
+/// Choices of the font pickers in Settings, the bundled text font first.
+pub fn choices() -> Vec<String> {
+    let mut choices = vec![TEXT_FAMILY.to_string()];
+    choices.extend(installed().iter().filter(|family| *family != TEXT_FAMILY && *family != EMOJI_FAMILY).cloned());
+    choices
+}
+
+/// Family of the font setting `fonts` among the `installed` ones, see the module docs.
+pub fn resolve<'a>(fonts: &str, installed: &'a BTreeSet<String>) -> &'a str {
+    fonts
+        .split(',')
+        .map(str::trim)
+        .filter(|family| !family.is_empty())
+        // Font names are matched the way fontconfig does, ignoring case
+        .find_map(|family| installed.iter().find(|installed| installed.eq_ignore_ascii_case(family)))
+        .map_or(TEXT_FAMILY, String::as_str)
+}
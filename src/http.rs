@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, ACCEPT_LANGUAGE, USER_AGENT};
+use reqwest::{Proxy, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ErrorKind, LauncherError};
+
+// First pause before retrying a failed GET, doubled after every attempt
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+const BROWSER_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:127.0) Gecko/20100101 Firefox/127.0";
+const FLASH_USER_AGENT: &str = "Shockwave Flash";
+const LAUNCHER_USER_AGENT: &str = concat!("PB2Launcher/", env!("CARGO_PKG_VERSION"));
+
+/// Connection settings applied to every request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpOptions {
+    /// `http://`, `https://` or `socks5://` proxy. When empty the system proxy
+    /// (`HTTP_PROXY`, `HTTPS_PROXY` and `ALL_PROXY` variables) is used.
+    pub proxy: String,
+    /// Seconds to wait for a connection to be established.
+    pub connect_timeout: u16,
+    /// Seconds to wait for the server to send more data.
+    pub read_timeout: u16,
+    /// How many times a failed GET is retried.
+    pub retries: u16,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        HttpOptions {
+            proxy: String::new(),
+            connect_timeout: 10,
+            read_timeout: 30,
+            retries: 2,
+        }
+    }
+}
+
+impl HttpOptions {
+    fn proxy(&self) -> Result<Option<Proxy>, LauncherError> {
+        let proxy = self.proxy.trim();
+        if proxy.is_empty() {
+            return Ok(None);
+        }
+        Proxy::all(proxy)
+            .map(Some)
+            .map_err(|err| LauncherError::new(ErrorKind::Network, t!("error-invalid-proxy", proxy = proxy)).with_cause(err))
+    }
+
+    pub fn client_builder(&self) -> Result<reqwest::ClientBuilder, LauncherError> {
+        let mut builder = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(self.connect_timeout.into()))
+            .read_timeout(Duration::from_secs(self.read_timeout.into()));
+        if let Some(proxy) = self.proxy()? {
+            builder = builder.proxy(proxy);
+        }
+        Ok(builder)
+    }
+}
+
+/// Headers sent to each kind of endpoint, so every request identifies itself the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    /// Website pages, which expect a regular browser.
+    Browser,
+    /// `server.php`, which expects requests made by the game.
+    FlashPlayer,
+    /// Game and Flash Player downloads.
+    Launcher,
+}
+
+impl Profile {
+    pub fn headers(self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        match self {
+            Profile::Browser => {
+                headers.insert(USER_AGENT, HeaderValue::from_static(BROWSER_USER_AGENT));
+                headers.insert(ACCEPT, HeaderValue::from_static("text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,*/*;q=0.8"));
+                headers.insert(ACCEPT_LANGUAGE, HeaderValue::from_static("en-US,en;q=0.5"));
+            }
+            Profile::FlashPlayer => {
+                headers.insert(USER_AGENT, HeaderValue::from_static(FLASH_USER_AGENT));
+                headers.insert(ACCEPT, HeaderValue::from_static("text/xml, application/xml, application/xhtml+xml, text/html;q=0.9, text/plain;q=0.8, text/css, image/png, image/jpeg, image/gif;q=0.8, application/x-shockwave-flash, video/mp4;q=0.9, flv-application/octet-stream;q=0.8, video/x-flv;q=0.7, audio/mp4, application/futuresplash, */*;q=0.5"));
+            }
+            Profile::Launcher => {
+                headers.insert(USER_AGENT, HeaderValue::from_static(LAUNCHER_USER_AGENT));
+            }
+        }
+        headers
+    }
+}
+
+/// Whether a failed request may succeed when sent again.
+pub fn is_transient(err: &reqwest::Error) -> bool {
+    err.is_timeout()
+        || err.is_connect()
+        || err
+            .status()
+            .is_some_and(|status| status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS)
+}
+
+/// Pause before retry number `attempt`, counting from zero.
+pub fn retry_delay(attempt: u16) -> Duration {
+    RETRY_DELAY * 2u32.saturating_pow(attempt.into())
+}
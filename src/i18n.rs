@@ -0,0 +1,173 @@
+//! Translations of the launcher's texts, kept as Fluent catalogs in `locales/`.
+//!
+//! Texts are looked up with [`t!`] in the language picked in Settings, or the system one.
+//! A text missing from a translation falls back to English. Error causes reported by
+//! libraries stay in English, they mostly end up in bug reports.
+
+use std::fmt;
+use std::sync::RwLock;
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use unic_langid::LanguageIdentifier;
+
+/// Looks up a translated text: `t!("play-game")`, or with arguments
+/// `t!("signed-in-as", name = login)`.
+macro_rules! t {
+    ($id:literal) => {
+        $crate::i18n::text($id, &[])
+    };
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::text($id, &[$((stringify!($name), fluent_bundle::FluentValue::from($value))),+])
+    };
+}
+
+/// A language the launcher is translated to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Russian,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Russian];
+
+    /// BCP 47 tag, also the directory of the catalog.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en-US",
+            Language::Russian => "ru",
+        }
+    }
+
+    /// Source of the Fluent catalog.
+    pub fn catalog(self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en-US/launcher.ftl"),
+            Language::Russian => include_str!("../locales/ru/launcher.ftl"),
+        }
+    }
+
+    /// Language of a POSIX locale such as `ru_RU.UTF-8` or a tag such as `ru-RU`.
+    pub fn from_locale(locale: &str) -> Option<Language> {
+        let language = locale.split(['_', '-', '.', '@']).next()?.to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(Language::English),
+            "ru" => Some(Language::Russian),
+            _ => None,
+        }
+    }
+
+    /// Language of the user's environment, English when it is not translated.
+    pub fn detect() -> Language {
+        // The POSIX variables in the order they override each other, then the OS setting
+        let from_env = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty() && value != "C" && value != "POSIX");
+        from_env
+            .or_else(sys_locale::get_locale)
+            .and_then(|locale| Language::from_locale(&locale))
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Language {
+    // Every language by its own name, so users find theirs whatever is shown
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Language::English => write!(f, "English"),
+            Language::Russian => write!(f, "Русский"),
+        }
+    }
+}
+
+/// Entry of the language picker in Settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LanguageChoice {
+    System,
+    Language(Language),
+}
+
+impl LanguageChoice {
+    pub fn all() -> Vec<LanguageChoice> {
+        std::iter::once(LanguageChoice::System)
+            .chain(Language::ALL.into_iter().map(LanguageChoice::Language))
+            .collect()
+    }
+
+    pub fn from_setting(language: Option<Language>) -> LanguageChoice {
+        language.map_or(LanguageChoice::System, LanguageChoice::Language)
+    }
+
+    pub fn into_setting(self) -> Option<Language> {
+        match self {
+            LanguageChoice::System => None,
+            LanguageChoice::Language(language) => Some(language),
+        }
+    }
+}
+
+impl fmt::Display for LanguageChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LanguageChoice::System => write!(f, "{} ({})", t!("language-system"), Language::detect()),
+            LanguageChoice::Language(language) => write!(f, "{}", language),
+        }
+    }
+}
+
+static BUNDLES: Lazy<Vec<(Language, FluentBundle<FluentResource>)>> =
+    Lazy::new(|| Language::ALL.into_iter().map(|language| (language, bundle(language))).collect());
+
+// Tests check the English texts whatever the locale of the machine running them
+static CURRENT: Lazy<RwLock<Language>> =
+    Lazy::new(|| RwLock::new(if cfg!(test) { Language::English } else { Language::detect() }));
+
+fn bundle(language: Language) -> FluentBundle<FluentResource> {
+    let id: LanguageIdentifier = language.code().parse().expect("language codes are valid");
+    let mut bundle = FluentBundle::new_concurrent(vec![id]);
+    // Bidi isolation marks show up as boxes in the UI fonts
+    bundle.set_use_isolating(false);
+    // The catalogs are checked by the tests, a broken entry is left out instead of failing the start
+    let resource = FluentResource::try_new(language.catalog().to_string()).unwrap_or_else(|(resource, _)| resource);
+    let _ = bundle.add_resource(resource);
+    bundle
+}
+
+/// Switches every text to `language`, the system language when `None`.
+pub fn set_language(language: Option<Language>) {
+    if let Ok(mut current) = CURRENT.write() {
+        *current = language.unwrap_or_else(Language::detect);
+    }
+}
+
+pub fn current_language() -> Language {
+    CURRENT.read().map(|current| *current).unwrap_or_default()
+}
+
+/// Text `id` in the current language, see [`t!`].
+pub fn text(id: &str, args: &[(&str, FluentValue)]) -> String {
+    text_in(current_language(), id, args)
+}
+
+/// Text `id` in `language`, the English one when it is not translated and `id` itself when
+/// no catalog has it.
+pub fn text_in(language: Language, id: &str, args: &[(&str, FluentValue)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+    [language, Language::English]
+        .iter()
+        .find_map(|language| {
+            let (_, bundle) = BUNDLES.iter().find(|(candidate, _)| candidate == language)?;
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = Vec::new();
+            Some(bundle.format_pattern(pattern, Some(&fluent_args), &mut errors).into_owned())
+        })
+        .unwrap_or_else(|| id.to_string())
+}
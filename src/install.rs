@@ -0,0 +1,218 @@
+//! The game files and the workflows managing them: Install, Update, Repair and Uninstall.
+//!
+//! Install sets up the player and the latest game build step by step, Update only fetches what
+//! changed, Repair checks every file and fetches the missing or damaged ones again and Uninstall
+//! removes them. Settings, the saved sign in and the launcher itself are not game files.
+
+use std::fs;
+use std::path::Path;
+
+use crate::error::{Context, LauncherError};
+use crate::http::Profile;
+use crate::notifications::Reporter;
+use crate::runtime::{self, GameRuntime, RuntimeKind};
+use crate::session::Session;
+use crate::settings::Settings;
+use crate::versions::{self, GameVersion, UpdateStatus};
+use crate::LauncherMainWindow::download_and_save_file;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallAction {
+    Install,
+    Update,
+    Repair,
+    Uninstall,
+}
+
+impl InstallAction {
+    /// Text of the button starting the action.
+    pub fn label(self) -> String {
+        match self {
+            InstallAction::Install => t!("install-game"),
+            InstallAction::Update => t!("update-game"),
+            InstallAction::Repair => t!("repair-game"),
+            InstallAction::Uninstall => t!("uninstall-game"),
+        }
+    }
+
+    /// Status shown while the action runs, until it reports its first step.
+    pub fn running_label(self) -> String {
+        match self {
+            InstallAction::Install => t!("installing-game"),
+            InstallAction::Update => t!("checking-game-update"),
+            InstallAction::Repair => t!("repairing-game"),
+            InstallAction::Uninstall => t!("uninstalling-game"),
+        }
+    }
+}
+
+/// What the main view tells about the game files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstallState {
+    NotInstalled,
+    /// Every file checked, by the newest build.
+    Installed(String),
+    /// What is missing or damaged, Repair fixes it.
+    Damaged(Vec<String>),
+}
+
+/// State of the game files in `install_dir` for `runtime`, `None` when no runtime runs here.
+pub fn state(install_dir: &Path, runtime: Option<&dyn GameRuntime>) -> Result<InstallState, LauncherError> {
+    let installed = versions::installed(install_dir)?;
+    let Some(newest) = installed.first() else {
+        return Ok(InstallState::NotInstalled);
+    };
+    let mut problems: Vec<String> = runtime
+        .and_then(|runtime| runtime.verify(install_dir).err())
+        .map(|err| err.to_string())
+        .into_iter()
+        .collect();
+    problems.extend(installed.iter().filter_map(|version| version.verify().err()).map(|err| err.to_string()));
+    if problems.is_empty() {
+        Ok(InstallState::Installed(newest.id.clone()))
+    } else {
+        Ok(InstallState::Damaged(problems))
+    }
+}
+
+/// Sets up the player and the latest game build from scratch, reporting each step.
+pub async fn install(session: &Session, install_dir: &Path, runtime: &dyn GameRuntime, reporter: &Reporter) -> Result<(), LauncherError> {
+    fs::create_dir_all(install_dir).context(t!("error-create-install-dir"))?;
+    // Interrupted downloads start over instead of being resumed
+    for dir in versions::unfinished(install_dir)? {
+        runtime::remove_path(&dir)?;
+    }
+
+    reporter.info(t!("install-step-player"));
+    runtime.download(session, install_dir, reporter).await?;
+
+    reporter.info(t!("install-step-game"));
+    let remote_time = fetch_remote_time(session).await?;
+    let id = versions::version_id(&remote_time);
+    if versions::update_status(&versions::installed(install_dir)?, &id) != UpdateStatus::UpToDate {
+        download_pb2(session, &versions::versions_dir(install_dir).join(&id), &remote_time, reporter).await?;
+    }
+    reporter.success(t!("game-downloaded"));
+    Ok(())
+}
+
+/// Fetches only what changed: the player when it does not verify and the latest build when the
+/// website has a newer one.
+pub async fn update(session: &Session, install_dir: &Path, runtime: &dyn GameRuntime, reporter: &Reporter) -> Result<(), LauncherError> {
+    fs::create_dir_all(install_dir).context(t!("error-create-install-dir"))?;
+
+    // The player is shared by all game builds
+    if runtime.verify(install_dir).is_err() {
+        runtime.download(session, install_dir, reporter).await?;
+    }
+
+    // Every build goes into its own directory named after its time stamp, older builds stay playable
+    let installed = versions::installed(install_dir)?;
+    let remote_time = fetch_remote_time(session).await?;
+    let id = versions::version_id(&remote_time);
+
+    match versions::update_status(&installed, &id) {
+        UpdateStatus::UpToDate => reporter.success(t!("game-up-to-date")),
+        UpdateStatus::NotInstalled => {
+            reporter.info(t!("downloading-game"));
+            download_pb2(session, &versions::versions_dir(install_dir).join(&id), &remote_time, reporter).await?;
+            reporter.success(t!("game-downloaded"));
+        }
+        UpdateStatus::Available(_) => {
+            reporter.info(t!("updating-game"));
+            download_pb2(session, &versions::versions_dir(install_dir).join(&id), &remote_time, reporter).await?;
+            reporter.success(t!("game-updated"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the player and every build, fetching the missing or damaged files again.
+///
+/// Only the latest build is published, a damaged older build is removed instead.
+pub async fn repair(session: &Session, install_dir: &Path, runtime: &dyn GameRuntime, reporter: &Reporter) -> Result<(), LauncherError> {
+    fs::create_dir_all(install_dir).context(t!("error-create-install-dir"))?;
+    let mut repaired = 0;
+
+    if runtime.verify(install_dir).is_err() {
+        reporter.info(t!("repairing-player"));
+        runtime.download(session, install_dir, reporter).await?;
+        repaired += 1;
+    }
+
+    for dir in versions::unfinished(install_dir)? {
+        runtime::remove_path(&dir)?;
+        repaired += 1;
+    }
+
+    let remote_time = fetch_remote_time(session).await?;
+    let latest = versions::version_id(&remote_time);
+    let installed = versions::installed(install_dir)?;
+    for version in installed.iter().filter(|version| version.verify().is_err()) {
+        repaired += 1;
+        if version.id == latest {
+            reporter.info(t!("repairing-build", build = &version.id));
+            download_pb2(session, &version.dir, &remote_time, reporter).await?;
+        } else {
+            runtime::remove_path(&version.dir)?;
+            reporter.warning(t!("damaged-build-removed", build = &version.id));
+        }
+    }
+    if versions::installed(install_dir)?.is_empty() {
+        reporter.info(t!("downloading-game"));
+        download_pb2(session, &versions::versions_dir(install_dir).join(&latest), &remote_time, reporter).await?;
+        repaired += 1;
+    }
+
+    if repaired == 0 {
+        reporter.success(t!("game-files-intact"));
+    } else {
+        reporter.success(t!("game-files-repaired", count = repaired));
+    }
+    Ok(())
+}
+
+/// Removes every game build and the players the launcher downloaded, for all runtimes.
+///
+/// The install directory itself stays, by default it is the launcher's own.
+pub fn uninstall(install_dir: &Path) -> Result<(), LauncherError> {
+    runtime::remove_path(&versions::versions_dir(install_dir))?;
+    // Left behind by launchers before versioned builds
+    runtime::remove_path(&install_dir.join(versions::GAME_FILE_NAME))?;
+    runtime::remove_path(&install_dir.join(versions::TIME_FILE_NAME))?;
+    // The default settings name no player of the user's, only downloaded ones go
+    for kind in RuntimeKind::ALL {
+        kind.runtime(&Settings::default()).remove(install_dir)?;
+    }
+    Ok(())
+}
+
+/// Whether the website publishes a newer game build than the ones in `install_dir`.
+pub async fn check_for_update(session: &Session, install_dir: &Path) -> Result<UpdateStatus, LauncherError> {
+    let remote_time = fetch_remote_time(session).await?;
+    let installed = versions::installed(install_dir)?;
+    Ok(versions::update_status(&installed, &versions::version_id(&remote_time)))
+}
+
+// Time stamp of the latest game build, it names the build
+async fn fetch_remote_time(session: &Session) -> Result<String, LauncherError> {
+    session.get(&session.endpoints().launcher_time(), Profile::Launcher)
+        .await
+        .context(t!("error-get-time"))?
+        .text()
+        .await
+        .context(t!("error-get-time"))
+}
+
+// The time stamp is written last, so a build is only listed once its game file is complete
+async fn download_pb2(session: &Session, version_dir: &Path, remote_time: &str, reporter: &Reporter) -> Result<(), LauncherError> {
+    fs::create_dir_all(version_dir).context(t!("error-create-build-dir"))?;
+    runtime::remove_path(&version_dir.join(versions::TIME_FILE_NAME))?;
+    download_and_save_file(session, &session.endpoints().game_swf(), &version_dir.join(versions::GAME_FILE_NAME), reporter)
+        .await
+        .context(t!("error-download-game"))?;
+    GameVersion { id: versions::version_id(remote_time), dir: version_dir.to_path_buf() }.verify()?;
+    fs::write(version_dir.join(versions::TIME_FILE_NAME), remote_time).context(t!("error-save-time"))?;
+    Ok(())
+}
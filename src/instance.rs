@@ -0,0 +1,129 @@
+//! Keeps a single launcher running per install. A second start hands its `pb2://` links to the
+//! running launcher over a Unix domain socket and exits, so two windows never race on the
+//! auth file or the downloads.
+
+use std::env;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use iced::futures::channel::mpsc;
+use iced::futures::stream::BoxStream;
+
+use crate::error::LauncherError;
+use crate::receiver::SharedReceiver;
+
+/// Links forwarded by later starts, an empty list when they only asked for the window.
+#[derive(Debug, Clone, Default)]
+pub struct Requests {
+    receiver: SharedReceiver<Vec<String>>,
+    // Held while this launcher serves the socket
+    _lock: Option<Arc<File>>,
+}
+
+impl Requests {
+    /// Forwarded requests as they arrive. Only the first stream taken gets them.
+    pub fn stream(&self) -> BoxStream<'static, Vec<String>> {
+        self.receiver.stream()
+    }
+}
+
+/// Outcome of starting up.
+#[derive(Debug)]
+pub enum Claim {
+    /// No launcher was running, this one serves the later starts.
+    Primary(Requests),
+    /// The running launcher took over the arguments.
+    Forwarded,
+}
+
+/// Socket of the launcher installed in `launcher_dir`, in the user's runtime directory.
+pub fn socket_path(launcher_dir: &Path) -> PathBuf {
+    let dir = env::var_os("XDG_RUNTIME_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(env::temp_dir);
+    // Socket paths are short, the install directory only goes in hashed
+    let install = format!("{:x}", md5::compute(launcher_dir.to_string_lossy().as_bytes()));
+    dir.join(format!("pb2-launcher-{}.sock", &install[..12]))
+}
+
+/// Becomes the running launcher at `socket`, or forwards `links` to the one already there.
+///
+/// Whoever holds the lock file next to the socket is the running launcher, so two starts
+/// never both bind the socket or remove the other's.
+#[cfg(unix)]
+pub fn claim(socket: &Path, links: &[String]) -> Result<Claim, LauncherError> {
+    use std::fs::{OpenOptions, TryLockError};
+    use std::io::Write;
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::time::Duration;
+
+    let failed = |err: std::io::Error| LauncherError::process(t!("error-instance-check")).with_cause(err);
+    let lock = OpenOptions::new().create(true).truncate(false).write(true).open(socket.with_extension("lock")).map_err(failed)?;
+    match lock.try_lock() {
+        Ok(()) => {
+            // A socket left behind belongs to a launcher that crashed, the lock went with it
+            let _ = std::fs::remove_file(socket);
+            let listener = UnixListener::bind(socket).map_err(failed)?;
+            std::fs::set_permissions(socket, std::fs::Permissions::from_mode(0o600)).map_err(failed)?;
+            Ok(Claim::Primary(serve(listener, lock)))
+        }
+        Err(TryLockError::WouldBlock) => {
+            // The running launcher may have just taken the lock and not bound the socket yet
+            let mut attempts = 0;
+            let mut stream = loop {
+                match UnixStream::connect(socket) {
+                    Ok(stream) => break stream,
+                    Err(_) if attempts < 20 => {
+                        attempts += 1;
+                        std::thread::sleep(Duration::from_millis(100));
+                    }
+                    Err(err) => return Err(failed(err)),
+                }
+            };
+            let request: String = links.iter().map(|link| format!("{}\n", link.replace('\n', ""))).collect();
+            stream.write_all(request.as_bytes()).map_err(failed)?;
+            Ok(Claim::Forwarded)
+        }
+        Err(TryLockError::Error(err)) => Err(failed(err)),
+    }
+}
+
+/// Other platforms run every start on its own.
+#[cfg(not(unix))]
+pub fn claim(_socket: &Path, _links: &[String]) -> Result<Claim, LauncherError> {
+    Ok(Claim::Primary(Requests::default()))
+}
+
+/// Removes the socket once the running launcher closes.
+pub fn release(socket: &Path) {
+    let _ = std::fs::remove_file(socket);
+}
+
+// Accepts later starts on a thread of its own, each sends its links and hangs up
+#[cfg(unix)]
+fn serve(listener: std::os::unix::net::UnixListener, lock: File) -> Requests {
+    use std::io::Read;
+    use std::time::Duration;
+
+    let (sender, receiver) = mpsc::unbounded();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+            let mut request = String::new();
+            if stream.read_to_string(&mut request).is_err() {
+                continue;
+            }
+            let links = request.lines().filter(|line| !line.is_empty()).map(str::to_string).collect();
+            if sender.unbounded_send(links).is_err() {
+                break;
+            }
+        }
+    });
+    Requests { receiver: SharedReceiver::new(receiver), _lock: Some(Arc::new(lock)) }
+}
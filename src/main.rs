@@ -1,25 +1,20 @@
-use std::error::Error;
-use std::io::Read;
-use iced::widget::scrollable;
+#[macro_use]
+mod i18n;
+
+use iced::widget::{scrollable, text_input};
 
-use reqwest::blocking::get;
 use scraper::{ElementRef, Html, Node, Selector};
 use std::str::FromStr;
 
-use reqwest::header::{ACCEPT, ACCEPT_ENCODING, ACCEPT_LANGUAGE, CONTENT_TYPE, HOST, ORIGIN, REFERER, USER_AGENT};
-use flate2::read::GzDecoder;
+use reqwest::header::{CONTENT_TYPE, ORIGIN, REFERER};
 use md5::compute;
 
-pub async fn login_website_http_post(login: &String, password: &String) -> Result<String, Box<dyn Error>> {
-    let client = reqwest::Client::new();
+pub async fn login_website_http_post(session: &Session, login: &String, password: &String) -> Result<String, LauncherError> {
+    let endpoints = session.endpoints();
     let mut headers = reqwest::header::HeaderMap::new();
-    headers.insert(USER_AGENT, "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:127.0) Gecko/20100101 Firefox/127.0".parse()?);
-    headers.insert(ACCEPT, "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,*/*;q=0.8".parse()?);
-    headers.insert(ACCEPT_LANGUAGE, "en-US,en;q=0.5".parse()?);
-    headers.insert(ACCEPT_ENCODING, "gzip".parse()?);
     headers.insert(CONTENT_TYPE, "application/x-www-form-urlencoded".parse()?);
-    headers.insert(ORIGIN, "https://www.plazmaburst2.com".parse()?);
-    headers.insert(REFERER, "https://www.plazmaburst2.com/".parse()?);
+    headers.insert(ORIGIN, endpoints.website.parse()?);
+    headers.insert(REFERER, endpoints.front_page().parse()?);
     // Check if the password is already in MD5 format
     let md5_regex = regex::Regex::new(r"^[a-f0-9]{32}$").unwrap();
     let password_to_use = if md5_regex.is_match(&password) {
@@ -28,44 +23,24 @@ pub async fn login_website_http_post(login: &String, password: &String) -> Resul
         // Convert password to MD5 if it's not in MD5 format
         format!("{:x}", compute(password.as_bytes()))
     };
-    let response = client.post("https://www.plazmaburst2.com/")
+    let response = session.post(&endpoints.front_page(), Profile::Browser)
         .headers(headers)
         .body(format!("login={}&password={}&Submit=Log-in", login, password_to_use))
         .send()
-        .await?;
-    println!("login={}&password={}&Submit=Log-in", login, password_to_use);
+        .await
+        .context(t!("error-sign-in"))?;
     if response.status() != reqwest::StatusCode::OK {
-        return Err(Box::new(std::io::Error::new(std::io::ErrorKind::Other, "Request failed")));
+        return Err(LauncherError::network(t!("error-sign-in-status", status = response.status().to_string())));
     }
 
-    let content_encoding = response.headers().get(reqwest::header::CONTENT_ENCODING);
-    let body = if let Some(encoding) = content_encoding {
-        if encoding == "gzip" {
-            // If GZIP-encoded, decode the content
-            let bytes = response.bytes().await?;
-            let mut gz = GzDecoder::new(&bytes[..]);
-            let mut decoded_body = String::new();
-            gz.read_to_string(&mut decoded_body)?;
-            decoded_body
-        } else {
-            response.text().await?
-        }
-    } else {
-        response.text().await?
-    };
+    // gzip responses are decoded by the client
+    let body = response.text().await?;
 
-    let document = Html::parse_document(&body);
-    let selector = Selector::parse("td#wb_box").unwrap();
     let mut message_found = false;
 
-    if let Some(element) = document.select(&selector).next() {
-        let welcome_message = element.text().collect::<Vec<_>>().join(" ").trim().to_string();
-        if let Some(end) = welcome_message.find('!') {
-            let extracted_message = &welcome_message[..=end]; // Includes the exclamation mark
-            println!("Extracted Message: {}", extracted_message);
-            message_found = true;
-            return Ok(extracted_message.to_string());
-        }
+    if let Some(extracted_message) = parse_welcome_message(&body) {
+        message_found = true;
+        return Ok(extracted_message);
     }
     //let l = login.clone();
     //let p = password.clone();
@@ -83,31 +58,37 @@ pub async fn login_website_http_post(login: &String, password: &String) -> Resul
             }
         }
     }
-    return Ok("No connection to game server.".to_string());
+    return Ok(t!("no-connection"));
 
     // If neither the welcome message nor an alert() message is found, return a default error message
     //return login_website_http_post_rq_load(login.to_string(), password.to_string());
 }
 
+// "Welcome back, <login>!" from the box the website shows to signed in visitors
+fn parse_welcome_message(body: &str) -> Option<String> {
+    let document = Html::parse_document(body);
+    let selector = Selector::parse("td#wb_box").unwrap();
+    let element = document.select(&selector).next()?;
+    let welcome_message = element.text().collect::<Vec<_>>().join(" ").trim().to_string();
+    let end = welcome_message.find('!')?;
+    Some(welcome_message[..=end].to_string()) // Includes the exclamation mark
+}
+
 
 
 
 
-pub async fn login_website_http_post_rq_load(login: &String, password: &String) -> Result<String, Box<dyn Error>> {
-    let client = reqwest::Client::new();
+pub async fn login_website_http_post_rq_load(session: &Session, login: &String, password: &String) -> Result<String, LauncherError> {
     let mut headers = reqwest::header::HeaderMap::new();
-    headers.insert(USER_AGENT, "Shockwave Flash".parse()?);
-    headers.insert(ACCEPT, "text/xml, application/xml, application/xhtml+xml, text/html;q=0.9, text/plain;q=0.8, text/css, image/png, image/jpeg, image/gif;q=0.8, application/x-shockwave-flash, video/mp4;q=0.9, flv-application/octet-stream;q=0.8, video/x-flv;q=0.7, audio/mp4, application/futuresplash, */*;q=0.5".parse()?);
     headers.insert(CONTENT_TYPE, "application/x-www-form-urlencoded".parse()?);
     //headers.insert(b"x-flash-version", "11,7,700,224".parse()?);
-    headers.insert(HOST, "www.plazmaburst2.com".parse()?);
 
-    let response = client.post("https://www.plazmaburst2.com/pb2/server.php")
+    let response = session.post(&session.endpoints().server_php(), Profile::FlashPlayer)
         .headers(headers)
         .body(format!("rq=load&l={}&p={}", login, password))
         .send()
-        .await?;
-    println!("login={}&password={}&Submit=Log-in", login, password);
+        .await
+        .context(t!("error-game-sign-in"))?;
     if response.status() == 200
     {
         return Ok(response.text().await?);
@@ -122,9 +103,8 @@ pub async fn login_website_http_post_rq_load(login: &String, password: &String)
 
 
 
-fn get_news_and_dates_by_page_number(pagenumber: u8) -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
-    let body = get(format!("https://www.plazmaburst2.com/?a=&s=0&pg={}", pagenumber))?.text()?;
-    let fragment = Html::parse_document(&body);
+fn parse_news_page(body: &str) -> Vec<(String, String)> {
+    let fragment = Html::parse_document(body);
     let date_selector = Selector::parse("strong.news_date").unwrap();
 
     let mut results = Vec::new();
@@ -174,16 +154,34 @@ fn get_news_and_dates_by_page_number(pagenumber: u8) -> Result<Vec<(String, Stri
         news_text = news_text.replace("</a>.", "</a>");
         results.push((date, news_text));
     }
-    println!("News Text: {:?}", results); // Print news_text before pushing
-    Ok(results)
+    results
 }
 
 
 
-fn get_news_pages_count() -> Result<u8, Box<dyn std::error::Error>> {
-    let body = get("https://www.plazmaburst2.com/")?.text()?;
+// Number of news pages, the dates and texts of one page and the account the website has signed in
+type NewsPage = (u8, Vec<(String, String)>, Option<String>);
+
+// Front page and news page `pagenumber`. The front page carries both the news page count
+// and whether the session is still signed in.
+async fn fetch_news(session: &Session, pagenumber: u8) -> Result<NewsPage, LauncherError> {
+    let front_page = session.get(&session.endpoints().front_page(), Profile::Browser)
+        .await
+        .context(t!("error-load-news"))?
+        .text()
+        .await
+        .context(t!("error-load-news"))?;
+    let body = session.get(&session.endpoints().news_page(pagenumber), Profile::Browser)
+        .await
+        .context(t!("error-load-news-page", page = pagenumber + 1))?
+        .text()
+        .await
+        .context(t!("error-load-news-page", page = pagenumber + 1))?;
+    Ok((parse_news_pages_count(&front_page), parse_news_page(&body), session::signed_in_as(&front_page)))
+}
 
-    let fragment = Html::parse_document(&body);
+fn parse_news_pages_count(body: &str) -> u8 {
+    let fragment = Html::parse_document(body);
     let selector = Selector::parse("div > a").unwrap();
 
     let mut max_page = 0;
@@ -200,9 +198,7 @@ fn get_news_pages_count() -> Result<u8, Box<dyn std::error::Error>> {
         }
     }
 
-    println!("Last page number: {}", max_page + 1);
-
-    Ok(max_page + 1)
+    max_page + 1
 }
 
 
@@ -212,20 +208,100 @@ use once_cell::sync::Lazy;
 use iced::advanced::{Renderer, Widget};
 use iced::futures::SinkExt;
 mod styles;
+mod endpoints;
+mod error;
+mod http;
+mod notifications;
+mod settings;
+mod theme;
+mod profile;
+mod maps;
+mod social;
+mod versions;
+mod install;
+mod dmg;
+mod fonts;
+mod desktop;
+mod instance;
+mod receiver;
+mod tray;
+mod shortcuts;
+mod scheduler;
+mod platform;
+mod runtime;
+mod session;
+mod status;
 mod LauncherMainWindow;
+#[cfg(test)]
+mod tests;
+
+use endpoints::Endpoints;
+use error::{Context, LauncherError};
+use http::Profile;
+use notifications::Level;
+use session::Session;
 
 static SCROLLABLE_ID: Lazy<scrollable::Id> = Lazy::new(scrollable::Id::unique);
+static USERNAME_INPUT_ID: Lazy<text_input::Id> = Lazy::new(text_input::Id::unique);
 
 
 
 pub fn main() -> iced::Result {
-    iced::application(
+    let links = match desktop::parse_args(std::env::args().skip(1)) {
+        Ok(desktop::Invocation::Run(links)) => links,
+        Ok(desktop::Invocation::InstallDesktop) => exit_with(desktop::integrate().map(|entry| {
+            println!("Added the launcher to the app menu: {}", entry.display());
+        })),
+        Ok(desktop::Invocation::UninstallDesktop) => exit_with(desktop::data_dir().and_then(|dir| desktop::uninstall(&dir)).map(|()| {
+            println!("Removed the launcher from the app menu.");
+        })),
+        Ok(desktop::Invocation::Help) => {
+            println!("{}", desktop::USAGE);
+            exit_with(Ok(()))
+        }
+        Err(err) => exit_with(Err(err)),
+    };
+    // A launcher already running takes the links and comes to the front instead
+    let socket = match LauncherMainWindow::launcher_dir() {
+        Ok(dir) => instance::socket_path(&dir),
+        Err(err) => exit_with(Err(err)),
+    };
+    let (requests, claimed) = match instance::claim(&socket, &links) {
+        Ok(instance::Claim::Primary(requests)) => (requests, true),
+        Ok(instance::Claim::Forwarded) => exit_with(Ok(())),
+        // Better a second window than none
+        Err(err) => {
+            eprintln!("{}", err);
+            (instance::Requests::default(), false)
+        }
+    };
+    let result = iced::application(
         "Plazma Burst 2 Launcher",
         LauncherMainWindow::LauncherMainWindow::update,
         LauncherMainWindow::LauncherMainWindow::view,
     )
         .theme(LauncherMainWindow::LauncherMainWindow::theme)
-        .run()
+        .subscription(LauncherMainWindow::LauncherMainWindow::subscription)
+        .scale_factor(LauncherMainWindow::LauncherMainWindow::scale_factor)
+        .font(fonts::BUNDLED[0])
+        .font(fonts::BUNDLED[1])
+        .default_font(iced::Font::with_name(fonts::TEXT_FAMILY))
+        .run_with(move || LauncherMainWindow::LauncherMainWindow::start(links, requests));
+    if claimed {
+        instance::release(&socket);
+    }
+    result
+}
+
+// Command line actions that do not open the window
+fn exit_with(result: Result<(), LauncherError>) -> ! {
+    match result {
+        Ok(()) => std::process::exit(0),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(2)
+        }
+    }
 }
 
 
@@ -254,11 +330,68 @@ pub enum Message {
     LoginPressed,
     LoginCancel,
     LoginCompleted(bool, String),
+    RefreshProfile,
+    Logout,
+    DismissSessionExpired,
+    ProfileLoaded(Result<profile::PlayerProfile, LauncherError>),
+    ToggleSocial,
+    RefreshSocial,
+    SocialLoaded(Result<Option<social::Social>, LauncherError>),
     SubmitLogin,
     TogglePasswordVisibility,
     LinkClicked(String),
-    DownloadGamePressed,
-    PlayGamePressed
+    InstallActionPressed(install::InstallAction),
+    InstallActionFinished(install::InstallAction, Result<(), LauncherError>),
+    UninstallForgetSignIn(bool),
+    ConfirmUninstall,
+    CancelUninstall,
+    GameVersionSelected(versions::VersionChoice),
+    RuntimeSelected(runtime::RuntimeKind),
+    PlayGamePressed,
+    PlayAsGuestPressed,
+    PlayGameFinished(Result<(), LauncherError>),
+    GameStarted,
+    GameExited,
+    OpenMaps,
+    CloseMaps,
+    MapQueryChanged(String),
+    SearchMaps,
+    MapsPageChanged(u32),
+    MapsLoaded(Result<Vec<maps::MapEntry>, LauncherError>),
+    PlayMap(maps::MapEntry),
+    OpenLink(String), // a pb2:// link the launcher was started with
+    InstanceActivated(Vec<String>), // another start forwarded its links to this launcher
+    TrayActionSelected(tray::TrayAction),
+    ShortcutPressed(shortcuts::Shortcut),
+    ReloadNews,
+    NewsLoaded(u8, Result<NewsPage, LauncherError>),
+    RefreshNews, // background refresh, failures only go to the notification history
+    NewsRefreshed(u8, Result<NewsPage, LauncherError>),
+    CheckForUpdates,
+    UpdateChecked(Result<versions::UpdateStatus, LauncherError>),
+    CheckServerStatus,
+    ServerStatusChecked(status::StatusReport),
+    ErrorOccurred(LauncherError, Option<Box<Message>>), // error and the message that retries it
+    RetryPressed,
+    DismissError,
+    Notify(Level, String),
+    DownloadProgress(notifications::Progress),
+    DismissNotification(u64),
+    NotificationTick(std::time::Instant),
+    ToggleNotificationHistory,
+    ClearNotificationHistory,
+    ReloadThemes,
+    OpenSettings,
+    SettingChanged(settings::Setting),
+    BrowseInstallDir,
+    InstallDirPicked(Option<std::path::PathBuf>),
+    BrowsePlayer,
+    PlayerPicked(Option<std::path::PathBuf>),
+    DetectPlayers,
+    AddToAppMenu,
+    ApplySettings,
+    ResetSettings,
+    CancelSettings,
 }
 
 
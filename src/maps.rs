@@ -0,0 +1,130 @@
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+use reqwest::Url;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+
+use crate::endpoints::query_value;
+use crate::error::{Context, ErrorKind, LauncherError};
+use crate::http::Profile;
+use crate::session::Session;
+
+pub const RECENT_MAPS_FILE_NAME: &str = "launcher_recent_maps.json";
+
+/// Query string parameter the game reads the map to start on from.
+pub const MAP_PARAMETER: &str = "pl";
+
+// How many recently played maps are remembered
+const RECENT_LIMIT: usize = 20;
+
+/// A user made map as listed on the website.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapEntry {
+    /// Map name the game loads it by.
+    pub id: String,
+    pub name: String,
+    pub author: Option<String>,
+    pub rating: Option<f32>,
+}
+
+/// State of the map browser screen while it is open.
+#[derive(Debug, Default)]
+pub struct MapBrowser {
+    pub query: String,
+    pub page: u32,
+    pub results: Vec<MapEntry>,
+    pub loading: bool,
+}
+
+/// Maps the user started from the launcher, newest first.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentMaps {
+    maps: Vec<MapEntry>,
+}
+
+impl RecentMaps {
+    /// Reads the recent maps file. A missing file means nothing was played yet.
+    pub fn load(path: &Path) -> Result<RecentMaps, LauncherError> {
+        if !path.exists() {
+            return Ok(RecentMaps::default());
+        }
+        let content = fs::read_to_string(path).context(t!("error-read-recent-maps"))?;
+        serde_json::from_str(&content)
+            .map_err(|err| LauncherError::new(ErrorKind::Io, t!("error-recent-maps-damaged")).with_cause(err))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), LauncherError> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|err| LauncherError::new(ErrorKind::Io, t!("error-save-recent-maps")).with_cause(err))?;
+        fs::write(path, content).context(t!("error-save-recent-maps"))
+    }
+
+    /// Moves `map` to the front, dropping the oldest entries past the limit.
+    pub fn record(&mut self, map: MapEntry) {
+        self.maps.retain(|recent| recent.id != map.id);
+        self.maps.insert(0, map);
+        self.maps.truncate(RECENT_LIMIT);
+    }
+
+    pub fn maps(&self) -> &[MapEntry] {
+        &self.maps
+    }
+}
+
+/// Loads one page of the website map listing, optionally narrowed down by a search.
+pub async fn fetch_maps(session: &Session, query: &str, page: u32) -> Result<Vec<MapEntry>, LauncherError> {
+    let body = session
+        .get(&session.endpoints().maps_page(query.trim(), page)?, Profile::Browser)
+        .await
+        .context(t!("error-load-maps"))?
+        .text()
+        .await
+        .context(t!("error-load-maps"))?;
+    Ok(parse_map_listing(&body))
+}
+
+/// Reads the map listing table: one row per map, linking to the map and to its author's profile.
+pub fn parse_map_listing(body: &str) -> Vec<MapEntry> {
+    let document = Html::parse_document(body);
+    let row_selector = Selector::parse("tr").unwrap();
+    let link_selector = Selector::parse("a[href]").unwrap();
+    let rating = Regex::new(r"(?i)rating:?\s*(\d+(?:\.\d+)?)").unwrap();
+
+    let mut maps = Vec::new();
+    for row in document.select(&row_selector) {
+        let mut map = None;
+        let mut author = None;
+        for link in row.select(&link_selector) {
+            let href = link.value().attr("href").unwrap_or_default();
+            if let Some(id) = query_value(href, "map") {
+                map = Some((id, link_text(link)));
+            } else if query_value(href, "ac").is_some() {
+                author = Some(link_text(link));
+            }
+        }
+        let Some((id, name)) = map else {
+            continue;
+        };
+        let text = row.text().collect::<Vec<_>>().join(" ");
+        maps.push(MapEntry {
+            name: if name.is_empty() { id.clone() } else { name },
+            id,
+            author: author.filter(|author| !author.is_empty()),
+            rating: rating.captures(&text).and_then(|captures| captures[1].parse().ok()),
+        });
+    }
+    maps
+}
+
+/// `pl=<id>` with the id encoded for the game's query string.
+pub fn launch_parameter(id: &str) -> String {
+    let mut url = Url::parse("http://localhost/").expect("static URL is valid");
+    url.query_pairs_mut().append_pair(MAP_PARAMETER, id);
+    url.query().unwrap_or_default().to_string()
+}
+
+fn link_text(link: ElementRef) -> String {
+    link.text().collect::<String>().trim().to_string()
+}
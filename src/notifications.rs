@@ -0,0 +1,186 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use iced::futures::channel::mpsc;
+
+// How many past notifications the history panel keeps
+const HISTORY_LIMIT: usize = 100;
+// How many toasts are visible at once, older ones are dismissed early
+const ACTIVE_LIMIT: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Level {
+    /// How long a toast of this level stays on screen.
+    pub fn lifetime(&self) -> Duration {
+        match self {
+            Level::Info | Level::Success => Duration::from_secs(5),
+            Level::Warning => Duration::from_secs(8),
+            Level::Error => Duration::from_secs(12),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Level::Info => t!("level-info"),
+            Level::Success => t!("level-success"),
+            Level::Warning => t!("level-warning"),
+            Level::Error => t!("level-error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub id: u64,
+    pub level: Level,
+    pub text: String,
+    pub created: Instant,
+}
+
+/// Toasts currently on screen plus the history of everything that was reported.
+#[derive(Debug, Default)]
+pub struct Notifications {
+    active: VecDeque<Notification>,
+    history: VecDeque<Notification>,
+    next_id: u64,
+}
+
+impl Notifications {
+    /// Shows a toast and records it in the history.
+    pub fn push(&mut self, level: Level, text: impl Into<String>) -> u64 {
+        let notification = self.record(level, text);
+        let id = notification.id;
+        self.active.push_back(notification);
+        while self.active.len() > ACTIVE_LIMIT {
+            self.active.pop_front();
+        }
+        id
+    }
+
+    /// Records a notification in the history only, for outcomes already shown elsewhere.
+    pub fn log(&mut self, level: Level, text: impl Into<String>) {
+        self.record(level, text);
+    }
+
+    fn record(&mut self, level: Level, text: impl Into<String>) -> Notification {
+        let notification = Notification {
+            id: self.next_id,
+            level,
+            text: text.into(),
+            created: Instant::now(),
+        };
+        self.next_id += 1;
+        self.history.push_back(notification.clone());
+        while self.history.len() > HISTORY_LIMIT {
+            self.history.pop_front();
+        }
+        notification
+    }
+
+    pub fn dismiss(&mut self, id: u64) {
+        self.active.retain(|notification| notification.id != id);
+    }
+
+    /// Drops toasts that have outlived their level's lifetime.
+    pub fn expire(&mut self, now: Instant) {
+        self.active
+            .retain(|notification| now.duration_since(notification.created) < notification.level.lifetime());
+    }
+
+    pub fn active(&self) -> impl Iterator<Item = &Notification> {
+        self.active.iter()
+    }
+
+    pub fn has_active(&self) -> bool {
+        !self.active.is_empty()
+    }
+
+    /// Newest first.
+    pub fn history(&self) -> impl Iterator<Item = &Notification> {
+        self.history.iter().rev()
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+}
+
+/// Bytes of a download received so far, out of the size the server announced if it did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub received: u64,
+    pub total: Option<u64>,
+}
+
+/// Handle given to background operations so they can report outcomes to the UI while they run.
+#[derive(Debug, Clone)]
+pub struct Reporter {
+    sender: Option<mpsc::UnboundedSender<(Level, String)>>,
+    progress: Option<mpsc::UnboundedSender<Progress>>,
+}
+
+impl Reporter {
+    pub fn channel() -> (Self, mpsc::UnboundedReceiver<(Level, String)>) {
+        let (sender, receiver) = mpsc::unbounded();
+        (Reporter { sender: Some(sender), progress: None }, receiver)
+    }
+
+    /// A reporter that drops everything, for callers that don't show progress.
+    pub fn silent() -> Self {
+        Reporter { sender: None, progress: None }
+    }
+
+    /// Also forwards download progress, which is shown in place rather than as notifications.
+    pub fn with_progress(mut self) -> (Self, mpsc::UnboundedReceiver<Progress>) {
+        let (sender, receiver) = mpsc::unbounded();
+        self.progress = Some(sender);
+        (self, receiver)
+    }
+
+    pub fn progress(&self, progress: Progress) {
+        if let Some(sender) = &self.progress {
+            let _ = sender.unbounded_send(progress);
+        }
+    }
+
+    pub fn report(&self, level: Level, text: impl Into<String>) {
+        if let Some(sender) = &self.sender {
+            // The receiver is gone once the UI stopped listening, nothing left to tell
+            let _ = sender.unbounded_send((level, text.into()));
+        }
+    }
+
+    pub fn info(&self, text: impl Into<String>) {
+        self.report(Level::Info, text);
+    }
+
+    pub fn success(&self, text: impl Into<String>) {
+        self.report(Level::Success, text);
+    }
+
+    pub fn warning(&self, text: impl Into<String>) {
+        self.report(Level::Warning, text);
+    }
+
+    pub fn error(&self, text: impl Into<String>) {
+        self.report(Level::Error, text);
+    }
+}
+
+/// Human friendly age of a notification for the history panel.
+pub fn age(created: Instant, now: Instant) -> String {
+    let seconds = now.duration_since(created).as_secs();
+    match seconds {
+        0..=9 => t!("age-just-now"),
+        10..=59 => t!("age-seconds", count = seconds),
+        60..=3599 => t!("age-minutes", count = seconds / 60),
+        _ => t!("age-hours", count = seconds / 3600),
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt;
+
+/// Operating system the launcher runs on, with the CPU architecture where players differ by it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    Windows(Architecture),
+    MacOS(Architecture),
+    Linux(Architecture),
+    /// An operating system no player is published for, by its Rust name.
+    Other(&'static str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Architecture {
+    X86_64,
+    X86,
+    Aarch64,
+    /// Any other architecture, by its Rust name.
+    Other(&'static str),
+}
+
+impl Platform {
+    /// Reads a platform from Rust's `target_os` and `target_arch` names.
+    pub fn detect(os: &'static str, arch: &'static str) -> Platform {
+        let arch = Architecture::detect(arch);
+        match os {
+            "windows" => Platform::Windows(arch),
+            "macos" => Platform::MacOS(arch),
+            "linux" => Platform::Linux(arch),
+            other => Platform::Other(other),
+        }
+    }
+}
+
+impl Architecture {
+    pub fn detect(arch: &'static str) -> Architecture {
+        match arch {
+            "x86_64" => Architecture::X86_64,
+            "x86" => Architecture::X86,
+            "aarch64" => Architecture::Aarch64,
+            other => Architecture::Other(other),
+        }
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Platform::Windows(arch) => write!(f, "Windows {}", arch),
+            Platform::MacOS(arch) => write!(f, "macOS {}", arch),
+            Platform::Linux(arch) => write!(f, "Linux {}", arch),
+            Platform::Other(os) => write!(f, "{}", os),
+        }
+    }
+}
+
+impl fmt::Display for Architecture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Architecture::X86_64 => write!(f, "x86_64"),
+            Architecture::X86 => write!(f, "x86"),
+            Architecture::Aarch64 => write!(f, "aarch64"),
+            Architecture::Other(arch) => write!(f, "{}", arch),
+        }
+    }
+}
+
+/// Platform the launcher was built for.
+pub fn get_platform() -> Platform {
+    Platform::detect(std::env::consts::OS, std::env::consts::ARCH)
+}
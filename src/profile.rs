@@ -0,0 +1,126 @@
+use scraper::Html;
+
+use crate::error::{Context, LauncherError};
+use crate::http::Profile;
+use crate::session::Session;
+
+/// Account data shown on the profile card after signing in.
+///
+/// Every field is optional: it is filled from whatever the `rq=load` answer and the
+/// website profile page happen to carry.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlayerProfile {
+    pub login: String,
+    pub rank: Option<u32>,
+    pub xp: Option<u64>,
+    pub kills: Option<u64>,
+    pub deaths: Option<u64>,
+    pub clan: Option<String>,
+    pub last_seen: Option<String>,
+}
+
+impl PlayerProfile {
+    pub fn new(login: &str) -> Self {
+        PlayerProfile {
+            login: login.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn kill_death_ratio(&self) -> Option<f64> {
+        match (self.kills, self.deaths) {
+            (Some(kills), Some(0)) => Some(kills as f64),
+            (Some(kills), Some(deaths)) => Some(kills as f64 / deaths as f64),
+            _ => None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == PlayerProfile::new(&self.login)
+    }
+
+    /// Fills the fields this profile is missing from `other`.
+    pub fn merge(&mut self, other: PlayerProfile) {
+        self.rank = self.rank.or(other.rank);
+        self.xp = self.xp.or(other.xp);
+        self.kills = self.kills.or(other.kills);
+        self.deaths = self.deaths.or(other.deaths);
+        self.clan = self.clan.take().or(other.clan);
+        self.last_seen = self.last_seen.take().or(other.last_seen);
+    }
+
+    fn set(&mut self, field: &str, value: &str) {
+        let value = value.trim();
+        if value.is_empty() {
+            return;
+        }
+        match field.trim().trim_end_matches(':').to_lowercase().as_str() {
+            "rank" => self.rank = parse_number(value).map(|rank| rank as u32),
+            "xp" | "exp" | "experience" => self.xp = parse_number(value),
+            "kills" => self.kills = parse_number(value),
+            "deaths" => self.deaths = parse_number(value),
+            "clan" => self.clan = Some(value.to_string()),
+            "last seen" | "last_seen" | "last online" | "last activity" => self.last_seen = Some(value.to_string()),
+            _ => {}
+        }
+    }
+}
+
+/// Reads the `key=value` pairs following the `x` success marker of an `rq=load` answer.
+pub fn parse_rq_load(login: &str, body: &str) -> PlayerProfile {
+    let mut profile = PlayerProfile::new(login);
+    if !body.starts_with('x') {
+        return profile;
+    }
+    for pair in body[1..].split('&') {
+        if let Some((key, value)) = pair.split_once('=') {
+            profile.set(&key.replace('_', " "), &value.replace('+', " "));
+        }
+    }
+    profile
+}
+
+/// Reads a website profile page, where every value follows its `Label:` cell.
+pub fn parse_profile_page(login: &str, body: &str) -> PlayerProfile {
+    let mut profile = PlayerProfile::new(login);
+    let document = Html::parse_document(body);
+    let texts: Vec<&str> = document
+        .root_element()
+        .text()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .collect();
+
+    for pair in texts.windows(2) {
+        if pair[0].ends_with(':') {
+            profile.set(pair[0], pair[1]);
+        }
+    }
+    profile
+}
+
+/// Loads the profile from the standalone launcher endpoint, then completes it from the website.
+pub async fn fetch_profile(session: &Session, login: &String, password: &String) -> Result<PlayerProfile, LauncherError> {
+    let rq_load = crate::login_website_http_post_rq_load(session, login, password).await?;
+    let mut profile = parse_rq_load(login, &rq_load);
+
+    let page = session.get(&session.endpoints().profile_page(login)?, Profile::Browser)
+        .await
+        .context(t!("error-load-profile"))?
+        .text()
+        .await
+        .context(t!("error-load-profile"))?;
+    profile.merge(parse_profile_page(login, &page));
+
+    Ok(profile)
+}
+
+fn parse_number(value: &str) -> Option<u64> {
+    // Leading number only, allowing "34,500" and "34 500" thousand separators
+    let digits: String = value
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == ',' || *c == ' ')
+        .filter(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
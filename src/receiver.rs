@@ -0,0 +1,48 @@
+//! Channel receivers read by iced subscriptions, which may ask for their stream again on every
+//! update while only one stream can own the receiver.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use iced::futures::channel::mpsc;
+use iced::futures::stream::{self, BoxStream, StreamExt};
+
+/// Receiver shared by its clones, whose messages go to the first stream taken from it.
+pub struct SharedReceiver<T> {
+    receiver: Arc<Mutex<Option<mpsc::UnboundedReceiver<T>>>>,
+}
+
+impl<T: Send + 'static> SharedReceiver<T> {
+    pub fn new(receiver: mpsc::UnboundedReceiver<T>) -> Self {
+        SharedReceiver { receiver: Arc::new(Mutex::new(Some(receiver))) }
+    }
+
+    /// Messages as they arrive. Later streams end right away.
+    pub fn stream(&self) -> BoxStream<'static, T> {
+        let receiver = self.receiver.clone();
+        stream::once(async move { receiver.lock().ok().and_then(|mut receiver| receiver.take()) })
+            .filter_map(|receiver| async move { receiver })
+            .flatten()
+            .boxed()
+    }
+}
+
+// Not derived, which would ask the same of `T`
+impl<T> Clone for SharedReceiver<T> {
+    fn clone(&self) -> Self {
+        SharedReceiver { receiver: self.receiver.clone() }
+    }
+}
+
+/// A receiver that never gets anything.
+impl<T> Default for SharedReceiver<T> {
+    fn default() -> Self {
+        SharedReceiver { receiver: Arc::new(Mutex::new(None)) }
+    }
+}
+
+impl<T> fmt::Debug for SharedReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedReceiver").finish_non_exhaustive()
+    }
+}
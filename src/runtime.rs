@@ -0,0 +1,708 @@
+use std::env;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+
+use flate2::read::GzDecoder;
+use iced::futures::future::BoxFuture;
+use iced::futures::FutureExt;
+use regex::Regex;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Context, ErrorKind, LauncherError};
+use crate::notifications::Reporter;
+use crate::platform::{get_platform, Architecture, Platform};
+use crate::session::Session;
+use crate::settings::Settings;
+use crate::LauncherMainWindow::{download, download_and_save_file};
+
+/// Directory inside the install directory Ruffle is unpacked into.
+pub const RUFFLE_DIR_NAME: &str = "ruffle";
+
+/// Directory inside the install directory holding the Windows projector and the Wine prefix the launcher manages.
+pub const WINE_DIR_NAME: &str = "wine";
+
+/// Player the game SWF is started with.
+///
+/// Each backend knows where its player comes from, how to check it is usable and
+/// how the game parameters are passed to it.
+pub trait GameRuntime: Send + Sync {
+    fn kind(&self) -> RuntimeKind;
+
+    /// Whether a player is published for `platform`.
+    fn supports(&self, platform: &Platform) -> bool;
+
+    /// Path of the player executable inside the install directory.
+    fn executable(&self, install_dir: &Path) -> Result<PathBuf, LauncherError>;
+
+    /// Downloads the player unless it is already there and verifies.
+    fn download<'a>(&'a self, session: &'a Session, install_dir: &'a Path, reporter: &'a Reporter) -> BoxFuture<'a, Result<(), LauncherError>>;
+
+    /// Checks the player is in place and can be started, returning its executable.
+    fn verify(&self, install_dir: &Path) -> Result<PathBuf, LauncherError>;
+
+    /// Removes the player the launcher downloaded, a player the user picked stays.
+    fn remove(&self, install_dir: &Path) -> Result<(), LauncherError>;
+
+    /// Command line starting `swf` with the game's query string `parameters`.
+    fn arguments(&self, swf: &str, parameters: &str) -> Vec<String>;
+
+    /// Process starting the game, for runtimes that need more than the player and its arguments.
+    fn command(&self, install_dir: &Path, swf: &str, parameters: &str) -> Result<Command, LauncherError> {
+        let mut command = Command::new(self.verify(install_dir)?);
+        command.args(self.arguments(swf, parameters));
+        Ok(command)
+    }
+}
+
+/// Runtime backends the user can pick from, stored in the settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RuntimeKind {
+    #[default]
+    FlashPlayer,
+    Ruffle,
+    /// The Windows projector run through Wine or Proton.
+    Wine,
+}
+
+impl RuntimeKind {
+    pub const ALL: [RuntimeKind; 3] = [RuntimeKind::FlashPlayer, RuntimeKind::Ruffle, RuntimeKind::Wine];
+
+    /// Backend of this runtime, set up from the player options in `settings`.
+    pub fn runtime(self, settings: &Settings) -> Box<dyn GameRuntime> {
+        match self {
+            RuntimeKind::FlashPlayer => Box::new(FlashPlayer { player_path: settings.player_path.clone() }),
+            RuntimeKind::Ruffle => Box::new(Ruffle),
+            RuntimeKind::Wine => Box::new(Wine { wine: settings.wine_binary.clone() }),
+        }
+    }
+
+    /// Runtimes with a player for `platform`.
+    pub fn supported(platform: &Platform, settings: &Settings) -> Vec<RuntimeKind> {
+        RuntimeKind::ALL
+            .into_iter()
+            .filter(|kind| kind.runtime(settings).supports(platform))
+            .collect()
+    }
+
+    /// `preferred` when it runs on `platform`, otherwise the first runtime that does.
+    ///
+    /// `None` when no player at all is published for `platform`.
+    pub fn pick(preferred: RuntimeKind, platform: &Platform, settings: &Settings) -> Option<RuntimeKind> {
+        let supported = RuntimeKind::supported(platform, settings);
+        if supported.contains(&preferred) {
+            Some(preferred)
+        } else {
+            supported.first().copied()
+        }
+    }
+}
+
+impl fmt::Display for RuntimeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuntimeKind::FlashPlayer => write!(f, "Adobe Flash Player"),
+            RuntimeKind::Ruffle => write!(f, "Ruffle"),
+            RuntimeKind::Wine => write!(f, "{}", t!("runtime-wine")),
+        }
+    }
+}
+
+/// Starts the game with `runtime`, failing early when its player is missing.
+pub fn launch(runtime: &dyn GameRuntime, install_dir: &Path, swf: &str, parameters: &str) -> Result<Child, LauncherError> {
+    runtime
+        .command(install_dir, swf, parameters)?
+        .spawn()
+        .map_err(|err| LauncherError::process(t!("error-start-game")).with_cause(err))
+}
+
+/// The Adobe Flash Player projector, from the player mirror unless the user has their own.
+pub struct FlashPlayer {
+    player_path: Option<PathBuf>,
+}
+
+// Define a struct to hold download information
+#[derive(Debug)]
+struct DownloadInfo {
+    remote_name: &'static str,
+    file_name: &'static str,
+}
+
+impl Platform {
+    fn get_download_info(&self) -> Option<&'static DownloadInfo> {
+        match self {
+            Platform::Windows(arch) => match arch {
+                Architecture::X86_64 => Some(&DownloadInfo {
+                    remote_name: PLAYER_X86_64_WINDOWS,
+                    file_name: "flashplayer.exe",
+                }),
+                // Windows on Arm runs the 32 bit projector emulated
+                Architecture::X86 | Architecture::Aarch64 => Some(&DownloadInfo {
+                    remote_name: PLAYER_I686_WINDOWS,
+                    file_name: "flashplayer.exe",
+                }),
+                Architecture::Other(_) => None,
+            },
+            // Intel only, Apple Silicon runs it through Rosetta. The app is unpacked from the disk image
+            Platform::MacOS(_) => Some(&DownloadInfo {
+                remote_name: PLAYER_MACOS,
+                file_name: PLAYER_MACOS_APP,
+            }),
+            Platform::Linux(arch) => match arch {
+                Architecture::X86_64 => Some(&DownloadInfo {
+                    remote_name: PLAYER_X86_64_LINUX,
+                    file_name: "flashplayer",
+                }),
+                Architecture::X86 => Some(&DownloadInfo {
+                    remote_name: PLAYER_I686_LINUX,
+                    file_name: "flashplayer",
+                }),
+                Architecture::Aarch64 | Architecture::Other(_) => None,
+            },
+            Platform::Other(_) => None,
+        }
+    }
+}
+
+// Define constants for the projector file names on the player mirror for readability
+const PLAYER_X86_64_WINDOWS: &str = "flashplayer-x86_64-pc-windows-msvc.exe";
+const PLAYER_I686_WINDOWS: &str = "flashplayer-i686-pc-windows-msvc.exe";
+const PLAYER_MACOS: &str = "flashplayer_32_sa.dmg";
+const PLAYER_MACOS_APP: &str = "Flash Player.app";
+const PLAYER_X86_64_LINUX: &str = "flashplayer-x86_64-unknown-linux-gnu";
+const PLAYER_I686_LINUX: &str = "flashplayer-i686-unknown-linux-gnu";
+
+fn platform_download_info() -> Result<&'static DownloadInfo, LauncherError> {
+    let platform = get_platform();
+    platform
+        .get_download_info()
+        .ok_or_else(|| LauncherError::platform(t!("error-no-player", platform = platform.to_string())))
+}
+
+impl GameRuntime for FlashPlayer {
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::FlashPlayer
+    }
+
+    // The user's own player runs wherever they got it for
+    fn supports(&self, platform: &Platform) -> bool {
+        self.player_path.is_some() || platform.get_download_info().is_some()
+    }
+
+    fn executable(&self, install_dir: &Path) -> Result<PathBuf, LauncherError> {
+        match &self.player_path {
+            Some(path) => Ok(path.clone()),
+            None => Ok(install_dir.join(platform_download_info()?.file_name)),
+        }
+    }
+
+    fn download<'a>(&'a self, session: &'a Session, install_dir: &'a Path, reporter: &'a Reporter) -> BoxFuture<'a, Result<(), LauncherError>> {
+        async move {
+            if let Some(path) = &self.player_path {
+                validate_player(path)?;
+                reporter.info(t!("using-player", path = path.display().to_string()));
+                return Ok(());
+            }
+            let download_info = platform_download_info()?;
+            let file_path = install_dir.join(download_info.file_name);
+            // A damaged download is fetched again
+            if self.verify(install_dir).is_ok() {
+                reporter.info(t!("player-exists"));
+                return Ok(());
+            }
+            reporter.info(t!("downloading-player"));
+            if download_info.remote_name.ends_with(".dmg") {
+                let image = download(session, &session.endpoints().player(download_info.remote_name), reporter)
+                    .await
+                    .context(t!("error-download-player"))?;
+                install_app(&image, &file_path)?;
+                // Launchers before the app was unpacked left the image itself behind
+                let _ = fs::remove_file(install_dir.join("flashplayer.dmg"));
+            } else {
+                remove_path(&size_record(&file_path))?;
+                download_and_save_file(session, &session.endpoints().player(download_info.remote_name), &file_path, reporter)
+                    .await
+                    .context(t!("error-download-player"))?;
+                make_executable(&file_path)?;
+                record_size(&file_path)?;
+            }
+            reporter.success(t!("player-downloaded"));
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn verify(&self, install_dir: &Path) -> Result<PathBuf, LauncherError> {
+        if let Some(path) = &self.player_path {
+            return validate_player(path);
+        }
+        let executable = self.executable(install_dir)?;
+        if executable.extension().is_some_and(|extension| extension == "app") {
+            if !executable.is_dir() {
+                return Err(LauncherError::new(ErrorKind::Io, t!("error-not-downloaded", name = "Flashplayer")));
+            }
+            return validate_player(&executable);
+        }
+        verify_executable(&executable, "Flashplayer")?;
+        Ok(executable)
+    }
+
+    fn remove(&self, install_dir: &Path) -> Result<(), LauncherError> {
+        if self.player_path.is_some() {
+            return Ok(());
+        }
+        // Nothing was downloaded where no projector is published
+        let Ok(executable) = self.executable(install_dir) else {
+            return Ok(());
+        };
+        remove_path(&executable)?;
+        remove_path(&size_record(&executable))?;
+        remove_path(&install_dir.join("flashplayer.dmg"))
+    }
+
+    // The projector reads the parameters from the query string of the movie it opens
+    fn arguments(&self, swf: &str, parameters: &str) -> Vec<String> {
+        vec![format!("{}{}", swf, parameters)]
+    }
+}
+
+/// The Ruffle desktop player, a Flash emulator that keeps working where the projector does not.
+pub struct Ruffle;
+
+// Ruffle nightly the launcher is tested against, released on the Ruffle mirror
+const RUFFLE_X86_64_WINDOWS: &str = "ruffle-nightly-2024_07_01-windows-x86_64.zip";
+const RUFFLE_I686_WINDOWS: &str = "ruffle-nightly-2024_07_01-windows-x86_32.zip";
+const RUFFLE_MACOS: &str = "ruffle-nightly-2024_07_01-macos-universal.tar.gz";
+const RUFFLE_X86_64_LINUX: &str = "ruffle-nightly-2024_07_01-linux-x86_64.tar.gz";
+
+impl Ruffle {
+    fn archive_name(platform: &Platform) -> Option<&'static str> {
+        match platform {
+            Platform::Windows(Architecture::X86_64) => Some(RUFFLE_X86_64_WINDOWS),
+            Platform::Windows(Architecture::X86 | Architecture::Aarch64) => Some(RUFFLE_I686_WINDOWS),
+            Platform::MacOS(_) => Some(RUFFLE_MACOS),
+            Platform::Linux(Architecture::X86_64) => Some(RUFFLE_X86_64_LINUX),
+            _ => None,
+        }
+    }
+
+    fn platform_archive_name() -> Result<&'static str, LauncherError> {
+        let platform = get_platform();
+        Ruffle::archive_name(&platform)
+            .ok_or_else(|| LauncherError::platform(t!("error-no-ruffle", platform = platform.to_string())))
+    }
+}
+
+impl GameRuntime for Ruffle {
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Ruffle
+    }
+
+    fn supports(&self, platform: &Platform) -> bool {
+        Ruffle::archive_name(platform).is_some()
+    }
+
+    fn executable(&self, install_dir: &Path) -> Result<PathBuf, LauncherError> {
+        let dir = install_dir.join(RUFFLE_DIR_NAME);
+        match get_platform() {
+            Platform::Windows(_) => Ok(dir.join("ruffle.exe")),
+            Platform::MacOS(_) => Ok(dir.join("Ruffle.app").join("Contents").join("MacOS").join("ruffle")),
+            Platform::Linux(_) => Ok(dir.join("ruffle")),
+            platform @ Platform::Other(_) => Err(LauncherError::platform(t!("error-no-ruffle", platform = platform.to_string()))),
+        }
+    }
+
+    fn download<'a>(&'a self, session: &'a Session, install_dir: &'a Path, reporter: &'a Reporter) -> BoxFuture<'a, Result<(), LauncherError>> {
+        async move {
+            let archive_name = Ruffle::platform_archive_name()?;
+            if self.verify(install_dir).is_ok() {
+                reporter.info(t!("ruffle-exists"));
+                return Ok(());
+            }
+            reporter.info(t!("downloading-ruffle"));
+            let archive = download(session, &session.endpoints().ruffle(archive_name), reporter)
+                .await
+                .context(t!("error-download-ruffle"))?;
+
+            // Unpack next to the final directory so a broken archive leaves no half installed player
+            let dir = install_dir.join(RUFFLE_DIR_NAME);
+            let unpacked = install_dir.join(format!("{}.part", RUFFLE_DIR_NAME));
+            let _ = fs::remove_dir_all(&unpacked);
+            fs::create_dir_all(&unpacked).context(t!("error-unpack-ruffle"))?;
+            if let Err(err) = unpack(archive_name, &archive, &unpacked) {
+                let _ = fs::remove_dir_all(&unpacked);
+                return Err(err);
+            }
+            let _ = fs::remove_dir_all(&dir);
+            fs::rename(&unpacked, &dir).context(t!("error-unpack-ruffle"))?;
+
+            let executable = self.executable(install_dir)?;
+            make_executable(&executable)?;
+            record_size(&executable)?;
+            verify_executable(&executable, "Ruffle")?;
+            reporter.success(t!("ruffle-downloaded"));
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn verify(&self, install_dir: &Path) -> Result<PathBuf, LauncherError> {
+        let executable = self.executable(install_dir)?;
+        verify_executable(&executable, "Ruffle")?;
+        Ok(executable)
+    }
+
+    fn remove(&self, install_dir: &Path) -> Result<(), LauncherError> {
+        remove_path(&install_dir.join(RUFFLE_DIR_NAME))?;
+        remove_path(&install_dir.join(format!("{}.part", RUFFLE_DIR_NAME)))
+    }
+
+    // Ruffle takes the game parameters as flashvars, one `-P name=value` per parameter
+    fn arguments(&self, swf: &str, parameters: &str) -> Vec<String> {
+        let query = Url::parse(&format!("http://localhost/{}", parameters)).ok();
+        let mut arguments: Vec<String> = query
+            .iter()
+            .flat_map(|url| url.query_pairs())
+            .map(|(name, value)| format!("-P{}={}", name, value))
+            .collect();
+        arguments.push(swf.to_string());
+        arguments
+    }
+}
+
+/// The Windows projector started through Wine, for Linux where the native projector lags behind.
+///
+/// Proton works too, by pointing the Wine binary at the `files/bin/wine` of a Proton install.
+pub struct Wine {
+    /// Wine binary, a name looked up in PATH or a path.
+    wine: String,
+}
+
+impl Wine {
+    fn prefix(install_dir: &Path) -> PathBuf {
+        install_dir.join(WINE_DIR_NAME).join("prefix")
+    }
+
+    fn wine_binary(&self) -> Result<PathBuf, LauncherError> {
+        let wine = Path::new(&self.wine);
+        let found = if wine.components().count() > 1 {
+            Some(wine.to_path_buf())
+        } else {
+            env::var_os("PATH")
+                .map(|path_var| env::split_paths(&path_var).map(|dir| dir.join(wine)).collect::<Vec<_>>())
+                .unwrap_or_default()
+                .into_iter()
+                .find(|candidate| candidate.is_file())
+        };
+        let wine = found.ok_or_else(|| {
+            LauncherError::platform(t!("error-no-wine", wine = self.wine.as_str()))
+        })?;
+        validate_player(&wine)
+    }
+}
+
+/// Windows projector run through Wine, built for the same architecture as the Linux host.
+pub fn windows_projector(platform: &Platform) -> &'static str {
+    match platform {
+        Platform::Linux(Architecture::X86) => PLAYER_I686_WINDOWS,
+        _ => PLAYER_X86_64_WINDOWS,
+    }
+}
+
+impl GameRuntime for Wine {
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Wine
+    }
+
+    // Wine runs Windows programs natively on x86 only
+    fn supports(&self, platform: &Platform) -> bool {
+        matches!(platform, Platform::Linux(Architecture::X86_64 | Architecture::X86))
+    }
+
+    fn executable(&self, install_dir: &Path) -> Result<PathBuf, LauncherError> {
+        Ok(install_dir.join(WINE_DIR_NAME).join("flashplayer.exe"))
+    }
+
+    fn download<'a>(&'a self, session: &'a Session, install_dir: &'a Path, reporter: &'a Reporter) -> BoxFuture<'a, Result<(), LauncherError>> {
+        async move {
+            let wine = self.wine_binary()?;
+            let projector = self.executable(install_dir)?;
+            if verify_executable(&projector, "Windows Flashplayer").is_ok() {
+                reporter.info(t!("windows-player-exists"));
+            } else {
+                reporter.info(t!("downloading-windows-player"));
+                fs::create_dir_all(install_dir.join(WINE_DIR_NAME)).context(t!("error-create-wine-dir"))?;
+                remove_path(&size_record(&projector))?;
+                download_and_save_file(session, &session.endpoints().player(windows_projector(&get_platform())), &projector, reporter)
+                    .await
+                    .context(t!("error-download-player"))?;
+                record_size(&projector)?;
+                reporter.success(t!("windows-player-downloaded"));
+            }
+
+            let prefix = Wine::prefix(install_dir);
+            if !prefix.join("system.reg").exists() {
+                reporter.info(t!("setting-up-wine-prefix"));
+                fs::create_dir_all(&prefix).context(t!("error-create-wine-prefix"))?;
+                // wineboot takes a while on first run, the window keeps responding meanwhile
+                let status = tokio::process::Command::new(&wine)
+                    .args(["wineboot", "--init"])
+                    .env("WINEPREFIX", &prefix)
+                    .status()
+                    .await
+                    .map_err(|err| LauncherError::process(t!("error-wine-prefix")).with_cause(err))?;
+                if !status.success() {
+                    return Err(LauncherError::process(t!("error-wineboot-failed", status = status.to_string())));
+                }
+                reporter.success(t!("wine-prefix-ready"));
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+
+    // The projector is a Windows program, it is checked for presence only
+    fn verify(&self, install_dir: &Path) -> Result<PathBuf, LauncherError> {
+        let projector = self.executable(install_dir)?;
+        verify_executable(&projector, "Windows Flashplayer")?;
+        self.wine_binary()
+    }
+
+    // The prefix goes along, it only holds what the launcher set up for the projector
+    fn remove(&self, install_dir: &Path) -> Result<(), LauncherError> {
+        remove_path(&install_dir.join(WINE_DIR_NAME))
+    }
+
+    // Wine opens the movie from a Windows path, the root of the Linux file system is drive Z:
+    fn arguments(&self, swf: &str, parameters: &str) -> Vec<String> {
+        vec![format!("{}{}", wine_path(swf), parameters)]
+    }
+
+    fn command(&self, install_dir: &Path, swf: &str, parameters: &str) -> Result<Command, LauncherError> {
+        let wine = self.verify(install_dir)?;
+        let mut command = Command::new(wine);
+        command
+            .arg(self.executable(install_dir)?)
+            .args(self.arguments(swf, parameters))
+            .env("WINEPREFIX", Wine::prefix(install_dir))
+            .env("WINEDEBUG", "-all");
+        Ok(command)
+    }
+}
+
+/// `path` as Wine sees it, through the Z: drive mapped to `/`.
+pub fn wine_path(path: &str) -> String {
+    match path.strip_prefix('/') {
+        Some(rest) => format!("Z:\\{}", rest.replace('/', "\\")),
+        None => path.to_string(),
+    }
+}
+
+fn unpack(archive_name: &str, archive: &[u8], dir: &Path) -> Result<(), LauncherError> {
+    let damaged = |err: &dyn fmt::Display| LauncherError::new(ErrorKind::Io, t!("error-ruffle-damaged")).with_cause(err);
+    if archive_name.ends_with(".zip") {
+        zip::ZipArchive::new(Cursor::new(archive))
+            .and_then(|mut zip| zip.extract(dir))
+            .map_err(|err| damaged(&err))
+    } else {
+        tar::Archive::new(GzDecoder::new(archive)).unpack(dir).map_err(|err| damaged(&err))
+    }
+}
+
+// File names Flash Player projectors are installed under
+const PLAYER_NAMES: &[&str] = &[
+    "flashplayer",
+    "flashplayerdebugger",
+    "flashplayer_sa",
+    "flashplayer.exe",
+    "flashplayer_sa.exe",
+    "flashplayer_32_sa.exe",
+    "flashplayerdebugger.exe",
+];
+
+// Flathub id of the Flash Player projector
+const FLATPAK_PLAYER: &str = "com.adobe.Flash-Player-Projector";
+
+/// Flash Players already installed on this system, from PATH, common install locations and Flatpak.
+pub fn detect_flash_players() -> Vec<PathBuf> {
+    find_players(env::var_os("PATH").as_deref(), &player_locations())
+}
+
+/// Players found in the `path_var` directories and at `locations`, each one validated and listed once.
+pub fn find_players(path_var: Option<&OsStr>, locations: &[PathBuf]) -> Vec<PathBuf> {
+    let in_path = path_var
+        .map(|path_var| env::split_paths(path_var).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|dir| PLAYER_NAMES.iter().map(move |name| dir.join(name)));
+
+    let mut players: Vec<PathBuf> = Vec::new();
+    for candidate in in_path.chain(locations.iter().cloned()) {
+        if validate_player(&candidate).is_ok() && !players.contains(&candidate) {
+            players.push(candidate);
+        }
+    }
+    players
+}
+
+fn player_locations() -> Vec<PathBuf> {
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let mut locations = Vec::new();
+    match get_platform() {
+        Platform::Windows(_) => {
+            for variable in ["ProgramFiles", "ProgramFiles(x86)", "LOCALAPPDATA"] {
+                if let Some(dir) = env::var_os(variable).map(PathBuf::from) {
+                    locations.push(dir.join("Flash Player Projector").join("flashplayer.exe"));
+                    locations.push(dir.join("Adobe").join("Flash Player").join("flashplayer.exe"));
+                }
+            }
+        }
+        Platform::MacOS(_) => {
+            let mut applications = vec![PathBuf::from("/Applications")];
+            applications.extend(home.iter().map(|home| home.join("Applications")));
+            for dir in applications {
+                locations.push(dir.join("Flash Player.app"));
+                locations.push(dir.join("Flash Player Debugger.app"));
+            }
+        }
+        Platform::Linux(_) | Platform::Other(_) => {
+            locations.push(PathBuf::from("/opt/flashplayer/flashplayer"));
+            locations.push(PathBuf::from("/usr/lib/flashplayer/flashplayer"));
+            locations.push(PathBuf::from("/var/lib/flatpak/exports/bin").join(FLATPAK_PLAYER));
+            if let Some(home) = &home {
+                locations.push(home.join(".local/bin/flashplayer"));
+                locations.push(home.join(".local/share/flatpak/exports/bin").join(FLATPAK_PLAYER));
+            }
+        }
+    }
+    locations
+}
+
+/// Checks `path` is a program that can be started, returning the executable to start.
+///
+/// A macOS `.app` bundle resolves to the executable inside it.
+pub fn validate_player(path: &Path) -> Result<PathBuf, LauncherError> {
+    let executable = if path.is_dir() {
+        bundle_executable(path)?
+    } else {
+        path.to_path_buf()
+    };
+    let mut header = [0u8; 4];
+    let read = File::open(&executable)
+        .and_then(|mut file| file.read(&mut header))
+        .map_err(|err| LauncherError::new(ErrorKind::Io, t!("error-cannot-read", path = executable.display().to_string())).with_cause(err))?;
+    if !is_program(&header[..read]) {
+        return Err(LauncherError::new(ErrorKind::Io, t!("error-not-a-program", path = executable.display().to_string())));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = fs::metadata(&executable).map(|metadata| metadata.permissions().mode()).unwrap_or_default();
+        if mode & 0o111 == 0 {
+            return Err(LauncherError::new(ErrorKind::Io, t!("error-not-executable", path = executable.display().to_string())));
+        }
+    }
+    Ok(executable)
+}
+
+// Executables start with an ELF, PE or Mach-O header, launch scripts (Flatpak exports) with a shebang
+fn is_program(header: &[u8]) -> bool {
+    const MACH_O: [[u8; 4]; 5] = [
+        [0xfe, 0xed, 0xfa, 0xce],
+        [0xfe, 0xed, 0xfa, 0xcf],
+        [0xce, 0xfa, 0xed, 0xfe],
+        [0xcf, 0xfa, 0xed, 0xfe],
+        [0xca, 0xfe, 0xba, 0xbe],
+    ];
+    header.starts_with(b"\x7fELF")
+        || header.starts_with(b"MZ")
+        || header.starts_with(b"#!")
+        || MACH_O.iter().any(|magic| header == magic)
+}
+
+/// Unpacks the app bundle in the disk image `image` to `bundle`, checking it holds a program.
+///
+/// Nothing is left at `bundle` when the image turns out to be damaged.
+pub fn install_app(image: &[u8], bundle: &Path) -> Result<PathBuf, LauncherError> {
+    let unpacked = bundle.with_extension("app.part");
+    let installed = crate::dmg::extract_app(image, &unpacked).and_then(|_| validate_player(&unpacked));
+    if let Err(err) = installed {
+        let _ = fs::remove_dir_all(&unpacked);
+        return Err(err);
+    }
+    let _ = fs::remove_dir_all(bundle);
+    fs::rename(&unpacked, bundle).context(t!("error-unpack-player"))?;
+    bundle_executable(bundle)
+}
+
+// The executable named in the bundle's Info.plist, or the only file next to it
+fn bundle_executable(bundle: &Path) -> Result<PathBuf, LauncherError> {
+    let not_a_player = || LauncherError::new(ErrorKind::Io, t!("error-not-a-program", path = bundle.display().to_string()));
+    let dir = bundle.join("Contents").join("MacOS");
+    let named = fs::read_to_string(bundle.join("Contents").join("Info.plist")).ok().and_then(|plist| {
+        let name = Regex::new(r"<key>CFBundleExecutable</key>\s*<string>([^<]+)</string>").unwrap();
+        name.captures(&plist).map(|captures| dir.join(&captures[1]))
+    });
+    if let Some(executable) = named.filter(|path| path.is_file()) {
+        return Ok(executable);
+    }
+    fs::read_dir(&dir)
+        .map_err(|_| not_a_player())?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.is_file())
+        .ok_or_else(not_a_player)
+}
+
+fn verify_executable(path: &Path, name: &str) -> Result<(), LauncherError> {
+    let damaged = || LauncherError::new(ErrorKind::Io, t!("error-download-damaged", name = name));
+    let metadata = fs::metadata(path).map_err(|_| LauncherError::new(ErrorKind::Io, t!("error-not-downloaded", name = name)))?;
+    if !metadata.is_file() || metadata.len() == 0 {
+        return Err(damaged());
+    }
+    // Players downloaded before sizes were recorded have nothing to compare with
+    match fs::read_to_string(size_record(path)) {
+        Ok(recorded) if recorded.trim().parse() != Ok(metadata.len()) => Err(damaged()),
+        _ => Ok(()),
+    }
+}
+
+// Size of a downloaded player, written once it is complete so a file cut short later is noticed
+fn size_record(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".size");
+    PathBuf::from(name)
+}
+
+fn record_size(path: &Path) -> Result<(), LauncherError> {
+    let size = fs::metadata(path).context(t!("error-cannot-read", path = path.display().to_string()))?.len();
+    fs::write(size_record(path), size.to_string()).context(t!("error-record-size", path = path.display().to_string()))
+}
+
+/// Removes the file or directory at `path`, a missing one is not an error.
+pub fn remove_path(path: &Path) -> Result<(), LauncherError> {
+    let removed = match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(_) => return Ok(()),
+    };
+    removed.context(t!("error-remove", path = path.display().to_string()))
+}
+
+// Downloads lose the executable bit, the player could not be started without it
+fn make_executable(path: &Path) -> Result<(), LauncherError> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755)).context(t!("error-make-executable"))?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
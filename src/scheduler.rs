@@ -0,0 +1,46 @@
+//! Background jobs the launcher repeats on its own: the game update check and the news refresh.
+//!
+//! Every run is moved by up to a tenth of the interval, so launchers started together do not
+//! all ask the website at the same moment.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use iced::futures::stream;
+use iced::Subscription;
+
+/// Delay before the first update check after the launcher starts.
+pub const FIRST_UPDATE_CHECK: Duration = Duration::from_secs(5);
+
+// Share of the interval a run may come earlier or later
+const JITTER: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Job {
+    UpdateCheck,
+    NewsRefresh,
+}
+
+/// `interval` moved by the jitter, `random` picks where in the jitter window.
+pub fn jittered(interval: Duration, random: u64) -> Duration {
+    let offset = (random % 2001) as f64 / 1000.0 - 1.0;
+    interval.mul_f64(1.0 + JITTER * offset)
+}
+
+/// Runs `job` after `first`, then every `interval`, each run give or take the jitter.
+pub fn every(job: Job, first: Duration, interval: Duration) -> Subscription<Job> {
+    // Changing the interval starts a new schedule
+    Subscription::run_with_id(
+        (job, interval),
+        stream::unfold(first, move |delay| async move {
+            tokio::time::sleep(jittered(delay, random())).await;
+            Some((job, interval))
+        }),
+    )
+}
+
+// Randomly keyed like every hash map, no random number crate needed
+fn random() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
@@ -0,0 +1,186 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use reqwest::cookie::{CookieStore, Jar};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Context, ErrorKind, LauncherError};
+use crate::http::{self, HttpOptions, Profile};
+use crate::Endpoints;
+
+pub const SESSION_FILE_NAME: &str = "launcher_session.json";
+
+/// HTTP state shared by every request the launcher makes.
+///
+/// All requests go through the same configured clients and cookie jar, so the website keeps
+/// recognising the account after signing in. Clones are cheap and share the jar.
+#[derive(Clone)]
+pub struct Session {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    endpoints: Endpoints,
+    options: HttpOptions,
+    jar: Arc<Jar>,
+    client: reqwest::Client,
+}
+
+// Layout of the session file
+#[derive(Serialize, Deserialize)]
+struct StoredSession {
+    website: String,
+    cookies: String,
+}
+
+impl Session {
+    pub fn new(endpoints: Endpoints, options: &HttpOptions) -> Result<Session, LauncherError> {
+        let jar = Arc::new(Jar::default());
+        let client = options
+            .client_builder()?
+            .cookie_provider(jar.clone())
+            .build()
+            .map_err(|err| LauncherError::network(t!("error-http-client")).with_cause(err))?;
+        Ok(Session {
+            inner: Arc::new(Inner {
+                endpoints,
+                options: options.clone(),
+                jar,
+                client,
+            }),
+        })
+    }
+
+    /// Starts a session with the cookies written by [`Session::save`].
+    ///
+    /// Cookies saved for another website are dropped. A missing file gives an empty session.
+    pub fn restore(endpoints: Endpoints, options: &HttpOptions, path: &Path) -> Result<Session, LauncherError> {
+        let session = Session::new(endpoints, options)?;
+        if !path.exists() {
+            return Ok(session);
+        }
+        let content = fs::read_to_string(path).context(t!("error-read-session"))?;
+        let stored: StoredSession = serde_json::from_str(&content)
+            .map_err(|err| LauncherError::new(ErrorKind::Io, t!("error-session-damaged")).with_cause(err))?;
+        if stored.website == session.endpoints().website {
+            if let Some(url) = session.website_url() {
+                for cookie in stored.cookies.split("; ").filter(|cookie| !cookie.is_empty()) {
+                    session.inner.jar.add_cookie_str(cookie, &url);
+                }
+            }
+        }
+        Ok(session)
+    }
+
+    /// Writes the website cookies so the next launch is still signed in.
+    ///
+    /// The file is readable by the current user only, since the cookies grant access to the account.
+    pub fn save(&self, path: &Path) -> Result<(), LauncherError> {
+        let Some(cookies) = self.website_cookies() else {
+            return forget(path);
+        };
+        let stored = StoredSession {
+            website: self.endpoints().website.clone(),
+            cookies,
+        };
+        let content = serde_json::to_string_pretty(&stored)
+            .map_err(|err| LauncherError::new(ErrorKind::Io, t!("error-save-session")).with_cause(err))?;
+        write_private(path, &content).context(t!("error-save-session"))
+    }
+
+    pub fn endpoints(&self) -> &Endpoints {
+        &self.inner.endpoints
+    }
+
+    /// GET that fails on error statuses and is retried with a growing delay while the failure looks transient.
+    pub async fn get(&self, url: &str, profile: Profile) -> reqwest::Result<reqwest::Response> {
+        let mut attempt = 0;
+        loop {
+            let result = self.inner.client
+                .get(url)
+                .headers(profile.headers())
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            match result {
+                Err(err) if attempt < self.inner.options.retries && http::is_transient(&err) => {
+                    tokio::time::sleep(http::retry_delay(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Single GET that leaves status codes to the caller, for probes that report failures themselves.
+    pub async fn get_once(&self, url: &str, profile: Profile) -> reqwest::Result<reqwest::Response> {
+        self.inner.client.get(url).headers(profile.headers()).send().await
+    }
+
+    /// POST with the headers of `profile`. Posts are never retried, they may not be safe to repeat.
+    pub fn post(&self, url: &str, profile: Profile) -> reqwest::RequestBuilder {
+        self.inner.client.post(url).headers(profile.headers())
+    }
+
+    /// Whether the website handed out cookies, which means a website sign in happened.
+    pub fn has_cookies(&self) -> bool {
+        self.website_cookies().is_some()
+    }
+
+    fn website_cookies(&self) -> Option<String> {
+        let cookies = self.inner.jar.cookies(&self.website_url()?)?;
+        cookies.to_str().ok().map(str::to_string)
+    }
+
+    // An invalid website never got cookies, so it is treated as having none
+    fn website_url(&self) -> Option<Url> {
+        self.endpoints().website_url().ok()
+    }
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Session")
+            .field("endpoints", self.endpoints())
+            .field("has_cookies", &self.has_cookies())
+            .finish()
+    }
+}
+
+/// Account a website page was rendered for, read from its welcome box.
+///
+/// `None` means the page was served to a guest, so a session that had cookies has expired.
+pub fn signed_in_as(page: &str) -> Option<String> {
+    let welcome = crate::parse_welcome_message(page)?;
+    let login = welcome.strip_prefix("Welcome back,")?.trim().trim_end_matches('!').trim();
+    (!login.is_empty()).then(|| login.to_string())
+}
+
+/// Deletes the saved session, if there is one.
+pub fn forget(path: &Path) -> Result<(), LauncherError> {
+    if path.exists() {
+        fs::remove_file(path).context(t!("error-remove-session"))?;
+    }
+    Ok(())
+}
+
+/// Writes a file only the current user can read, for cookies and credentials.
+pub fn write_private(path: &Path, content: &str) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // The mode above only applies when the file is created
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    let mut file = options.open(path)?;
+    file.write_all(content.as_bytes())
+}
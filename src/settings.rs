@@ -0,0 +1,263 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{Context, ErrorKind, LauncherError};
+use crate::http::HttpOptions;
+use crate::i18n::Language;
+use crate::runtime::RuntimeKind;
+use crate::versions::DEFAULT_KEEP_VERSIONS;
+use crate::{fonts, theme, Endpoints};
+
+pub const SETTINGS_FILE_NAME: &str = "launcher_settings.json";
+
+/// Interface scales offered, in percent.
+pub const UI_SCALES: RangeInclusive<u16> = 50..=250;
+
+/// Intervals offered for the background update check and news refresh, in minutes.
+pub const REFRESH_INTERVALS: RangeInclusive<u16> = 0..=240;
+
+/// Layout version written by this launcher, bumped whenever a field is renamed or restructured.
+pub const SETTINGS_VERSION: u32 = 2;
+
+// Upgrades a settings document from version `index` to `index + 1`
+const MIGRATIONS: &[fn(&mut Value)] = &[
+    // 0 -> 1: files written before versioning only lacked the version field
+    |_| {},
+    // 1 -> 2: `theme` named an iced theme, it now names a launcher palette
+    |document| {
+        if let Some(theme) = document.get_mut("theme") {
+            let palette = match theme.as_str().unwrap_or_default() {
+                "Light" | "Solarized Light" | "Gruvbox Light" | "Catppuccin Latte" | "Tokyo Night Light" => theme::LIGHT,
+                _ => theme::DARK,
+            };
+            *theme = Value::from(palette);
+        }
+    },
+];
+
+/// Everything the user can tune, stored as JSON next to the launcher.
+///
+/// Missing fields fall back to their defaults, so adding a field never needs a migration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub version: u32,
+    pub theme: String,
+    pub scrollbar_width: u16,
+    pub scrollbar_margin: u16,
+    pub scroller_width: u16,
+    pub text_font: String,
+    pub news_font: String,
+    pub endpoints: Endpoints,
+    pub http: HttpOptions,
+    /// Where the game files go, next to the launcher when unset.
+    pub install_dir: Option<PathBuf>,
+    /// Flash Player the user already has, used instead of downloading the projector.
+    pub player_path: Option<PathBuf>,
+    /// Wine binary for the Wine runtime, a name looked up in PATH or a path.
+    pub wine_binary: String,
+    /// Player the game starts with unless the signed in account picked another one.
+    pub runtime: RuntimeKind,
+    /// Runtime picked per account, by login.
+    pub account_runtimes: BTreeMap<String, RuntimeKind>,
+    /// Game build Play starts, the newest installed one when unset.
+    pub game_version: Option<String>,
+    /// How many game builds stay installed after a download, 0 keeps all.
+    pub keep_versions: u16,
+    /// Keep the credentials in `Plazma Burst 2.auth` after signing in.
+    pub remember_password: bool,
+    /// Show the tray icon with the quick actions.
+    pub tray_icon: bool,
+    /// Hide the window to the tray while the game runs.
+    pub hide_to_tray: bool,
+    /// Language of the launcher, the system one when unset.
+    pub language: Option<Language>,
+    /// Size of the whole interface, text included, in percent.
+    pub ui_scale: u16,
+    /// Minutes between background checks for a new game build, 0 turns them off.
+    pub update_check_interval: u16,
+    /// Minutes between background news refreshes, 0 turns them off.
+    pub news_refresh_interval: u16,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            version: SETTINGS_VERSION,
+            theme: theme::DARK.to_string(),
+            scrollbar_width: 10,
+            scrollbar_margin: 0,
+            scroller_width: 10,
+            text_font: fonts::TEXT_FAMILY.to_string(),
+            news_font: fonts::TEXT_FAMILY.to_string(),
+            endpoints: Endpoints::default(),
+            http: HttpOptions::default(),
+            install_dir: None,
+            player_path: None,
+            wine_binary: "wine".to_string(),
+            runtime: RuntimeKind::default(),
+            account_runtimes: BTreeMap::new(),
+            game_version: None,
+            keep_versions: DEFAULT_KEEP_VERSIONS,
+            remember_password: true,
+            tray_icon: false,
+            hide_to_tray: false,
+            language: None,
+            ui_scale: 100,
+            update_check_interval: 60,
+            news_refresh_interval: 30,
+        }
+    }
+}
+
+impl Settings {
+    /// Reads the settings file, upgrading older layouts. A missing file gives the defaults.
+    pub fn load(path: &Path) -> Result<Settings, LauncherError> {
+        if !path.exists() {
+            return Ok(Settings::default());
+        }
+        let content = fs::read_to_string(path).context(t!("error-read-settings"))?;
+        Settings::from_json(&content)
+    }
+
+    pub fn from_json(content: &str) -> Result<Settings, LauncherError> {
+        let mut document: Value = serde_json::from_str(content)
+            .map_err(|err| LauncherError::new(ErrorKind::Io, t!("error-settings-damaged")).with_cause(err))?;
+        migrate(&mut document)?;
+        serde_json::from_value(document)
+            .map_err(|err| LauncherError::new(ErrorKind::Io, t!("error-settings-damaged")).with_cause(err))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), LauncherError> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|err| LauncherError::new(ErrorKind::Io, t!("error-save-settings")).with_cause(err))?;
+        fs::write(path, content).context(t!("error-save-settings"))
+    }
+
+    /// Directory the game is installed into.
+    pub fn install_dir(&self, launcher_dir: &Path) -> PathBuf {
+        match &self.install_dir {
+            Some(dir) => dir.clone(),
+            None => launcher_dir.to_path_buf(),
+        }
+    }
+
+    /// Runtime for `account`, the default one for guests and accounts that never picked one.
+    pub fn runtime_for(&self, account: Option<&str>) -> RuntimeKind {
+        account
+            .and_then(|account| self.account_runtimes.get(account))
+            .copied()
+            .unwrap_or(self.runtime)
+    }
+
+    /// Cleans up values typed on the Settings screen before they are used.
+    pub fn normalize(&mut self) {
+        let defaults = Endpoints::default();
+        self.endpoints.website = normalize_url(&self.endpoints.website, defaults.website);
+        self.endpoints.player_mirror = normalize_url(&self.endpoints.player_mirror, defaults.player_mirror);
+        self.endpoints.ruffle_mirror = normalize_url(&self.endpoints.ruffle_mirror, defaults.ruffle_mirror);
+        self.http.proxy = self.http.proxy.trim().to_string();
+        if self.install_dir.as_ref().is_some_and(|dir| dir.as_os_str().is_empty()) {
+            self.install_dir = None;
+        }
+        self.wine_binary = match self.wine_binary.trim() {
+            "" => "wine".to_string(),
+            wine => wine.to_string(),
+        };
+        if self.player_path.as_ref().is_some_and(|path| path.as_os_str().is_empty()) {
+            self.player_path = None;
+        }
+        self.ui_scale = self.ui_scale.clamp(*UI_SCALES.start(), *UI_SCALES.end());
+        self.update_check_interval = self.update_check_interval.min(*REFRESH_INTERVALS.end());
+        self.news_refresh_interval = self.news_refresh_interval.min(*REFRESH_INTERVALS.end());
+    }
+
+    pub fn apply(&mut self, setting: Setting) {
+        match setting {
+            Setting::Theme(theme) => self.theme = theme,
+            Setting::ScrollbarWidth(width) => self.scrollbar_width = width,
+            Setting::ScrollbarMargin(margin) => self.scrollbar_margin = margin,
+            Setting::ScrollerWidth(width) => self.scroller_width = width,
+            Setting::TextFont(font) => self.text_font = font,
+            Setting::NewsFont(font) => self.news_font = font,
+            Setting::Website(website) => self.endpoints.website = website,
+            Setting::PlayerMirror(mirror) => self.endpoints.player_mirror = mirror,
+            Setting::RuffleMirror(mirror) => self.endpoints.ruffle_mirror = mirror,
+            Setting::Proxy(proxy) => self.http.proxy = proxy,
+            Setting::ConnectTimeout(seconds) => self.http.connect_timeout = seconds,
+            Setting::ReadTimeout(seconds) => self.http.read_timeout = seconds,
+            Setting::Retries(retries) => self.http.retries = retries,
+            Setting::InstallDir(dir) => self.install_dir = dir,
+            Setting::PlayerPath(path) => self.player_path = path,
+            Setting::WineBinary(wine) => self.wine_binary = wine,
+            Setting::Runtime(runtime) => self.runtime = runtime,
+            Setting::KeepVersions(keep) => self.keep_versions = keep,
+            Setting::RememberPassword(remember) => self.remember_password = remember,
+            Setting::TrayIcon(show) => self.tray_icon = show,
+            Setting::HideToTray(hide) => self.hide_to_tray = hide,
+            Setting::Language(language) => self.language = language,
+            Setting::UiScale(scale) => self.ui_scale = scale,
+            Setting::UpdateCheckInterval(minutes) => self.update_check_interval = minutes,
+            Setting::NewsRefreshInterval(minutes) => self.news_refresh_interval = minutes,
+        }
+    }
+}
+
+/// A single edit made on the Settings screen.
+#[derive(Debug, Clone)]
+pub enum Setting {
+    Theme(String),
+    ScrollbarWidth(u16),
+    ScrollbarMargin(u16),
+    ScrollerWidth(u16),
+    TextFont(String),
+    NewsFont(String),
+    Website(String),
+    PlayerMirror(String),
+    RuffleMirror(String),
+    Proxy(String),
+    ConnectTimeout(u16),
+    ReadTimeout(u16),
+    Retries(u16),
+    InstallDir(Option<PathBuf>),
+    PlayerPath(Option<PathBuf>),
+    WineBinary(String),
+    Runtime(RuntimeKind),
+    KeepVersions(u16),
+    RememberPassword(bool),
+    TrayIcon(bool),
+    HideToTray(bool),
+    Language(Option<Language>),
+    UiScale(u16),
+    UpdateCheckInterval(u16),
+    NewsRefreshInterval(u16),
+}
+
+// Base URLs are joined with paths, so they must not end with a slash
+fn normalize_url(url: &str, default: String) -> String {
+    let url = url.trim().trim_end_matches('/');
+    if url.is_empty() {
+        default
+    } else {
+        url.to_string()
+    }
+}
+
+fn migrate(document: &mut Value) -> Result<(), LauncherError> {
+    let version = document.get("version").and_then(Value::as_u64).unwrap_or(0);
+    if version > u64::from(SETTINGS_VERSION) {
+        return Err(LauncherError::new(ErrorKind::Io, t!("error-settings-newer", version = version)));
+    }
+    for migration in &MIGRATIONS[version as usize..] {
+        migration(document);
+    }
+    if let Some(object) = document.as_object_mut() {
+        object.insert("version".to_string(), Value::from(SETTINGS_VERSION));
+    }
+    Ok(())
+}
@@ -0,0 +1,53 @@
+//! Keyboard shortcuts of the launcher window.
+//!
+//! Tab and Shift+Tab move between the text fields, Enter submits the field's form and Escape
+//! closes whatever screen is open. On the news and map pages PageUp, PageDown and the left and
+//! right arrows switch pages. Ctrl+L opens the sign in form, F5 reloads the screen and
+//! Ctrl+Plus, Ctrl+Minus and Ctrl+0 change the interface scale.
+
+use iced::keyboard::key::Named;
+use iced::keyboard::{Key, Modifiers};
+
+/// What a key press asks the window to do, the window decides what that means on its screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shortcut {
+    FocusNext,
+    FocusPrevious,
+    NextPage,
+    PreviousPage,
+    Cancel,
+    OpenLogin,
+    Refresh,
+    ZoomIn,
+    ZoomOut,
+    ResetZoom,
+}
+
+/// Shortcut of a key press. `captured` tells a focused text field already used the key,
+/// then only the keys it has no use for are shortcuts.
+pub fn shortcut(key: &Key, modifiers: Modifiers, captured: bool) -> Option<Shortcut> {
+    if modifiers.command() {
+        return match key.as_ref() {
+            Key::Character(character) => match character.to_ascii_lowercase().as_str() {
+                "l" => Some(Shortcut::OpenLogin),
+                "=" | "+" => Some(Shortcut::ZoomIn),
+                "-" => Some(Shortcut::ZoomOut),
+                "0" => Some(Shortcut::ResetZoom),
+                _ => None,
+            },
+            _ => None,
+        };
+    }
+    match key.as_ref() {
+        Key::Named(Named::Tab) if modifiers.shift() => Some(Shortcut::FocusPrevious),
+        Key::Named(Named::Tab) => Some(Shortcut::FocusNext),
+        // A focused field drops its focus on Escape, the screen closes along with it
+        Key::Named(Named::Escape) => Some(Shortcut::Cancel),
+        Key::Named(Named::F5) => Some(Shortcut::Refresh),
+        // The cursor keys belong to the text field that has the focus
+        _ if captured => None,
+        Key::Named(Named::PageDown | Named::ArrowRight) => Some(Shortcut::NextPage),
+        Key::Named(Named::PageUp | Named::ArrowLeft) => Some(Shortcut::PreviousPage),
+        _ => None,
+    }
+}
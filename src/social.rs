@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use iced::futures::future;
+use regex::Regex;
+use scraper::{Html, Selector};
+
+use crate::endpoints::query_value;
+use crate::error::{Context, LauncherError};
+use crate::http::Profile;
+use crate::session::{self, Session};
+
+/// How often friends and messages are refreshed while signed in on the website.
+pub const SOCIAL_INTERVAL: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Friend {
+    pub login: String,
+    pub online: bool,
+    pub last_seen: Option<String>,
+}
+
+/// A private message as listed in the website inbox.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InboxMessage {
+    pub id: String,
+    pub from: String,
+    pub subject: String,
+    pub date: Option<String>,
+    pub unread: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Inbox {
+    pub messages: Vec<InboxMessage>,
+}
+
+impl Inbox {
+    pub fn unread_count(&self) -> usize {
+        self.messages.iter().filter(|message| message.unread).count()
+    }
+
+    /// Unread messages that were not in `previous`, the ones worth a notification.
+    pub fn new_since<'a>(&'a self, previous: &Inbox) -> Vec<&'a InboxMessage> {
+        self.messages
+            .iter()
+            .filter(|message| message.unread)
+            .filter(|message| previous.messages.iter().all(|known| known.id != message.id))
+            .collect()
+    }
+}
+
+/// Friends and inbox of the account signed in on the website.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Social {
+    /// Online friends first.
+    pub friends: Vec<Friend>,
+    pub inbox: Inbox,
+}
+
+/// Loads friends and inbox through the signed in session.
+///
+/// `None` means the website served the pages to a guest: the session is gone.
+pub async fn fetch_social(session: &Session) -> Result<Option<Social>, LauncherError> {
+    let endpoints = session.endpoints();
+    let (friends, inbox) = future::join(
+        fetch_page(session, endpoints.friends_page(), &t!("error-load-friends")),
+        fetch_page(session, endpoints.inbox_page(), &t!("error-load-messages")),
+    )
+    .await;
+    let (friends, inbox) = (friends?, inbox?);
+    if session::signed_in_as(&friends).is_none() || session::signed_in_as(&inbox).is_none() {
+        return Ok(None);
+    }
+    Ok(Some(Social {
+        friends: parse_friends(&friends),
+        inbox: parse_inbox(&inbox),
+    }))
+}
+
+async fn fetch_page(session: &Session, url: String, context: &str) -> Result<String, LauncherError> {
+    session
+        .get(&url, Profile::Browser)
+        .await
+        .context(context)?
+        .text()
+        .await
+        .context(context)
+}
+
+/// Reads the friends table: a profile link per row, followed by the online status.
+pub fn parse_friends(body: &str) -> Vec<Friend> {
+    let document = Html::parse_document(body);
+    let row_selector = Selector::parse("tr").unwrap();
+    let link_selector = Selector::parse("a[href]").unwrap();
+    let last_seen = Regex::new(r"(?i)last seen:?\s*(.+)").unwrap();
+
+    let mut friends = Vec::new();
+    for row in document.select(&row_selector) {
+        let Some(login) = row
+            .select(&link_selector)
+            .find_map(|link| query_value(link.value().attr("href").unwrap_or_default(), "ac"))
+        else {
+            continue;
+        };
+        let text = row.text().collect::<Vec<_>>().join(" ");
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let status = text.to_lowercase();
+        friends.push(Friend {
+            login,
+            online: status.contains("online") && !status.contains("offline"),
+            last_seen: last_seen.captures(&text).map(|captures| captures[1].trim().to_string()),
+        });
+    }
+    // Stable, so the website order is kept within online and offline friends
+    friends.sort_by_key(|friend| !friend.online);
+    friends
+}
+
+/// Reads the inbox table: sender, subject linking to the message and date, unread rows are marked `unread`.
+pub fn parse_inbox(body: &str) -> Inbox {
+    let document = Html::parse_document(body);
+    let row_selector = Selector::parse("tr").unwrap();
+    let link_selector = Selector::parse("a[href]").unwrap();
+    let cell_selector = Selector::parse("td").unwrap();
+
+    let mut messages = Vec::new();
+    for row in document.select(&row_selector) {
+        let mut id = None;
+        let mut from = None;
+        for link in row.select(&link_selector) {
+            let href = link.value().attr("href").unwrap_or_default();
+            let text = link.text().collect::<String>().trim().to_string();
+            if let Some(message_id) = query_value(href, "msg") {
+                id = Some((message_id, text));
+            } else if query_value(href, "ac").is_some() {
+                from = Some(text);
+            }
+        }
+        let (Some((id, subject)), Some(from)) = (id, from) else {
+            continue;
+        };
+        let date = row
+            .select(&cell_selector)
+            .last()
+            .map(|cell| cell.text().collect::<String>().trim().to_string())
+            .filter(|date| !date.is_empty() && *date != subject && *date != from);
+        let unread = row.value().classes().any(|class| class == "unread");
+        messages.push(InboxMessage { id, from, subject, date, unread });
+    }
+    Inbox { messages }
+}
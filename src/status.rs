@@ -0,0 +1,155 @@
+use std::time::{Duration, Instant};
+
+use iced::futures::future;
+use regex::Regex;
+use scraper::{Html, Selector};
+
+use crate::http::Profile;
+use crate::session::Session;
+use crate::Endpoints;
+
+/// How often the status widget probes the servers.
+pub const STATUS_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Remote services the launcher depends on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Service {
+    /// Website pages, the news and the sign in form.
+    Website,
+    /// `server.php`, used by the game and the standalone launcher sign in.
+    GameServer,
+    /// `time.php`, which tells whether the game needs an update.
+    Updates,
+}
+
+impl Service {
+    pub const ALL: [Service; 3] = [Service::Website, Service::GameServer, Service::Updates];
+
+    pub fn label(&self) -> String {
+        match self {
+            Service::Website => t!("service-website"),
+            Service::GameServer => t!("service-game-server"),
+            Service::Updates => t!("service-updates"),
+        }
+    }
+
+    fn url(&self, endpoints: &Endpoints) -> String {
+        match self {
+            Service::Website => endpoints.front_page(),
+            Service::GameServer => endpoints.server_php(),
+            Service::Updates => endpoints.launcher_time(),
+        }
+    }
+
+    fn profile(&self) -> Profile {
+        match self {
+            Service::Website => Profile::Browser,
+            Service::GameServer => Profile::FlashPlayer,
+            Service::Updates => Profile::Launcher,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Health {
+    /// Answered, after this long.
+    Up(Duration),
+    /// Did not answer, or answered with a server error.
+    Down(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ServiceStatus {
+    pub service: Service,
+    pub health: Health,
+}
+
+/// Activity figures the website shows on its front page, when it shows them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServerStats {
+    pub players_online: Option<u32>,
+    pub active_servers: Option<u32>,
+}
+
+/// Result of probing every [`Service`] once.
+#[derive(Debug, Clone)]
+pub struct StatusReport {
+    pub services: Vec<ServiceStatus>,
+    pub stats: ServerStats,
+    pub checked: Instant,
+}
+
+impl StatusReport {
+    pub fn health(&self, service: Service) -> Option<&Health> {
+        self.services
+            .iter()
+            .find(|status| status.service == service)
+            .map(|status| &status.health)
+    }
+
+    pub fn all_up(&self) -> bool {
+        self.services.iter().all(|status| matches!(status.health, Health::Up(_)))
+    }
+}
+
+/// Probes every service at the same time. Failures are part of the report, never an error.
+pub async fn check(session: &Session) -> StatusReport {
+    let probes = Service::ALL.map(|service| probe(session, service));
+    let results = future::join_all(probes).await;
+
+    let mut stats = ServerStats::default();
+    let mut services = Vec::new();
+    for (status, page) in results {
+        if let Some(page) = page {
+            stats = parse_server_stats(&page);
+        }
+        services.push(status);
+    }
+    StatusReport { services, stats, checked: Instant::now() }
+}
+
+// Times one request; the website page is kept to read the activity figures from
+async fn probe(session: &Session, service: Service) -> (ServiceStatus, Option<String>) {
+    let started = Instant::now();
+    let result = session.get_once(&service.url(session.endpoints()), service.profile()).await;
+    let latency = started.elapsed();
+
+    let (health, page) = match result {
+        Ok(response) if response.status().is_server_error() => (Health::Down(format!("HTTP {}", response.status().as_u16())), None),
+        Ok(response) => {
+            let page = match service {
+                Service::Website => response.text().await.ok(),
+                _ => None,
+            };
+            (Health::Up(latency), page)
+        }
+        Err(err) if err.is_timeout() => (Health::Down(t!("service-timed-out")), None),
+        Err(err) if err.is_connect() => (Health::Down(t!("service-unreachable")), None),
+        Err(err) => (Health::Down(err.to_string()), None),
+    };
+    (ServiceStatus { service, health }, page)
+}
+
+/// Reads "N players online" and "N active servers" from the activity box of a website page.
+pub fn parse_server_stats(page: &str) -> ServerStats {
+    let document = Html::parse_document(page);
+    let selector = Selector::parse("#online_box").unwrap();
+    let Some(online_box) = document.select(&selector).next() else {
+        return ServerStats::default();
+    };
+    let text = online_box.text().collect::<Vec<_>>().join(" ");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let players = Regex::new(r"(?i)([\d,]+) players? online|players online:? ([\d,]+)").unwrap();
+    let servers = Regex::new(r"(?i)([\d,]+) (?:active )?servers?\b|active servers:? ([\d,]+)").unwrap();
+    ServerStats {
+        players_online: first_number(&players, &text),
+        active_servers: first_number(&servers, &text),
+    }
+}
+
+fn first_number(pattern: &Regex, text: &str) -> Option<u32> {
+    let captures = pattern.captures(text)?;
+    let number = captures.get(1).or_else(|| captures.get(2))?;
+    number.as_str().replace(',', "").parse().ok()
+}
@@ -1,53 +1,35 @@
-use iced::{Background, Border, Color, Theme};
-use iced::widget::{button, Button};
-use iced::application::Appearance;
+use iced::{Background, Border, Color, Font, Theme, Vector};
+use iced::widget::{button, container};
 use iced::widget::button::Style;
-use crate::Message;
+use crate::fonts;
+use crate::notifications::Level;
+use crate::theme::{darken, Palette};
 
-pub fn news_pages_switch_button_style(theme: &Theme) -> Box<dyn Fn(&Theme, button::Status) -> button::Style> {
-    Box::new(move |theme, _status| {
+pub fn news_pages_switch_button_style(palette: &Palette) -> Box<dyn Fn(&Theme, button::Status) -> button::Style> {
+    let (background, text_color) = (palette.page_button, palette.page_button_text);
+    Box::new(move |_theme, status| {
+        let background = match status {
+            button::Status::Hovered => darken(background, 0.1),
+            _ => background,
+        };
         button::Style {
-            background: Some(Background::Color(Color::from_rgb8(255, 165, 0))),
-            text_color: Color::from_rgb8(0, 0, 139),
+            background: Some(Background::Color(background)),
+            text_color,
             border: Border::rounded(200),
             shadow: Default::default(),
         }
     })
 }
 
-impl StyleSheet for Button<'_, Message> {
-    type Style = ();
-
-    fn appearance(&self, _style: &Self::Style) -> Appearance {
-        //let palette = self.extended_palette();
-
-        Appearance {
-            background_color: Color::from_rgb(255.,255.,255.),
-            text_color: Color::from_rgb(0.,0.,0.)
-        }
-    }
-}
-
-/// A set of rules that dictate the style of an indicator.
-pub trait StyleSheet {
-    /// The supported style of the [`StyleSheet`].
-    type Style: Default;
-
-    /// Produces the active [`Appearance`] of a indicator.
-    fn appearance(&self, style: &Self::Style) -> Appearance;
-}
-
-pub fn news_pages_selected_button_style(theme: &Theme) -> Box<dyn Fn(&Theme, button::Status) -> button::Style> {
-    Box::new(move |theme, status| {
-
-        use iced::{Background, Color, Border, Vector};
+pub fn news_pages_selected_button_style(palette: &Palette) -> Box<dyn Fn(&Theme, button::Status) -> button::Style> {
+    let (base_color, text_color) = (palette.selected_page_button, palette.selected_page_button_text);
+    Box::new(move |_theme, status| {
 
-        let base_color = Color::from_rgb8(255, 0, 0); // Brighter yellow for highlight
-        let hovered_color = Color::from_rgb8(255, 0, 0);; //.darken(0.1); // Slightly darker when hovered
+        let hovered_color = darken(base_color, 0.1); // Slightly darker when hovered
 
         let base_style = Style {
             background: Some(Background::Color(base_color)),
-            text_color: Color::BLACK, // Ensuring good contrast
+            text_color,
             border: Border::rounded(10), // More pronounced rounded effect
             shadow: iced::Shadow {
                 offset: Vector::new(1.0, 2.0),
@@ -68,14 +50,48 @@ pub fn news_pages_selected_button_style(theme: &Theme) -> Box<dyn Fn(&Theme, but
 }
 
 // Implement a transparent button style
-pub fn transparent_button_hyperlink_style(theme: &Theme) -> Box<dyn Fn(&Theme, button::Status) -> button::Style>
+pub fn transparent_button_hyperlink_style(palette: &Palette) -> Box<dyn Fn(&Theme, button::Status) -> button::Style>
 {
-    Box::new(move |theme, _status| {
+    let text_color = palette.hyperlink;
+    Box::new(move |_theme, _status| {
         button::Style {
             background: Some(Background::Color(Color::TRANSPARENT)),
-            text_color: Color::from_rgb8(255, 204, 0),
+            text_color,
             border: Border::rounded(0),
             shadow: Default::default(),
         }
     })
 }
+
+pub fn error_banner_style(palette: &Palette) -> Box<dyn Fn(&Theme) -> container::Style> {
+    status_style(palette.danger, palette.on_status)
+}
+
+pub fn notification_color(palette: &Palette, level: Level) -> Color {
+    match level {
+        Level::Info => palette.info,
+        Level::Success => palette.success,
+        Level::Warning => palette.warning,
+        Level::Error => palette.danger,
+    }
+}
+
+pub fn notification_style(palette: &Palette, level: Level) -> Box<dyn Fn(&Theme) -> container::Style> {
+    status_style(notification_color(palette, level), palette.on_status)
+}
+
+fn status_style(background: Color, text_color: Color) -> Box<dyn Fn(&Theme) -> container::Style> {
+    Box::new(move |_theme| {
+        container::Style {
+            background: Some(Background::Color(background)),
+            text_color: Some(text_color),
+            border: Border::rounded(5),
+            ..container::Style::default()
+        }
+    })
+}
+
+// Font of a font setting, the bundled one when none of its families is installed
+pub fn named_font(name: &str) -> Font {
+    Font::with_name(fonts::resolve(name, fonts::installed()))
+}
@@ -0,0 +1,85 @@
+//! Desktop entries, links and the command line.
+
+use std::fs;
+use std::path::Path;
+
+use super::temp_dir;
+use crate::desktop::{self, Invocation, Link};
+use crate::Message;
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn links_are_parsed_into_actions() {
+    assert_eq!(Link::parse("pb2://play").unwrap(), Link::Play);
+    assert_eq!(Link::parse("pb2://play/").unwrap(), Link::Play);
+    assert_eq!(Link::parse("pb2:play").unwrap(), Link::Play);
+    assert_eq!(Link::parse("pb2://play?map=").unwrap(), Link::Play);
+    assert_eq!(Link::parse("pb2://play?map=tower%20defense").unwrap(), Link::PlayMap("tower defense".to_string()));
+    assert_eq!(Link::parse("PB2://LOGIN").unwrap(), Link::Login);
+    assert_eq!(Link::parse("pb2://maps").unwrap(), Link::Maps);
+
+    for link in ["pb2://uninstall", "https://plazmaburst2.com/", "pb2 play"] {
+        assert!(Link::parse(link).unwrap_err().context.ends_with("is not a link the launcher can open"));
+    }
+}
+
+#[test]
+fn links_are_routed_to_messages() {
+    assert!(matches!(Link::Play.message(), Message::PlayGamePressed));
+    assert!(matches!(Link::Login.message(), Message::LoginPressed));
+    assert!(matches!(Link::Maps.message(), Message::OpenMaps));
+    match Link::PlayMap("tower".to_string()).message() {
+        Message::PlayMap(map) => assert_eq!((map.id.as_str(), map.name.as_str()), ("tower", "tower")),
+        other => panic!("unexpected message {:?}", other),
+    }
+}
+
+#[test]
+fn command_line_is_parsed() {
+    assert_eq!(desktop::parse_args(args(&[])).unwrap(), Invocation::Run(Vec::new()));
+    assert_eq!(
+        desktop::parse_args(args(&["pb2://login", "pb2://play?map=tower"])).unwrap(),
+        Invocation::Run(args(&["pb2://login", "pb2://play?map=tower"]))
+    );
+    assert_eq!(desktop::parse_args(args(&["--install-desktop"])).unwrap(), Invocation::InstallDesktop);
+    assert_eq!(desktop::parse_args(args(&["--uninstall-desktop"])).unwrap(), Invocation::UninstallDesktop);
+    assert_eq!(desktop::parse_args(args(&["--help"])).unwrap(), Invocation::Help);
+    assert_eq!(desktop::parse_args(args(&["--frobnicate"])).unwrap_err().context, "Unknown argument --frobnicate");
+}
+
+#[test]
+fn desktop_entry_starts_the_launcher_for_links() {
+    let entry = desktop::desktop_entry(Path::new("/opt/PB2 Launcher/PB2Launcher"));
+
+    assert!(entry.starts_with("[Desktop Entry]\n"));
+    assert!(entry.contains("\nExec=\"/opt/PB2 Launcher/PB2Launcher\" %u\n"));
+    assert!(entry.contains("\nIcon=pb2-launcher\n"));
+    assert!(entry.contains("\nMimeType=x-scheme-handler/pb2;\n"));
+    // Reserved characters are escaped for the Exec quoting, then for the string value
+    let entry = desktop::desktop_entry(Path::new("/home/a$b/PB2Launcher"));
+    assert!(entry.contains("\nExec=\"/home/a\\\\$b/PB2Launcher\" %u\n"));
+    // Field codes start with %, so a literal one is doubled
+    let entry = desktop::desktop_entry(Path::new("/home/a%u/PB2Launcher"));
+    assert!(entry.contains("\nExec=\"/home/a%%u/PB2Launcher\" %u\n"));
+}
+
+#[test]
+fn desktop_entry_and_icon_are_installed_and_removed() {
+    let data_dir = temp_dir("desktop", "install");
+
+    let entry = desktop::install(&data_dir, Path::new("/opt/pb2/PB2Launcher")).unwrap();
+
+    assert_eq!(entry, data_dir.join("applications").join(desktop::DESKTOP_FILE_NAME));
+    assert!(fs::read_to_string(&entry).unwrap().contains("Exec=\"/opt/pb2/PB2Launcher\" %u"));
+    let icon = data_dir.join("icons/hicolor/scalable/apps/pb2-launcher.svg");
+    assert!(fs::read_to_string(&icon).unwrap().starts_with("<svg"));
+
+    desktop::uninstall(&data_dir).unwrap();
+    assert!(!entry.exists());
+    assert!(!icon.exists());
+    // Nothing left to remove is fine
+    desktop::uninstall(&data_dir).unwrap();
+}
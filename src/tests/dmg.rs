@@ -0,0 +1,304 @@
+//! The macOS player disk image.
+
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+use super::temp_dir;
+use crate::dmg;
+use crate::runtime;
+
+const BLOCK_SIZE: usize = 512;
+const NODE_SIZE: usize = 4096;
+const CATALOG_BLOCK: usize = 4;
+const MACH_O_PLAYER: &[u8] = b"\xcf\xfa\xed\xfe mock projector";
+const INFO_PLIST: &str = "<plist><dict>\n\t<key>CFBundleExecutable</key>\n\t<string>Flash Player</string>\n</dict></plist>";
+
+enum Item {
+    Folder { id: u32 },
+    File { content: &'static [u8], mode: u16 },
+    Symlink { target: &'static [u8] },
+}
+
+// Catalog of a disk image the way the Flash Player one is laid out, plus a file next to the app
+fn projector_volume() -> Vec<(u32, &'static str, Item)> {
+    vec![
+        (1, "Flash Player", Item::Folder { id: 2 }),
+        (2, ".background.png", Item::File { content: b"\x89PNG", mode: 0o100644 }),
+        (2, "Flash Player.app", Item::Folder { id: 16 }),
+        (16, "Contents", Item::Folder { id: 17 }),
+        (17, "Info.plist", Item::File { content: INFO_PLIST.as_bytes(), mode: 0o100644 }),
+        (17, "MacOS", Item::Folder { id: 18 }),
+        (17, "Executables", Item::Symlink { target: b"MacOS" }),
+        (18, "Flash Player", Item::File { content: MACH_O_PLAYER, mode: 0o100755 }),
+        (17, "Resources", Item::Folder { id: 19 }),
+        (19, "player.icns", Item::File { content: b"icns", mode: 0o100644 }),
+    ]
+}
+
+fn fork(size: usize, start_block: usize) -> Vec<u8> {
+    let blocks = size.div_ceil(BLOCK_SIZE);
+    let mut fork = Vec::new();
+    fork.extend_from_slice(&(size as u64).to_be_bytes());
+    fork.extend_from_slice(&0u32.to_be_bytes());
+    fork.extend_from_slice(&(blocks as u32).to_be_bytes());
+    fork.extend_from_slice(&(start_block as u32).to_be_bytes());
+    fork.extend_from_slice(&(blocks as u32).to_be_bytes());
+    fork.resize(80, 0);
+    fork
+}
+
+fn catalog_record(parent: u32, name: &str, item: &Item, data_block: usize) -> Vec<u8> {
+    let name: Vec<u16> = name.encode_utf16().collect();
+    let mut record = Vec::new();
+    record.extend_from_slice(&(6 + 2 * name.len() as u16).to_be_bytes());
+    record.extend_from_slice(&parent.to_be_bytes());
+    record.extend_from_slice(&(name.len() as u16).to_be_bytes());
+    for unit in name {
+        record.extend_from_slice(&unit.to_be_bytes());
+    }
+    let mut data = vec![0u8; 248];
+    match item {
+        Item::Folder { id } => {
+            data[..2].copy_from_slice(&1u16.to_be_bytes());
+            data[8..12].copy_from_slice(&id.to_be_bytes());
+            data[42..44].copy_from_slice(&0o40755u16.to_be_bytes());
+            data.truncate(88);
+        }
+        Item::File { content, mode } => {
+            data[..2].copy_from_slice(&2u16.to_be_bytes());
+            data[42..44].copy_from_slice(&mode.to_be_bytes());
+            data[88..168].copy_from_slice(&fork(content.len(), data_block));
+        }
+        Item::Symlink { target } => {
+            data[..2].copy_from_slice(&2u16.to_be_bytes());
+            data[42..44].copy_from_slice(&0o120755u16.to_be_bytes());
+            data[48..52].copy_from_slice(b"slnk");
+            data[52..56].copy_from_slice(b"rhap");
+            data[88..168].copy_from_slice(&fork(target.len(), data_block));
+        }
+    }
+    record.extend_from_slice(&data);
+    record
+}
+
+// HFS+ volume with a two node catalog: the header node and a single leaf
+fn hfs_volume(items: &[(u32, &'static str, Item)]) -> Vec<u8> {
+    let catalog_blocks = 2 * NODE_SIZE / BLOCK_SIZE;
+    let mut next_block = CATALOG_BLOCK + catalog_blocks;
+    let mut volume = vec![0u8; next_block * BLOCK_SIZE];
+
+    let mut leaf = vec![0u8; NODE_SIZE];
+    leaf[8] = 0xff;
+    leaf[9] = 1;
+    leaf[10..12].copy_from_slice(&(items.len() as u16 + 1).to_be_bytes());
+    let mut offset = 14;
+    let mut records = Vec::new();
+    for (parent, name, item) in items {
+        let content: &[u8] = match item {
+            Item::Folder { .. } => &[],
+            Item::File { content, .. } => content,
+            Item::Symlink { target } => target,
+        };
+        records.push(catalog_record(*parent, name, item, next_block));
+        let blocks = content.len().div_ceil(BLOCK_SIZE);
+        volume.extend_from_slice(content);
+        volume.resize((next_block + blocks) * BLOCK_SIZE, 0);
+        next_block += blocks;
+    }
+    // A thread record, which only maps the root folder back to its name
+    let mut thread = vec![0u8, 6, 0, 0, 0, 2, 0, 0, 0, 3];
+    thread.resize(30, 0);
+    records.push(thread);
+    for (index, record) in records.iter().enumerate() {
+        leaf[offset..offset + record.len()].copy_from_slice(record);
+        leaf[NODE_SIZE - 2 * (index + 1)..NODE_SIZE - 2 * index].copy_from_slice(&(offset as u16).to_be_bytes());
+        offset += record.len();
+    }
+
+    let mut header_node = vec![0u8; NODE_SIZE];
+    header_node[8] = 1;
+    header_node[24..28].copy_from_slice(&1u32.to_be_bytes());
+    header_node[32..34].copy_from_slice(&(NODE_SIZE as u16).to_be_bytes());
+    let catalog_start = CATALOG_BLOCK * BLOCK_SIZE;
+    volume[catalog_start..catalog_start + NODE_SIZE].copy_from_slice(&header_node);
+    volume[catalog_start + NODE_SIZE..catalog_start + 2 * NODE_SIZE].copy_from_slice(&leaf);
+
+    let header = &mut volume[1024..1536];
+    header[..2].copy_from_slice(b"H+");
+    header[2..4].copy_from_slice(&4u16.to_be_bytes());
+    header[40..44].copy_from_slice(&(BLOCK_SIZE as u32).to_be_bytes());
+    header[272..352].copy_from_slice(&fork(2 * NODE_SIZE, CATALOG_BLOCK));
+    volume
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut text = String::new();
+    for chunk in bytes.chunks(3) {
+        let value = chunk.iter().enumerate().fold(0u32, |value, (index, byte)| value | (*byte as u32) << (16 - 8 * index));
+        for index in 0..4 {
+            if index <= chunk.len() {
+                text.push(ALPHABET[(value >> (18 - 6 * index) & 63) as usize] as char);
+            } else {
+                text.push('=');
+            }
+        }
+        // Property lists wrap their data
+        if text.len() % 69 == 68 {
+            text.push_str("\n\t\t\t");
+        }
+    }
+    text
+}
+
+fn block_table(sectors: usize, chunks: &[(u32, usize, usize, usize, usize)]) -> Vec<u8> {
+    let mut table = vec![0u8; 204];
+    table[..4].copy_from_slice(b"mish");
+    table[4..8].copy_from_slice(&1u32.to_be_bytes());
+    table[16..24].copy_from_slice(&(sectors as u64).to_be_bytes());
+    table[200..204].copy_from_slice(&(chunks.len() as u32 + 1).to_be_bytes());
+    for (kind, sector, count, offset, length) in chunks.iter().copied().chain([(0xffff_ffff, sectors, 0, 0, 0)]) {
+        table.extend_from_slice(&kind.to_be_bytes());
+        table.extend_from_slice(&0u32.to_be_bytes());
+        for value in [sector, count, offset, length] {
+            table.extend_from_slice(&(value as u64).to_be_bytes());
+        }
+    }
+    table
+}
+
+// UDIF image of `volume`: a zlib chunk, a raw chunk and zeros, after a protective MBR partition
+fn udif_image(volume: &[u8], compression: u32) -> Vec<u8> {
+    let mut volume = volume.to_vec();
+    volume.resize(volume.len() + 512, 0);
+    let sectors = volume.len() / 512;
+    let split = sectors / 2;
+    let used = (sectors - 1) * 512;
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&volume[..split * 512]).unwrap();
+    let mut data = encoder.finish().unwrap();
+    let compressed = data.len();
+    data.extend_from_slice(&volume[split * 512..used]);
+
+    let hfs = block_table(sectors, &[
+        (compression, 0, split, 0, compressed),
+        (1, split, sectors - 1 - split, compressed, used - split * 512),
+        (0, sectors - 1, 1, 0, 0),
+    ]);
+    udif_container(data, &hfs, sectors)
+}
+
+// UDIF image around `data`, with `hfs` as the block table of its HFS partition
+fn udif_container(data: Vec<u8>, hfs: &[u8], sectors: usize) -> Vec<u8> {
+    let mbr = block_table(1, &[(0, 0, 1, 0, 0)]);
+    let plist = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<plist version=\"1.0\">\n<dict>\n\t<key>resource-fork</key>\n\t<dict>\n\t\t<key>blkx</key>\n\t\t<array>\n\
+         \t\t\t<dict>\n\t\t\t\t<key>Attributes</key>\n\t\t\t\t<string>0x0050</string>\n\t\t\t\t<key>Data</key>\n\t\t\t\t<data>\n\t\t\t{}\n\t\t\t\t</data>\n\t\t\t\t<key>Name</key>\n\t\t\t\t<string>Protective Master Boot Record (MBR : 0)</string>\n\t\t\t</dict>\n\
+         \t\t\t<dict>\n\t\t\t\t<key>Attributes</key>\n\t\t\t\t<string>0x0050</string>\n\t\t\t\t<key>Data</key>\n\t\t\t\t<data>\n\t\t\t{}\n\t\t\t\t</data>\n\t\t\t\t<key>Name</key>\n\t\t\t\t<string>disk image (Apple_HFS : 1)</string>\n\t\t\t</dict>\n\
+         \t\t</array>\n\t</dict>\n</dict>\n</plist>\n",
+        base64(&mbr),
+        base64(hfs)
+    );
+
+    let mut image = data;
+    let data_length = image.len();
+    image.extend_from_slice(plist.as_bytes());
+    let mut koly = vec![0u8; 512];
+    koly[..4].copy_from_slice(b"koly");
+    koly[4..8].copy_from_slice(&4u32.to_be_bytes());
+    koly[8..12].copy_from_slice(&512u32.to_be_bytes());
+    koly[32..40].copy_from_slice(&(data_length as u64).to_be_bytes());
+    koly[216..224].copy_from_slice(&(data_length as u64).to_be_bytes());
+    koly[224..232].copy_from_slice(&(plist.len() as u64).to_be_bytes());
+    koly[492..500].copy_from_slice(&(sectors as u64).to_be_bytes());
+    image.extend_from_slice(&koly);
+    image
+}
+
+fn projector_image() -> Vec<u8> {
+    udif_image(&hfs_volume(&projector_volume()), 0x8000_0005)
+}
+
+#[test]
+fn app_bundle_is_unpacked_from_the_disk_image() {
+    let dir = temp_dir("dmg", "unpack");
+    let bundle = dir.join("Flash Player.app");
+
+    let name = dmg::extract_app(&projector_image(), &bundle).unwrap();
+
+    assert_eq!(name, "Flash Player.app");
+    let contents = bundle.join("Contents");
+    assert_eq!(fs::read(contents.join("MacOS").join("Flash Player")).unwrap(), MACH_O_PLAYER);
+    assert_eq!(fs::read_to_string(contents.join("Info.plist")).unwrap(), INFO_PLIST);
+    assert_eq!(fs::read(contents.join("Resources").join("player.icns")).unwrap(), b"icns");
+    // Only the bundle, not what sits next to it in the image
+    assert!(!dir.join(".background.png").exists());
+    assert!(!bundle.join(".background.png").exists());
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = fs::metadata(contents.join("MacOS").join("Flash Player")).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+        assert_eq!(fs::read_link(contents.join("Executables")).unwrap(), PathBuf::from("MacOS"));
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn projector_is_launched_through_the_bundle_executable() {
+    let dir = temp_dir("dmg", "install");
+    let bundle = dir.join("Flash Player.app");
+
+    let executable = runtime::install_app(&projector_image(), &bundle).unwrap();
+
+    assert_eq!(executable, bundle.join("Contents").join("MacOS").join("Flash Player"));
+    assert_eq!(runtime::validate_player(&bundle).unwrap(), executable);
+    assert!(!dir.join("Flash Player.app.part").exists());
+}
+
+#[test]
+fn damaged_disk_image_leaves_nothing_behind() {
+    let dir = temp_dir("dmg", "damaged");
+    let bundle = dir.join("Flash Player.app");
+    let mut truncated = projector_image();
+    truncated.drain(..1024);
+
+    for image in [b"not a disk image".to_vec(), truncated] {
+        let err = runtime::install_app(&image, &bundle).unwrap_err();
+        assert_eq!(err.context, "Flash Player disk image is damaged");
+        assert!(!bundle.exists());
+        assert!(!dir.join("Flash Player.app.part").exists());
+    }
+}
+
+#[test]
+fn unsupported_compression_is_reported() {
+    let dir = temp_dir("dmg", "bzip2");
+    let image = udif_image(&hfs_volume(&projector_volume()), 0x8000_0006);
+
+    let err = dmg::extract_app(&image, &dir.join("Flash Player.app")).unwrap_err();
+
+    assert!(err.to_string().contains("unsupported chunk compression 0x80000006"));
+}
+
+#[test]
+fn forged_chunk_sizes_are_damage() {
+    let dir = temp_dir("dmg", "forged");
+    let tables = [
+        ("chunk outside of the partition", block_table(1, &[(0, usize::MAX / 2, 1, 0, 0)])),
+        ("chunk outside of the partition", block_table(1, &[(0, 0, usize::MAX, 0, 0)])),
+        ("partition too large", block_table(1 << 40, &[])),
+    ];
+
+    for (cause, table) in tables {
+        let err = dmg::extract_app(&udif_container(Vec::new(), &table, 1), &dir.join("Flash Player.app")).unwrap_err();
+        assert_eq!(err.context, "Flash Player disk image is damaged");
+        assert!(err.to_string().contains(cause), "{}", err);
+    }
+}
@@ -0,0 +1,38 @@
+//! Bundled fonts.
+
+use std::collections::BTreeSet;
+
+use iced::advanced::graphics::text::cosmic_text::fontdb;
+
+use crate::fonts::{self, EMOJI_FAMILY, TEXT_FAMILY};
+
+fn families(families: &[&str]) -> BTreeSet<String> {
+    families.iter().map(|family| family.to_string()).collect()
+}
+
+#[test]
+fn bundled_fonts_carry_their_family_names() {
+    let mut database = fontdb::Database::new();
+    for font in fonts::BUNDLED {
+        database.load_font_data(font.to_vec());
+    }
+    let loaded: BTreeSet<String> = database.faces().map(|face| face.families[0].0.clone()).collect();
+
+    assert_eq!(loaded, families(&[TEXT_FAMILY, EMOJI_FAMILY]));
+}
+
+#[test]
+fn first_installed_family_of_the_list_is_used() {
+    let installed = families(&[TEXT_FAMILY, "DejaVu Sans", "Verdana"]);
+
+    assert_eq!(fonts::resolve("Verdana", &installed), "Verdana");
+    assert_eq!(fonts::resolve("Segoe UI Emoji, dejavu sans , Verdana", &installed), "DejaVu Sans");
+}
+
+#[test]
+fn missing_fonts_fall_back_to_the_bundled_one() {
+    let installed = families(&[TEXT_FAMILY, EMOJI_FAMILY]);
+
+    assert_eq!(fonts::resolve("Segoe UI Emoji", &installed), TEXT_FAMILY);
+    assert_eq!(fonts::resolve("", &installed), TEXT_FAMILY);
+}
@@ -0,0 +1,106 @@
+//! The HTTP layer: retries, header profiles and proxies.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::mock_server::{MockServer, Response};
+use super::*;
+use crate::error::ErrorKind;
+use crate::http::{HttpOptions, Profile};
+use crate::notifications::Reporter;
+use crate::runtime::RuntimeKind;
+use crate::settings::Settings;
+use crate::session::Session;
+use crate::install;
+
+fn busy() -> Response {
+    Response { status: 503, ..Response::text("Busy") }
+}
+
+#[tokio::test]
+async fn transient_get_failure_is_retried() {
+    let attempts = AtomicUsize::new(0);
+    let server = MockServer::start(move |request| {
+        if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+            busy()
+        } else {
+            pb2_site(request)
+        }
+    });
+    let session = server.session();
+
+    let time = session.get(&server.endpoints().launcher_time(), Profile::Launcher).await.unwrap().text().await.unwrap();
+
+    assert_eq!(time, TIME);
+    assert_eq!(server.hits("/launcher/time.php"), 2);
+}
+
+#[tokio::test]
+async fn retries_give_up_after_the_configured_count() {
+    let server = MockServer::start(|_| busy());
+    let session = Session::new(server.endpoints(), &HttpOptions { retries: 1, ..Default::default() }).unwrap();
+
+    let err = session.get(&server.endpoints().launcher_time(), Profile::Launcher).await.unwrap_err();
+
+    assert_eq!(err.status().map(|status| status.as_u16()), Some(503));
+    assert_eq!(server.hits("/launcher/time.php"), 2);
+}
+
+#[tokio::test]
+async fn missing_page_is_not_retried() {
+    let server = MockServer::start(|_| Response::not_found());
+
+    assert!(server.session().get(&server.endpoints().launcher_time(), Profile::Launcher).await.is_err());
+    assert_eq!(server.hits("/launcher/time.php"), 1);
+}
+
+#[tokio::test]
+async fn sign_in_post_is_not_retried() {
+    let server = MockServer::start(|_| busy());
+
+    let err = crate::login_website_http_post(&server.session(), &WEBSITE_USER.to_string(), &WEBSITE_PASSWORD.to_string())
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::Network);
+    assert_eq!(server.hits("/"), 1);
+}
+
+#[tokio::test]
+async fn each_endpoint_gets_its_header_profile() {
+    let server = MockServer::start(pb2_site);
+    let session = server.session();
+    let dir = temp_dir("http", "profiles");
+
+    crate::login_website_http_post(&session, &WEBSITE_USER.to_string(), &WEBSITE_PASSWORD.to_string()).await.unwrap();
+    crate::login_website_http_post_rq_load(&session, &LAUNCHER_USER.to_string(), &LAUNCHER_PASSWORD.to_string()).await.unwrap();
+    install::install(&session, &dir, &*RuntimeKind::FlashPlayer.runtime(&Settings::default()), &Reporter::silent()).await.unwrap();
+
+    let user_agent = |path: &str| {
+        let request = server.requests().into_iter().find(|request| request.path == path).unwrap();
+        request.header("user-agent").unwrap().to_string()
+    };
+    assert!(user_agent("/").starts_with("Mozilla/5.0"));
+    assert_eq!(user_agent("/pb2/server.php"), "Shockwave Flash");
+    assert!(user_agent("/pb2/pb2_re34.swf").starts_with("PB2Launcher/"));
+}
+
+#[tokio::test]
+async fn configured_proxy_carries_the_requests() {
+    let proxy = MockServer::start(|_| Response::html(FRONT_PAGE));
+    let endpoints = crate::Endpoints { website: "http://pb2.invalid".to_string(), ..Default::default() };
+    let options = HttpOptions { proxy: proxy.endpoints().website, ..Default::default() };
+    let session = Session::new(endpoints, &options).unwrap();
+
+    session.get(&session.endpoints().front_page(), Profile::Browser).await.unwrap();
+
+    assert_eq!(proxy.hits("http://pb2.invalid/"), 1);
+}
+
+#[test]
+fn invalid_proxy_is_rejected() {
+    let options = HttpOptions { proxy: "http://bad proxy:8080".to_string(), ..Default::default() };
+
+    let err = Session::new(crate::Endpoints::default(), &options).unwrap_err();
+
+    assert!(err.context.starts_with("Invalid proxy address"));
+}
@@ -0,0 +1,148 @@
+//! Translations.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use fluent_bundle::{FluentResource, FluentValue};
+use fluent_syntax::ast::{Entry, Expression, InlineExpression, Pattern, PatternElement};
+use regex::Regex;
+
+use crate::i18n::{self, Language, LanguageChoice};
+
+// Message ids of a catalog with the variables each one takes
+fn messages(language: Language) -> Vec<(String, BTreeSet<String>)> {
+    let resource = FluentResource::try_new(language.catalog().to_string())
+        .unwrap_or_else(|(_, errors)| panic!("{} catalog does not parse: {:?}", language.code(), errors));
+    resource
+        .entries()
+        .filter_map(|entry| match entry {
+            Entry::Message(message) => {
+                let mut variables = BTreeSet::new();
+                if let Some(pattern) = &message.value {
+                    pattern_variables(pattern, &mut variables);
+                }
+                Some((message.id.name.to_string(), variables))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn pattern_variables(pattern: &Pattern<&str>, variables: &mut BTreeSet<String>) {
+    for element in &pattern.elements {
+        if let PatternElement::Placeable { expression } = element {
+            expression_variables(expression, variables);
+        }
+    }
+}
+
+fn expression_variables(expression: &Expression<&str>, variables: &mut BTreeSet<String>) {
+    match expression {
+        Expression::Select { selector, variants } => {
+            inline_variables(selector, variables);
+            for variant in variants {
+                pattern_variables(&variant.value, variables);
+            }
+        }
+        Expression::Inline(inline) => inline_variables(inline, variables),
+    }
+}
+
+fn inline_variables(expression: &InlineExpression<&str>, variables: &mut BTreeSet<String>) {
+    match expression {
+        InlineExpression::VariableReference { id } => {
+            variables.insert(id.name.to_string());
+        }
+        InlineExpression::Placeable { expression } => expression_variables(expression, variables),
+        _ => {}
+    }
+}
+
+#[test]
+fn translations_have_every_english_text() {
+    let english = messages(Language::English);
+
+    for language in Language::ALL {
+        let translated = messages(language);
+        let ids: Vec<&String> = translated.iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, english.iter().map(|(id, _)| id).collect::<Vec<_>>(), "{} catalog", language.code());
+        for ((id, variables), (_, english_variables)) in translated.iter().zip(&english) {
+            assert_eq!(variables, english_variables, "variables of {} in the {} catalog", id, language.code());
+        }
+    }
+}
+
+#[test]
+fn every_text_looked_up_is_in_the_catalog() {
+    let english: BTreeSet<String> = messages(Language::English).into_iter().map(|(id, _)| id).collect();
+    let lookup = Regex::new(r#"(?:t!\(|i18n::text\()"([a-z0-9-]+)""#).unwrap();
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+
+    let mut looked_up = 0;
+    for file in fs::read_dir(src).unwrap() {
+        let path = file.unwrap().path();
+        if path.extension() != Some("rs".as_ref()) {
+            continue;
+        }
+        let source = fs::read_to_string(&path).unwrap();
+        for id in lookup.captures_iter(&source) {
+            assert!(english.contains(&id[1]), "{} looks up {}, which no catalog has", path.display(), &id[1]);
+            looked_up += 1;
+        }
+    }
+    assert!(looked_up > 100);
+}
+
+#[test]
+fn locales_pick_their_language() {
+    assert_eq!(Language::from_locale("ru_RU.UTF-8"), Some(Language::Russian));
+    assert_eq!(Language::from_locale("ru-RU"), Some(Language::Russian));
+    assert_eq!(Language::from_locale("en_GB.UTF-8@euro"), Some(Language::English));
+    assert_eq!(Language::from_locale("de_DE.UTF-8"), None);
+    assert_eq!(Language::from_locale(""), None);
+}
+
+#[test]
+fn language_setting_round_trips_through_the_picker() {
+    assert_eq!(LanguageChoice::all().len(), Language::ALL.len() + 1);
+    for choice in LanguageChoice::all() {
+        assert_eq!(LanguageChoice::from_setting(choice.into_setting()), choice);
+    }
+    assert_eq!(LanguageChoice::System.into_setting(), None);
+}
+
+#[test]
+fn texts_are_formatted_in_the_picked_language() {
+    let args = [("name", FluentValue::from("Tester"))];
+
+    assert_eq!(i18n::text_in(Language::English, "signed-in-as", &args), "Signed in as: Tester");
+    assert_eq!(i18n::text_in(Language::Russian, "signed-in-as", &args), "Вы вошли как: Tester");
+}
+
+#[test]
+fn counts_pick_the_plural_form() {
+    let unread = |language, count: usize| i18n::text_in(language, "unread-messages", &[("count", FluentValue::from(count))]);
+
+    assert_eq!(unread(Language::English, 1), "You have 1 unread message.");
+    assert_eq!(unread(Language::English, 3), "You have 3 unread messages.");
+    assert_eq!(unread(Language::Russian, 1), "У вас 1 непрочитанное сообщение.");
+    assert_eq!(unread(Language::Russian, 3), "У вас 3 непрочитанных сообщения.");
+    assert_eq!(unread(Language::Russian, 5), "У вас 5 непрочитанных сообщений.");
+}
+
+#[test]
+fn errors_are_translated() {
+    let invalid = |language, host: &str| {
+        i18n::text_in(language, "error-invalid-address", &[("host", FluentValue::from(host)), ("address", FluentValue::from("pb2"))])
+    };
+
+    assert_eq!(invalid(Language::English, "website"), "Website address pb2 is not a valid http(s) URL");
+    assert_eq!(invalid(Language::English, "player"), "Player mirror address pb2 is not a valid http(s) URL");
+    assert_eq!(invalid(Language::Russian, "website"), "Адрес сайта pb2 не является http(s)-адресом");
+}
+
+#[test]
+fn unknown_texts_show_their_id() {
+    assert_eq!(i18n::text_in(Language::Russian, "no-such-text", &[]), "no-such-text");
+}
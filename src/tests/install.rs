@@ -0,0 +1,223 @@
+//! Installing, updating, repairing and uninstalling the game.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use iced::futures::StreamExt;
+
+use super::mock_server::{MockServer, Response};
+use super::*;
+use crate::error::ErrorKind;
+use crate::runtime::{GameRuntime, RuntimeKind};
+use crate::settings::Settings;
+use crate::install::{self, check_for_update, InstallState};
+use crate::notifications::{Level, Progress, Reporter};
+use crate::versions::{self, UpdateStatus};
+
+fn flash_player() -> Box<dyn GameRuntime> {
+    RuntimeKind::FlashPlayer.runtime(&Settings::default())
+}
+
+fn player_file(dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(dir)
+        .unwrap()
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.file_name().unwrap().to_string_lossy().starts_with("flashplayer") && path.extension() != Some("size".as_ref()))
+}
+
+#[tokio::test]
+async fn fresh_install_downloads_everything() {
+    let server = MockServer::start(pb2_site);
+    let dir = temp_dir("install", "fresh");
+
+    install::install(&server.session(), &dir, &*flash_player(), &Reporter::silent()).await.unwrap();
+
+    let build = dir.join("versions").join(TIME);
+    assert_eq!(fs::read_to_string(build.join("last_update.v")).unwrap(), TIME);
+    assert_eq!(fs::read(build.join("pb2_re34_alt.swf")).unwrap(), GAME_SWF);
+    assert!(player_file(&dir).is_some());
+}
+
+#[tokio::test]
+async fn install_reports_the_bytes_downloaded() {
+    let server = MockServer::start(pb2_site);
+    let dir = temp_dir("install", "progress");
+    let (reporter, progress) = Reporter::silent().with_progress();
+
+    install::install(&server.session(), &dir, &*flash_player(), &reporter).await.unwrap();
+    drop(reporter);
+
+    let progress: Vec<_> = progress.collect().await;
+    let swf = GAME_SWF.len() as u64;
+    assert!(progress.contains(&Progress { received: 0, total: Some(swf) }));
+    assert_eq!(progress.last(), Some(&Progress { received: swf, total: Some(swf) }));
+}
+
+#[tokio::test]
+async fn cut_short_player_is_damaged() {
+    let server = MockServer::start(pb2_site);
+    let dir = temp_dir("install", "cut-short-player");
+    install::install(&server.session(), &dir, &*flash_player(), &Reporter::silent()).await.unwrap();
+    fs::write(player_file(&dir).unwrap(), &PLAYER[..8]).unwrap();
+
+    let err = flash_player().verify(&dir).unwrap_err();
+
+    assert_eq!(err.context, "Flashplayer download is damaged, press Repair");
+}
+
+#[tokio::test]
+async fn up_to_date_install_is_left_alone() {
+    let server = MockServer::start(pb2_site);
+    let dir = temp_dir("install", "up-to-date");
+
+    install::install(&server.session(), &dir, &*flash_player(), &Reporter::silent()).await.unwrap();
+    install::update(&server.session(), &dir, &*flash_player(), &Reporter::silent()).await.unwrap();
+
+    assert_eq!(server.hits("/pb2/pb2_re34.swf"), 1);
+    assert_eq!(server.requests().iter().filter(|request| request.path.starts_with("/mirror/")).count(), 1);
+}
+
+#[tokio::test]
+async fn new_time_stamp_installs_next_to_the_previous_build() {
+    let time = Arc::new(Mutex::new(TIME.to_string()));
+    let served_time = time.clone();
+    let server = MockServer::start(move |request| {
+        if request.path == "/launcher/time.php" {
+            Response::text(&served_time.lock().unwrap())
+        } else {
+            pb2_site(request)
+        }
+    });
+    let dir = temp_dir("install", "update");
+
+    install::install(&server.session(), &dir, &*flash_player(), &Reporter::silent()).await.unwrap();
+    *time.lock().unwrap() = "1720999999".to_string();
+    install::update(&server.session(), &dir, &*flash_player(), &Reporter::silent()).await.unwrap();
+
+    assert_eq!(server.hits("/pb2/pb2_re34.swf"), 2);
+    let builds: Vec<String> = versions::installed(&dir).unwrap().into_iter().map(|version| version.id).collect();
+    assert_eq!(builds, ["1720999999", TIME]);
+}
+
+#[tokio::test]
+async fn missing_game_file_is_reported_with_context() {
+    let server = MockServer::start(|request| match request.path.as_str() {
+        "/pb2/pb2_re34.swf" => Response::not_found(),
+        _ => pb2_site(request),
+    });
+    let dir = temp_dir("install", "missing-swf");
+
+    let err = install::install(&server.session(), &dir, &*flash_player(), &Reporter::silent()).await.unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::Network);
+    assert_eq!(err.context, "Failed to download PB2 swf");
+    assert!(versions::installed(&dir).unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn download_outcomes_are_reported() {
+    let server = MockServer::start(pb2_site);
+    let dir = temp_dir("install", "reported");
+    let (reporter, mut reports) = Reporter::channel();
+
+    install::install(&server.session(), &dir, &*flash_player(), &reporter).await.unwrap();
+    install::update(&server.session(), &dir, &*flash_player(), &reporter).await.unwrap();
+    drop(reporter);
+
+    let mut outcomes = Vec::new();
+    while let Ok(report) = reports.try_recv() {
+        outcomes.push(report);
+    }
+    assert!(outcomes.contains(&(Level::Success, "Flashplayer downloaded successfully.".to_string())));
+    assert!(outcomes.contains(&(Level::Success, "PB2 downloaded successfully.".to_string())));
+    assert_eq!(outcomes.last(), Some(&(Level::Success, "PB2 is up to date.".to_string())));
+}
+
+#[tokio::test]
+async fn update_check_compares_without_downloading() {
+    let server = MockServer::start(pb2_site);
+    let dir = temp_dir("install", "check");
+
+    assert_eq!(check_for_update(&server.session(), &dir).await.unwrap(), UpdateStatus::NotInstalled);
+    install::install(&server.session(), &dir, &*flash_player(), &Reporter::silent()).await.unwrap();
+    assert_eq!(check_for_update(&server.session(), &dir).await.unwrap(), UpdateStatus::UpToDate);
+
+    assert_eq!(server.hits("/pb2/pb2_re34.swf"), 1);
+}
+
+#[tokio::test]
+async fn failed_update_check_is_a_network_error() {
+    let dir = temp_dir("install", "check-offline");
+
+    let err = check_for_update(&unreachable_session(), &dir).await.unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::Network);
+    assert_eq!(err.context, "Failed to get PB2 time");
+}
+
+#[tokio::test]
+async fn update_only_fetches_the_damaged_player() {
+    let server = MockServer::start(pb2_site);
+    let dir = temp_dir("install", "update-player");
+    install::install(&server.session(), &dir, &*flash_player(), &Reporter::silent()).await.unwrap();
+    fs::write(player_file(&dir).unwrap(), "").unwrap();
+
+    install::update(&server.session(), &dir, &*flash_player(), &Reporter::silent()).await.unwrap();
+
+    assert_eq!(server.requests().iter().filter(|request| request.path.starts_with("/mirror/")).count(), 2);
+    assert_eq!(server.hits("/pb2/pb2_re34.swf"), 1);
+    assert!(flash_player().verify(&dir).is_ok());
+}
+
+#[tokio::test]
+async fn repair_fetches_the_damaged_latest_build_and_drops_older_ones() {
+    let server = MockServer::start(pb2_site);
+    let dir = temp_dir("install", "repair");
+    install::install(&server.session(), &dir, &*flash_player(), &Reporter::silent()).await.unwrap();
+    let latest = versions::versions_dir(&dir).join(TIME);
+    fs::write(latest.join(versions::GAME_FILE_NAME), "<html>Not Found</html>").unwrap();
+    let older = versions::versions_dir(&dir).join("1700000000");
+    fs::create_dir_all(&older).unwrap();
+    fs::write(older.join(versions::GAME_FILE_NAME), "").unwrap();
+    fs::write(older.join(versions::TIME_FILE_NAME), "1700000000").unwrap();
+    assert!(matches!(install::state(&dir, Some(&*flash_player())).unwrap(), InstallState::Damaged(problems) if problems.len() == 2));
+
+    install::repair(&server.session(), &dir, &*flash_player(), &Reporter::silent()).await.unwrap();
+
+    assert_eq!(fs::read(latest.join(versions::GAME_FILE_NAME)).unwrap(), GAME_SWF);
+    assert!(!older.exists());
+    assert_eq!(install::state(&dir, Some(&*flash_player())).unwrap(), InstallState::Installed(TIME.to_string()));
+}
+
+#[tokio::test]
+async fn repair_leaves_intact_files_alone() {
+    let server = MockServer::start(pb2_site);
+    let dir = temp_dir("install", "intact");
+    let (reporter, reports) = Reporter::channel();
+    install::install(&server.session(), &dir, &*flash_player(), &Reporter::silent()).await.unwrap();
+
+    install::repair(&server.session(), &dir, &*flash_player(), &reporter).await.unwrap();
+    drop(reporter);
+
+    assert_eq!(server.hits("/pb2/pb2_re34.swf"), 1);
+    assert_eq!(server.requests().iter().filter(|request| request.path.starts_with("/mirror/")).count(), 1);
+    let outcomes: Vec<_> = reports.collect().await;
+    assert_eq!(outcomes.last(), Some(&(Level::Success, "All game files are intact.".to_string())));
+}
+
+#[tokio::test]
+async fn uninstall_removes_only_the_game_files() {
+    let server = MockServer::start(pb2_site);
+    let dir = temp_dir("install", "uninstall");
+    install::install(&server.session(), &dir, &*flash_player(), &Reporter::silent()).await.unwrap();
+    fs::write(dir.join("launcher_settings.json"), "{}").unwrap();
+
+    install::uninstall(&dir).unwrap();
+
+    assert!(!versions::versions_dir(&dir).exists());
+    assert_eq!(player_file(&dir), None);
+    assert!(dir.join("launcher_settings.json").exists());
+    assert_eq!(install::state(&dir, Some(&*flash_player())).unwrap(), InstallState::NotInstalled);
+}
@@ -0,0 +1,60 @@
+//! The single running instance.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use iced::futures::StreamExt;
+
+use crate::instance::{self, Claim};
+
+fn temp_socket(name: &str) -> PathBuf {
+    let socket = std::env::temp_dir().join(format!("pb2-launcher-{}-{}.sock", name, std::process::id()));
+    let _ = fs::remove_file(&socket);
+    socket
+}
+
+fn links(links: &[&str]) -> Vec<String> {
+    links.iter().map(|link| link.to_string()).collect()
+}
+
+#[test]
+fn socket_depends_on_the_install() {
+    let first = instance::socket_path(Path::new("/opt/pb2"));
+
+    assert_eq!(first, instance::socket_path(Path::new("/opt/pb2")));
+    assert_ne!(first, instance::socket_path(Path::new("/home/tester/pb2")));
+    assert!(first.file_name().unwrap().to_string_lossy().starts_with("pb2-launcher-"));
+}
+
+#[tokio::test]
+async fn second_start_forwards_its_links_to_the_first() {
+    let socket = temp_socket("forward");
+    let Claim::Primary(requests) = instance::claim(&socket, &[]).unwrap() else {
+        panic!("first start must become the running launcher");
+    };
+    let mut forwarded = requests.stream();
+
+    assert!(matches!(instance::claim(&socket, &links(&["pb2://play?map=tower", "pb2://login"])).unwrap(), Claim::Forwarded));
+    assert!(matches!(instance::claim(&socket, &[]).unwrap(), Claim::Forwarded));
+
+    let first = tokio::time::timeout(Duration::from_secs(5), forwarded.next()).await.unwrap();
+    assert_eq!(first.unwrap(), links(&["pb2://play?map=tower", "pb2://login"]));
+    // A start without links only brings the window up
+    let second = tokio::time::timeout(Duration::from_secs(5), forwarded.next()).await.unwrap();
+    assert_eq!(second.unwrap(), Vec::<String>::new());
+
+    instance::release(&socket);
+}
+
+#[test]
+fn socket_left_by_a_crashed_launcher_is_taken_over() {
+    let socket = temp_socket("stale");
+    drop(std::os::unix::net::UnixListener::bind(&socket).unwrap());
+    assert!(socket.exists());
+
+    assert!(matches!(instance::claim(&socket, &[]).unwrap(), Claim::Primary(_)));
+
+    instance::release(&socket);
+    assert!(!socket.exists());
+}
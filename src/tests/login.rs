@@ -0,0 +1,110 @@
+//! Website and launcher sign in flows.
+
+use super::mock_server::MockServer;
+use super::*;
+use crate::error::ErrorKind;
+use crate::LauncherMainWindow::handle_login;
+
+#[tokio::test]
+async fn website_login_returns_welcome_message() {
+    let server = MockServer::start(pb2_site);
+
+    let message = crate::login_website_http_post(&server.session(), &WEBSITE_USER.to_string(), &WEBSITE_PASSWORD.to_string())
+        .await
+        .unwrap();
+
+    assert_eq!(message, "Welcome back, Tester!");
+}
+
+#[tokio::test]
+async fn website_login_sends_md5_password() {
+    let server = MockServer::start(pb2_site);
+
+    crate::login_website_http_post(&server.session(), &WEBSITE_USER.to_string(), &WEBSITE_PASSWORD.to_string())
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert!(requests[0].body.contains(&format!("password={:x}", md5::compute(WEBSITE_PASSWORD))));
+}
+
+#[tokio::test]
+async fn website_login_returns_alert_text() {
+    let server = MockServer::start(pb2_site);
+
+    let message = crate::login_website_http_post(&server.session(), &WEBSITE_USER.to_string(), &"wrong".to_string())
+        .await
+        .unwrap();
+
+    assert_eq!(message, "Wrong login or password.\r\nPlease try again.");
+}
+
+#[tokio::test]
+async fn rq_load_returns_server_answer() {
+    let server = MockServer::start(pb2_site);
+
+    let answer = crate::login_website_http_post_rq_load(&server.session(), &LAUNCHER_USER.to_string(), &LAUNCHER_PASSWORD.to_string())
+        .await
+        .unwrap();
+
+    assert_eq!(answer, RQ_LOAD_SUCCESS);
+    assert_eq!(server.hits("/pb2/server.php"), 1);
+}
+
+#[tokio::test]
+async fn handle_login_with_website_password() {
+    let server = MockServer::start(pb2_site);
+
+    let (message, has_signed_in) = handle_login(&server.session(), &WEBSITE_USER.to_string(), &WEBSITE_PASSWORD.to_string()).await.unwrap();
+
+    assert!(has_signed_in);
+    assert!(message.starts_with("Welcome back, Tester!"));
+    assert!(message.contains("game website"));
+    assert_eq!(server.hits("/pb2/server.php"), 0);
+}
+
+#[tokio::test]
+async fn handle_login_with_md5_password() {
+    let server = MockServer::start(pb2_site);
+    let md5_password = format!("{:x}", md5::compute(WEBSITE_PASSWORD));
+
+    let (message, has_signed_in) = handle_login(&server.session(), &WEBSITE_USER.to_string(), &md5_password).await.unwrap();
+
+    assert!(has_signed_in);
+    assert!(message.contains("md5 password"));
+}
+
+#[tokio::test]
+async fn handle_login_falls_back_to_standalone_launcher() {
+    let server = MockServer::start(pb2_site);
+
+    let (message, has_signed_in) = handle_login(&server.session(), &LAUNCHER_USER.to_string(), &LAUNCHER_PASSWORD.to_string()).await.unwrap();
+
+    assert!(has_signed_in);
+    assert!(message.contains("standalone launcher"));
+    assert_eq!(server.hits("/"), 1);
+    assert_eq!(server.hits("/pb2/server.php"), 1);
+}
+
+#[tokio::test]
+async fn handle_login_with_wrong_password() {
+    let server = MockServer::start(pb2_site);
+
+    let (message, has_signed_in) = handle_login(&server.session(), &WEBSITE_USER.to_string(), &"wrong".to_string()).await.unwrap();
+
+    assert!(!has_signed_in);
+    assert!(message.starts_with("Wrong login or password."));
+}
+
+#[tokio::test]
+async fn handle_login_without_server_is_a_network_error() {
+    let session = unreachable_session();
+
+    let err = handle_login(&session, &WEBSITE_USER.to_string(), &WEBSITE_PASSWORD.to_string()).await.unwrap_err();
+
+    assert_eq!(err.kind, ErrorKind::Network);
+    assert_eq!(err.context, "Failed to send sign in request");
+    assert!(err.is_retryable());
+}
@@ -0,0 +1,101 @@
+//! Map listings, map search and recently played maps.
+
+use std::fs;
+
+use super::mock_server::{MockServer, Response};
+use super::*;
+use crate::maps::{fetch_maps, launch_parameter, parse_map_listing, MapEntry, RecentMaps};
+use crate::LauncherMainWindow::game_parameters;
+
+fn map(id: &str) -> MapEntry {
+    MapEntry { id: id.to_string(), name: id.to_string(), author: None, rating: None }
+}
+
+#[test]
+fn map_listing_is_parsed() {
+    let maps = parse_map_listing(MAPS_PAGE);
+
+    assert_eq!(
+        maps,
+        vec![
+            MapEntry {
+                id: "tester-arena".to_string(),
+                name: "Tester's Arena".to_string(),
+                author: Some("Tester".to_string()),
+                rating: Some(4.5),
+            },
+            MapEntry {
+                id: "night raid".to_string(),
+                name: "Night Raid".to_string(),
+                author: Some("Eric Gurt".to_string()),
+                rating: Some(5.0),
+            },
+            map("untitled-17"),
+        ]
+    );
+}
+
+#[test]
+fn pages_without_maps_give_an_empty_listing() {
+    assert!(parse_map_listing(FRONT_PAGE).is_empty());
+}
+
+#[tokio::test]
+async fn search_is_sent_to_the_website() {
+    let server = MockServer::start(|request| {
+        if request.path.starts_with("/?a=&s=9&") {
+            Response::html(MAPS_PAGE)
+        } else {
+            Response::not_found()
+        }
+    });
+
+    let maps = fetch_maps(&server.session(), " night raid ", 2).await.unwrap();
+
+    assert_eq!(maps.len(), 3);
+    assert_eq!(server.hits("/?a=&s=9&pg=2&search=night+raid"), 1);
+}
+
+#[test]
+fn map_is_added_to_the_game_parameters() {
+    assert_eq!(launch_parameter("night raid"), "pl=night+raid");
+    assert_eq!(
+        game_parameters(Some(("Tester".to_string(), "secret".to_string())), Some("tester-arena")),
+        "?l=Tester&p=secret&from_standalone=1&pl=tester-arena"
+    );
+    assert_eq!(game_parameters(None, None), "?l=.guest&p=.guest&from_standalone=1");
+}
+
+#[test]
+fn replaying_a_map_moves_it_to_the_front() {
+    let mut recent = RecentMaps::default();
+    recent.record(map("a"));
+    recent.record(map("b"));
+    recent.record(map("a"));
+
+    let ids: Vec<&str> = recent.maps().iter().map(|map| map.id.as_str()).collect();
+    assert_eq!(ids, ["a", "b"]);
+}
+
+#[test]
+fn recent_maps_are_capped() {
+    let mut recent = RecentMaps::default();
+    for index in 0..30 {
+        recent.record(map(&index.to_string()));
+    }
+
+    assert_eq!(recent.maps().len(), 20);
+    assert_eq!(recent.maps()[0].id, "29");
+}
+
+#[test]
+fn recent_maps_survive_a_round_trip() {
+    let path = std::env::temp_dir().join(format!("pb2-launcher-recent-maps-{}.json", std::process::id()));
+    let mut recent = RecentMaps::default();
+    recent.record(parse_map_listing(MAPS_PAGE).remove(0));
+
+    recent.save(&path).unwrap();
+
+    assert_eq!(RecentMaps::load(&path).unwrap(), recent);
+    fs::remove_file(&path).unwrap();
+}
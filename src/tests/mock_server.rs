@@ -0,0 +1,163 @@
+//! Local HTTP server that stands in for the website in tests.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::http::HttpOptions;
+use crate::session::Session;
+use crate::Endpoints;
+
+/// A request as seen by the mock server.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A canned response for a request.
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn html(body: &str) -> Self {
+        Response { status: 200, content_type: "text/html; charset=utf-8", headers: Vec::new(), body: body.as_bytes().to_vec() }
+    }
+
+    pub fn text(body: &str) -> Self {
+        Response { status: 200, content_type: "text/plain", headers: Vec::new(), body: body.as_bytes().to_vec() }
+    }
+
+    pub fn bytes(body: &[u8]) -> Self {
+        Response { status: 200, content_type: "application/octet-stream", headers: Vec::new(), body: body.to_vec() }
+    }
+
+    pub fn not_found() -> Self {
+        Response { status: 404, content_type: "text/plain", headers: Vec::new(), body: b"Not Found".to_vec() }
+    }
+
+    pub fn with_header(mut self, name: &'static str, value: &str) -> Self {
+        self.headers.push((name, value.to_string()));
+        self
+    }
+}
+
+type Handler = dyn Fn(&Request) -> Response + Send + Sync;
+
+/// Minimal HTTP/1.1 server on a random local port that answers every request
+/// through `handler` and remembers what it was asked for.
+///
+/// The listener thread is detached and lives until the test process exits.
+pub struct MockServer {
+    address: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+    pub fn start(handler: impl Fn(&Request) -> Response + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind mock server");
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let log = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let handler = handler.clone();
+                let log = log.clone();
+                thread::spawn(move || {
+                    if let Err(err) = serve_connection(stream, &*handler, &log) {
+                        eprintln!("Mock server connection failed: {}", err);
+                    }
+                });
+            }
+        });
+
+        MockServer { address, requests }
+    }
+
+    /// Endpoints pointing both the website and the player mirror at this server.
+    pub fn endpoints(&self) -> Endpoints {
+        Endpoints {
+            website: self.address.clone(),
+            player_mirror: format!("{}/mirror", self.address),
+            ruffle_mirror: format!("{}/ruffle", self.address),
+        }
+    }
+
+    /// Fresh HTTP session against [`MockServer::endpoints`].
+    pub fn session(&self) -> Session {
+        Session::new(self.endpoints(), &HttpOptions::default()).unwrap()
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    pub fn hits(&self, path: &str) -> usize {
+        self.requests().iter().filter(|request| request.path == path).count()
+    }
+}
+
+fn serve_connection(stream: TcpStream, handler: &Handler, log: &Mutex<Vec<Request>>) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    let mut headers = Vec::new();
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let request = Request { method, path, headers, body: String::from_utf8_lossy(&body).into_owned() };
+    let response = handler(&request);
+    log.lock().unwrap().push(request);
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} MOCK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    )?;
+    for (name, value) in &response.headers {
+        write!(stream, "{}: {}\r\n", name, value)?;
+    }
+    write!(stream, "\r\n")?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
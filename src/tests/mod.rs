@@ -0,0 +1,153 @@
+//! Offline tests, one module per area of the launcher.
+//!
+//! Network tests talk to a [`mock_server::MockServer`] serving the captured pages in `tests/fixtures`,
+//! so none of them need the real website.
+
+mod mock_server;
+mod scrapers;
+mod login;
+mod session;
+mod http;
+mod status;
+mod install;
+mod notifications;
+mod settings;
+mod theme;
+mod profile;
+mod maps;
+mod versions;
+mod runtime;
+mod dmg;
+mod desktop;
+#[cfg(unix)]
+mod instance;
+mod receiver;
+mod tray;
+mod shortcuts;
+mod scheduler;
+mod fonts;
+mod i18n;
+mod platform;
+mod social;
+
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+use mock_server::{Request, Response};
+
+use crate::http::HttpOptions;
+use crate::session::Session;
+
+macro_rules! fixture {
+    ($name:literal) => {
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/", $name))
+    };
+}
+
+pub(crate) const FRONT_PAGE: &str = fixture!("front_page.html");
+pub(crate) const NEWS_PAGE_2: &str = fixture!("news_page_2.html");
+pub(crate) const LOGIN_SUCCESS: &str = fixture!("login_success.html");
+pub(crate) const LOGIN_ALERT: &str = fixture!("login_alert.html");
+pub(crate) const RQ_LOAD_SUCCESS: &str = fixture!("rq_load_success.txt");
+pub(crate) const PROFILE: &str = fixture!("profile.html");
+pub(crate) const RQ_LOAD_FAILURE: &str = fixture!("rq_load_failure.txt");
+pub(crate) const TIME: &str = fixture!("time.txt");
+pub(crate) const MAPS_PAGE: &str = fixture!("maps_page.html");
+pub(crate) const FRIENDS_PAGE: &str = fixture!("friends_page.html");
+pub(crate) const INBOX_PAGE: &str = fixture!("inbox_page.html");
+
+pub(crate) const GAME_SWF: &[u8] = b"FWS\x0a\x16\x00\x00\x00mock game body";
+pub(crate) const PLAYER: &[u8] = b"\x7fELF mock projector";
+
+// Accounts known to the mock website
+pub(crate) const WEBSITE_USER: &str = "Tester";
+pub(crate) const WEBSITE_PASSWORD: &str = "secret";
+pub(crate) const LAUNCHER_USER: &str = "Standalone";
+pub(crate) const LAUNCHER_PASSWORD: &str = "launcher-only";
+
+// Session cookie the mock website hands out on a successful sign in
+pub(crate) const SESSION_COOKIE: &str = "PHPSESSID=mock-session";
+
+/// Session against a port nothing listens on, without retries so it fails right away.
+pub(crate) fn unreachable_session() -> Session {
+    let endpoints = crate::Endpoints { website: "http://127.0.0.1:1".to_string(), ..Default::default() };
+    Session::new(endpoints, &HttpOptions { retries: 0, ..Default::default() }).unwrap()
+}
+
+/// Empty scratch directory of one test, removed again when dropped, even if the test fails.
+pub(crate) struct TempDir(PathBuf);
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Fresh scratch directory for the test `name` of `area`, unique to this test run.
+pub(crate) fn temp_dir(area: &str, name: &str) -> TempDir {
+    let dir = std::env::temp_dir().join(format!("pb2-launcher-{}-{}-{}", area, name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    TempDir(dir)
+}
+
+/// Answers like the game website does for the fixture accounts above.
+pub(crate) fn pb2_site(request: &Request) -> Response {
+    let website_password = format!("{:x}", md5::compute(WEBSITE_PASSWORD));
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/") if request.header("cookie") == Some(SESSION_COOKIE) => Response::html(LOGIN_SUCCESS),
+        // Friends and messages are only shown to signed in visitors
+        ("GET", "/?a=&s=10") if request.header("cookie") == Some(SESSION_COOKIE) => Response::html(FRIENDS_PAGE),
+        ("GET", "/?a=&s=11") if request.header("cookie") == Some(SESSION_COOKIE) => Response::html(INBOX_PAGE),
+        ("GET", "/?a=&s=10") | ("GET", "/?a=&s=11") => Response::html(FRONT_PAGE),
+        ("GET", "/") | ("GET", "/?a=&s=0&pg=0") => Response::html(FRONT_PAGE),
+        ("GET", path) if path.starts_with("/?a=&s=0&pg=") => Response::html(NEWS_PAGE_2),
+        ("GET", path) if path == format!("/?a=&s=7&ac={}", WEBSITE_USER) => Response::html(PROFILE),
+        ("POST", "/") => {
+            if request.body == format!("login={}&password={}&Submit=Log-in", WEBSITE_USER, website_password) {
+                Response::html(LOGIN_SUCCESS).with_header("Set-Cookie", &format!("{}; Path=/", SESSION_COOKIE))
+            } else {
+                Response::html(LOGIN_ALERT)
+            }
+        }
+        ("POST", "/pb2/server.php") => {
+            if request.body == format!("rq=load&l={}&p={}", LAUNCHER_USER, LAUNCHER_PASSWORD) {
+                Response::text(RQ_LOAD_SUCCESS)
+            } else {
+                Response::text(RQ_LOAD_FAILURE)
+            }
+        }
+        ("GET", "/launcher/time.php") => Response::text(TIME),
+        ("GET", "/pb2/pb2_re34.swf") => Response::bytes(GAME_SWF),
+        ("GET", path) if path.starts_with("/mirror/") => Response::bytes(PLAYER),
+        ("GET", path) if path.starts_with("/ruffle/") => Response::bytes(&ruffle_archive()),
+        _ => Response::not_found(),
+    }
+}
+
+/// Ruffle release archive holding the player at the path of every platform.
+pub(crate) fn ruffle_archive() -> Vec<u8> {
+    let mut archive = tar::Builder::new(flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast()));
+    for path in ["ruffle", "ruffle.exe", "Ruffle.app/Contents/MacOS/ruffle"] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(PLAYER.len() as u64);
+        header.set_mode(0o644);
+        archive.append_data(&mut header, path, PLAYER).unwrap();
+    }
+    archive.into_inner().unwrap().finish().unwrap()
+}
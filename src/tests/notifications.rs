@@ -0,0 +1,53 @@
+//! Notification toasts and history.
+
+use std::time::{Duration, Instant};
+
+use crate::notifications::{Level, Notifications};
+
+#[test]
+fn toasts_expire_by_level() {
+    let mut notifications = Notifications::default();
+    notifications.push(Level::Success, "PB2 is up to date.");
+    notifications.push(Level::Error, "Wrong login or password.");
+
+    notifications.expire(Instant::now() + Duration::from_secs(6));
+
+    let active: Vec<_> = notifications.active().map(|notification| notification.level).collect();
+    assert_eq!(active, [Level::Error]);
+
+    notifications.expire(Instant::now() + Duration::from_secs(60));
+    assert!(!notifications.has_active());
+    assert_eq!(notifications.history().count(), 2);
+}
+
+#[test]
+fn dismissed_toast_stays_in_history() {
+    let mut notifications = Notifications::default();
+    let id = notifications.push(Level::Info, "Downloading Flashplayer...");
+
+    notifications.dismiss(id);
+
+    assert!(!notifications.has_active());
+    assert_eq!(notifications.history().next().unwrap().text, "Downloading Flashplayer...");
+}
+
+#[test]
+fn logged_notification_is_not_shown_as_toast() {
+    let mut notifications = Notifications::default();
+    notifications.log(Level::Error, "Failed to download PB2 swf");
+
+    assert!(!notifications.has_active());
+    assert_eq!(notifications.history().count(), 1);
+}
+
+#[test]
+fn history_is_newest_first() {
+    let mut notifications = Notifications::default();
+    for text in ["first", "second", "third", "fourth", "fifth"] {
+        notifications.push(Level::Info, text);
+    }
+
+    assert_eq!(notifications.history().next().unwrap().text, "fifth");
+    assert_eq!(notifications.active().count(), 4);
+    assert_eq!(notifications.active().next().unwrap().text, "second");
+}
@@ -0,0 +1,43 @@
+//! Platform detection.
+
+use crate::platform::{Architecture, Platform};
+use crate::runtime::RuntimeKind;
+use crate::settings::Settings;
+
+#[test]
+fn platform_is_read_from_rust_target_names() {
+    assert_eq!(Platform::detect("linux", "aarch64"), Platform::Linux(Architecture::Aarch64));
+    assert_eq!(Platform::detect("windows", "x86"), Platform::Windows(Architecture::X86));
+    assert_eq!(Platform::detect("macos", "aarch64"), Platform::MacOS(Architecture::Aarch64));
+    assert_eq!(Platform::detect("freebsd", "x86_64"), Platform::Other("freebsd"));
+    assert_eq!(Platform::detect("linux", "riscv64").to_string(), "Linux riscv64");
+}
+
+#[test]
+fn apple_silicon_and_windows_on_arm_run_both_players() {
+    for platform in [Platform::detect("macos", "aarch64"), Platform::detect("windows", "aarch64")] {
+        assert_eq!(RuntimeKind::supported(&platform, &Settings::default()), [RuntimeKind::FlashPlayer, RuntimeKind::Ruffle]);
+    }
+}
+
+#[test]
+fn unsupported_runtime_falls_back_to_one_that_runs() {
+    let linux_x86 = Platform::detect("linux", "x86");
+
+    assert_eq!(RuntimeKind::pick(RuntimeKind::Ruffle, &linux_x86, &Settings::default()), Some(RuntimeKind::FlashPlayer));
+    assert_eq!(RuntimeKind::pick(RuntimeKind::Ruffle, &Platform::detect("linux", "x86_64"), &Settings::default()), Some(RuntimeKind::Ruffle));
+    assert_eq!(RuntimeKind::pick(RuntimeKind::Wine, &Platform::detect("macos", "x86_64"), &Settings::default()), Some(RuntimeKind::FlashPlayer));
+}
+
+#[test]
+fn no_runtime_is_picked_without_a_player() {
+    assert_eq!(RuntimeKind::pick(RuntimeKind::FlashPlayer, &Platform::detect("linux", "aarch64"), &Settings::default()), None);
+    assert_eq!(RuntimeKind::pick(RuntimeKind::FlashPlayer, &Platform::detect("freebsd", "x86_64"), &Settings::default()), None);
+}
+
+#[test]
+fn own_flash_player_runs_where_none_is_published() {
+    let settings = Settings { player_path: Some("/opt/flashplayer/flashplayer".into()), ..Default::default() };
+
+    assert_eq!(RuntimeKind::pick(RuntimeKind::FlashPlayer, &Platform::detect("linux", "aarch64"), &settings), Some(RuntimeKind::FlashPlayer));
+}
@@ -0,0 +1,76 @@
+//! Player profiles.
+
+use super::mock_server::{MockServer, Response};
+use super::*;
+use crate::profile::{fetch_profile, parse_profile_page, parse_rq_load, PlayerProfile};
+
+#[test]
+fn rq_load_fields_are_parsed() {
+    let profile = parse_rq_load(LAUNCHER_USER, RQ_LOAD_SUCCESS);
+
+    assert_eq!(profile.rank, Some(12));
+    assert_eq!(profile.xp, Some(34500));
+    assert_eq!(profile.kills, Some(1520));
+    assert_eq!(profile.deaths, Some(1000));
+    assert_eq!(profile.clan, None);
+}
+
+#[test]
+fn failed_rq_load_gives_empty_profile() {
+    assert!(parse_rq_load(LAUNCHER_USER, RQ_LOAD_FAILURE).is_empty());
+}
+
+#[test]
+fn profile_page_is_parsed() {
+    let profile = parse_profile_page(WEBSITE_USER, PROFILE);
+
+    assert_eq!(
+        profile,
+        PlayerProfile {
+            login: WEBSITE_USER.to_string(),
+            rank: Some(12),
+            xp: Some(34500),
+            kills: Some(1520),
+            deaths: Some(1000),
+            clan: Some("Falkonians".to_string()),
+            last_seen: Some("July 5, 2024".to_string()),
+        }
+    );
+    assert_eq!(profile.kill_death_ratio(), Some(1.52));
+}
+
+#[test]
+fn merge_keeps_known_fields() {
+    let mut profile = parse_rq_load(LAUNCHER_USER, "x&rank=40");
+    profile.merge(parse_profile_page(LAUNCHER_USER, PROFILE));
+
+    assert_eq!(profile.rank, Some(40));
+    assert_eq!(profile.clan.as_deref(), Some("Falkonians"));
+}
+
+#[tokio::test]
+async fn profile_is_fetched_from_both_sources() {
+    let server = MockServer::start(|request| match request.path.as_str() {
+        "/pb2/server.php" => Response::text("x&rank=13"),
+        _ => pb2_site(request),
+    });
+
+    let profile = fetch_profile(&server.session(), &WEBSITE_USER.to_string(), &WEBSITE_PASSWORD.to_string())
+        .await
+        .unwrap();
+
+    assert_eq!(profile.rank, Some(13));
+    assert_eq!(profile.last_seen.as_deref(), Some("July 5, 2024"));
+    assert_eq!(server.hits("/pb2/server.php"), 1);
+}
+
+#[tokio::test]
+async fn missing_profile_page_is_an_error() {
+    let server = MockServer::start(pb2_site);
+
+    let err = fetch_profile(&server.session(), &LAUNCHER_USER.to_string(), &LAUNCHER_PASSWORD.to_string())
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.context, "Failed to load player profile");
+}
@@ -0,0 +1,22 @@
+//! Receivers shared between subscriptions.
+
+use iced::futures::channel::mpsc;
+use iced::futures::StreamExt;
+
+use crate::receiver::SharedReceiver;
+
+#[tokio::test]
+async fn only_the_first_stream_gets_the_messages() {
+    let (sender, receiver) = mpsc::unbounded();
+    let shared = SharedReceiver::new(receiver);
+    sender.unbounded_send(1).unwrap();
+    drop(sender);
+
+    assert_eq!(shared.clone().stream().collect::<Vec<_>>().await, [1]);
+    assert_eq!(shared.stream().collect::<Vec<_>>().await, Vec::<i32>::new());
+}
+
+#[tokio::test]
+async fn default_receiver_is_empty() {
+    assert_eq!(SharedReceiver::<i32>::default().stream().collect::<Vec<_>>().await, Vec::<i32>::new());
+}
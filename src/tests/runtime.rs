@@ -0,0 +1,206 @@
+//! Player runtimes: Flash Player, Ruffle and Wine.
+
+use std::fs;
+
+use super::mock_server::{MockServer, Response};
+use super::*;
+use crate::notifications::Reporter;
+use crate::platform::{Architecture, Platform};
+use crate::runtime::{self, RuntimeKind, RUFFLE_DIR_NAME};
+use crate::settings::Settings;
+use crate::LauncherMainWindow::game_parameters;
+
+#[test]
+fn flash_player_reads_the_parameters_from_the_movie_url() {
+    let parameters = game_parameters(Some(("Tester".to_string(), "secret".to_string())), None);
+
+    assert_eq!(
+        RuntimeKind::FlashPlayer.runtime(&Settings::default()).arguments("/games/pb2.swf", &parameters),
+        ["/games/pb2.swf?l=Tester&p=secret&from_standalone=1"]
+    );
+}
+
+#[test]
+fn ruffle_gets_the_parameters_as_flashvars() {
+    let parameters = game_parameters(Some(("Tester".to_string(), "secret".to_string())), Some("night raid"));
+
+    assert_eq!(
+        RuntimeKind::Ruffle.runtime(&Settings::default()).arguments("/games/pb2.swf", &parameters),
+        ["-Pl=Tester", "-Pp=secret", "-Pfrom_standalone=1", "-Ppl=night raid", "/games/pb2.swf"]
+    );
+}
+
+#[test]
+fn runtime_is_picked_per_account() {
+    let mut settings = Settings::default();
+    settings.account_runtimes.insert("Tester".to_string(), RuntimeKind::Ruffle);
+
+    assert_eq!(settings.runtime_for(Some("Tester")), RuntimeKind::Ruffle);
+    assert_eq!(settings.runtime_for(Some("Marine")), RuntimeKind::FlashPlayer);
+    assert_eq!(settings.runtime_for(None), RuntimeKind::FlashPlayer);
+}
+
+#[test]
+fn missing_player_fails_verification() {
+    let dir = temp_dir("runtime", "missing");
+
+    let err = RuntimeKind::Ruffle.runtime(&Settings::default()).verify(&dir).unwrap_err();
+
+    assert_eq!(err.context, "Ruffle is not downloaded yet, press Repair");
+}
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+#[tokio::test]
+async fn ruffle_is_unpacked_and_verified() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let server = MockServer::start(pb2_site);
+    let dir = temp_dir("runtime", "ruffle");
+    let ruffle = RuntimeKind::Ruffle.runtime(&Settings::default());
+
+    ruffle.download(&server.session(), &dir, &Reporter::silent()).await.unwrap();
+    ruffle.download(&server.session(), &dir, &Reporter::silent()).await.unwrap();
+
+    let executable = ruffle.verify(&dir).unwrap();
+    assert_eq!(executable, dir.join(RUFFLE_DIR_NAME).join("ruffle"));
+    assert_eq!(fs::metadata(&executable).unwrap().permissions().mode() & 0o111, 0o111);
+    assert_eq!(server.requests().iter().filter(|request| request.path.starts_with("/ruffle/")).count(), 1);
+}
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+#[tokio::test]
+async fn damaged_ruffle_archive_leaves_nothing_behind() {
+    let server = MockServer::start(|request| match request.path.as_str() {
+        path if path.starts_with("/ruffle/") => Response::bytes(b"not an archive"),
+        _ => pb2_site(request),
+    });
+    let dir = temp_dir("runtime", "damaged");
+
+    let err = RuntimeKind::Ruffle.runtime(&Settings::default()).download(&server.session(), &dir, &Reporter::silent()).await.unwrap_err();
+
+    assert_eq!(err.context, "Ruffle archive is damaged");
+    assert!(!dir.join(RUFFLE_DIR_NAME).exists());
+}
+
+#[cfg(unix)]
+fn fake_player(path: &std::path::Path, content: &[u8], mode: u32) {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+    fs::set_permissions(path, fs::Permissions::from_mode(mode)).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn players_are_found_in_path_and_known_locations() {
+    let dir = temp_dir("runtime", "detect");
+    let in_path = dir.join("bin").join("flashplayer");
+    let flatpak = dir.join("flatpak").join("com.adobe.Flash-Player-Projector");
+    fake_player(&in_path, PLAYER, 0o755);
+    fake_player(&flatpak, b"#!/bin/sh\nexec flatpak run com.adobe.Flash-Player-Projector \"$@\"\n", 0o755);
+    fake_player(&dir.join("bin").join("flashplayerdebugger"), b"not a program", 0o755);
+    let path_var = std::env::join_paths([dir.join("bin"), dir.join("missing")]).unwrap();
+
+    let players = runtime::find_players(Some(&path_var), &[flatpak.clone(), in_path.clone()]);
+
+    assert_eq!(players, [in_path, flatpak]);
+}
+
+#[cfg(unix)]
+#[test]
+fn player_that_cannot_be_started_is_rejected() {
+    let dir = temp_dir("runtime", "validate");
+    let text = dir.join("readme.txt");
+    let not_executable = dir.join("flashplayer");
+    fake_player(&text, b"Flash Player", 0o755);
+    fake_player(&not_executable, PLAYER, 0o644);
+
+    assert!(runtime::validate_player(&text).unwrap_err().context.ends_with("is not a program"));
+    assert!(runtime::validate_player(&not_executable).unwrap_err().context.ends_with("is not executable"));
+    assert!(runtime::validate_player(&dir.join("missing")).is_err());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn own_flash_player_is_not_downloaded() {
+    let server = MockServer::start(pb2_site);
+    let dir = temp_dir("runtime", "own-player");
+    let player = dir.join("system").join("flashplayer");
+    fake_player(&player, PLAYER, 0o755);
+    let settings = Settings { player_path: Some(player.clone()), ..Default::default() };
+    let flash_player = RuntimeKind::FlashPlayer.runtime(&settings);
+
+    flash_player.download(&server.session(), &dir, &Reporter::silent()).await.unwrap();
+
+    assert_eq!(flash_player.verify(&dir).unwrap(), player);
+    assert_eq!(server.requests().iter().filter(|request| request.path.starts_with("/mirror/")).count(), 0);
+}
+
+#[test]
+fn wine_projector_matches_the_host_architecture() {
+    assert_eq!(runtime::windows_projector(&Platform::Linux(Architecture::X86)), "flashplayer-i686-pc-windows-msvc.exe");
+    assert_eq!(runtime::windows_projector(&Platform::Linux(Architecture::X86_64)), "flashplayer-x86_64-pc-windows-msvc.exe");
+}
+
+#[test]
+fn swf_path_is_translated_for_wine() {
+    assert_eq!(runtime::wine_path("/home/tester/pb2/pb2_re34_alt.swf"), r"Z:\home\tester\pb2\pb2_re34_alt.swf");
+    assert_eq!(runtime::wine_path(r"C:\pb2.swf"), r"C:\pb2.swf");
+}
+
+// Stands in for Wine: logs its prefix and arguments, and creates the prefix on wineboot
+#[cfg(unix)]
+fn stub_wine(dir: &std::path::Path) -> Settings {
+    let wine = dir.join("stub").join("wine");
+    let script = format!(
+        "#!/bin/sh\necho \"$WINEPREFIX|$*\" >> '{}'\n[ \"$1\" = wineboot ] && touch \"$WINEPREFIX/system.reg\"\nexit 0\n",
+        dir.join("wine.log").display()
+    );
+    fake_player(&wine, script.as_bytes(), 0o755);
+    Settings { wine_binary: wine.display().to_string(), ..Default::default() }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn wine_runtime_downloads_the_windows_projector_and_sets_up_its_prefix() {
+    let server = MockServer::start(pb2_site);
+    let dir = temp_dir("runtime", "wine");
+    let wine = RuntimeKind::Wine.runtime(&stub_wine(&dir));
+
+    wine.download(&server.session(), &dir, &Reporter::silent()).await.unwrap();
+    wine.download(&server.session(), &dir, &Reporter::silent()).await.unwrap();
+
+    let prefix = dir.join(runtime::WINE_DIR_NAME).join("prefix");
+    assert_eq!(fs::read(dir.join(runtime::WINE_DIR_NAME).join("flashplayer.exe")).unwrap(), PLAYER);
+    assert_eq!(server.hits("/mirror/flashplayer-x86_64-pc-windows-msvc.exe"), 1);
+    assert_eq!(fs::read_to_string(dir.join("wine.log")).unwrap(), format!("{}|wineboot --init\n", prefix.display()));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn wine_runtime_starts_the_projector_with_a_windows_path() {
+    let server = MockServer::start(pb2_site);
+    let dir = temp_dir("runtime", "wine-start");
+    let wine = RuntimeKind::Wine.runtime(&stub_wine(&dir));
+    wine.download(&server.session(), &dir, &Reporter::silent()).await.unwrap();
+
+    let status = wine.command(&dir, "/games/pb2.swf", "?l=.guest").unwrap().status().unwrap();
+
+    assert!(status.success());
+    let log = fs::read_to_string(dir.join("wine.log")).unwrap();
+    let projector = dir.join(runtime::WINE_DIR_NAME).join("flashplayer.exe");
+    assert!(log.ends_with(&format!("{} Z:\\games\\pb2.swf?l=.guest\n", projector.display())));
+}
+
+#[test]
+fn missing_wine_binary_is_reported() {
+    let dir = temp_dir("runtime", "no-wine");
+    fs::create_dir_all(dir.join(runtime::WINE_DIR_NAME)).unwrap();
+    fs::write(dir.join(runtime::WINE_DIR_NAME).join("flashplayer.exe"), PLAYER).unwrap();
+    let settings = Settings { wine_binary: "pb2-launcher-no-such-wine".to_string(), ..Default::default() };
+
+    let err = RuntimeKind::Wine.runtime(&settings).verify(&dir).unwrap_err();
+
+    assert!(err.context.starts_with("Wine binary pb2-launcher-no-such-wine was not found"));
+}
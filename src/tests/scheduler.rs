@@ -0,0 +1,28 @@
+//! Background checks.
+
+use std::time::Duration;
+
+use crate::scheduler::jittered;
+
+#[test]
+fn jitter_stays_within_a_tenth_of_the_interval() {
+    let interval = Duration::from_secs(600);
+
+    for random in [0, 1, 999, 1000, 1001, 2000, 2001, u64::MAX] {
+        let delay = jittered(interval, random);
+        assert!(delay >= Duration::from_secs(540) && delay <= Duration::from_secs(660), "{:?}", delay);
+    }
+    assert_eq!(jittered(interval, 1000), interval);
+}
+
+#[test]
+fn jitter_spreads_the_runs() {
+    let interval = Duration::from_secs(600);
+
+    assert!(jittered(interval, 0) < jittered(interval, 2000));
+}
+
+#[test]
+fn zero_interval_is_not_jittered() {
+    assert_eq!(jittered(Duration::ZERO, 12345), Duration::ZERO);
+}
@@ -0,0 +1,53 @@
+//! News page scrapers.
+
+use super::mock_server::MockServer;
+use super::*;
+
+#[test]
+fn news_pages_count_is_read_from_pagination() {
+    assert_eq!(crate::parse_news_pages_count(FRONT_PAGE), 4);
+}
+
+#[test]
+fn news_pages_count_without_pagination_is_one() {
+    assert_eq!(crate::parse_news_pages_count(LOGIN_ALERT), 1);
+}
+
+#[test]
+fn news_page_is_split_into_dates_and_text() {
+    let news = crate::parse_news_page(FRONT_PAGE);
+
+    assert_eq!(news.len(), 2);
+    assert_eq!(news[0].0, "July 5, 2024");
+    assert!(news[0].1.contains("Map of the Week"));
+    assert!(news[0].1.contains("<a href=\"/?a=maps\">maps page</a>"));
+    assert_eq!(news[1].0, "June 28, 2024");
+    assert!(news[1].1.contains("<a href=\"https://discord.gg/plazmaburst2\">Discord</a>"));
+}
+
+#[test]
+fn news_text_stops_at_the_next_news_block() {
+    let news = crate::parse_news_page(FRONT_PAGE);
+
+    assert!(!news[0].1.contains("maintenance"));
+}
+
+#[tokio::test]
+async fn news_is_fetched_from_the_server() {
+    let server = MockServer::start(pb2_site);
+
+    let (count, news, website_login) = crate::fetch_news(&server.session(), 2).await.unwrap();
+
+    assert_eq!(count, 4);
+    assert_eq!(news.len(), 1);
+    assert_eq!(news[0].0, "March 14, 2024");
+    assert_eq!(website_login, None);
+    assert_eq!(server.hits("/?a=&s=0&pg=2"), 1);
+}
+
+#[tokio::test]
+async fn unreachable_server_is_an_error() {
+    let session = unreachable_session();
+
+    assert!(crate::fetch_news(&session, 0).await.is_err());
+}
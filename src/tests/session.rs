@@ -0,0 +1,139 @@
+//! Sessions and their saved cookies.
+
+use std::fs;
+use std::path::PathBuf;
+
+use super::mock_server::MockServer;
+use super::*;
+use crate::http::{HttpOptions, Profile};
+use crate::session::{self, Session};
+use crate::Endpoints;
+
+fn temp_session_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("pb2-launcher-session-{}-{}.json", name, std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+async fn signed_in_session(server: &MockServer) -> Session {
+    let session = server.session();
+    crate::login_website_http_post(&session, &WEBSITE_USER.to_string(), &WEBSITE_PASSWORD.to_string())
+        .await
+        .unwrap();
+    session
+}
+
+#[test]
+fn welcome_box_names_the_account() {
+    assert_eq!(session::signed_in_as(LOGIN_SUCCESS), Some(WEBSITE_USER.to_string()));
+    assert_eq!(session::signed_in_as(FRONT_PAGE), None);
+}
+
+#[tokio::test]
+async fn website_login_keeps_session_cookie() {
+    let server = MockServer::start(pb2_site);
+    let session = signed_in_session(&server).await;
+
+    assert!(session.has_cookies());
+    let page = session.get(&server.endpoints().front_page(), Profile::Browser).await.unwrap().text().await.unwrap();
+    assert_eq!(session::signed_in_as(&page), Some(WEBSITE_USER.to_string()));
+    assert_eq!(server.requests().last().unwrap().header("cookie"), Some(SESSION_COOKIE));
+}
+
+#[tokio::test]
+async fn failed_login_leaves_no_cookies() {
+    let server = MockServer::start(pb2_site);
+    let session = server.session();
+
+    crate::login_website_http_post(&session, &WEBSITE_USER.to_string(), &"wrong".to_string()).await.unwrap();
+
+    assert!(!session.has_cookies());
+}
+
+#[tokio::test]
+async fn saved_session_is_restored() {
+    let server = MockServer::start(pb2_site);
+    let path = temp_session_file("restore");
+
+    signed_in_session(&server).await.save(&path).unwrap();
+    let restored = Session::restore(server.endpoints(), &HttpOptions::default(), &path).unwrap();
+
+    assert!(restored.has_cookies());
+    restored.get(&server.endpoints().front_page(), Profile::Browser).await.unwrap();
+    assert_eq!(server.requests().last().unwrap().header("cookie"), Some(SESSION_COOKIE));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn saved_session_is_private() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let server = MockServer::start(pb2_site);
+    let path = temp_session_file("private");
+
+    signed_in_session(&server).await.save(&path).unwrap();
+
+    assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn session_of_another_website_is_dropped() {
+    let server = MockServer::start(pb2_site);
+    let path = temp_session_file("other-website");
+
+    signed_in_session(&server).await.save(&path).unwrap();
+    let restored = Session::restore(crate::Endpoints::default(), &HttpOptions::default(), &path).unwrap();
+
+    assert!(!restored.has_cookies());
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn expired_session_is_detected() {
+    let server = MockServer::start(pb2_site);
+    let path = temp_session_file("expired");
+    let stored = serde_json::json!({ "website": server.endpoints().website, "cookies": "PHPSESSID=expired" });
+    fs::write(&path, stored.to_string()).unwrap();
+
+    let session = Session::restore(server.endpoints(), &HttpOptions::default(), &path).unwrap();
+    let (_, _, website_login) = crate::fetch_news(&session, 0).await.unwrap();
+
+    assert!(session.has_cookies());
+    assert_eq!(website_login, None);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn saving_without_cookies_forgets_the_session() {
+    let server = MockServer::start(pb2_site);
+    let path = temp_session_file("forget");
+    fs::write(&path, "{}").unwrap();
+
+    server.session().save(&path).unwrap();
+
+    assert!(!path.exists());
+}
+
+#[test]
+fn missing_session_file_gives_empty_session() {
+    let server = MockServer::start(pb2_site);
+
+    let session = Session::restore(server.endpoints(), &HttpOptions::default(), &temp_session_file("missing")).unwrap();
+
+    assert!(!session.has_cookies());
+}
+
+#[test]
+fn invalid_website_has_no_cookies() {
+    let endpoints = Endpoints { website: "localhost:8080".to_string(), ..Default::default() };
+
+    let session = Session::new(endpoints, &HttpOptions::default()).unwrap();
+
+    assert!(!session.has_cookies());
+}
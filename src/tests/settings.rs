@@ -0,0 +1,119 @@
+//! Settings files, their migrations and validation.
+
+use std::fs;
+
+use crate::i18n::Language;
+use crate::settings::{Setting, Settings, SETTINGS_VERSION, UI_SCALES};
+
+#[test]
+fn missing_file_gives_defaults() {
+    let path = std::env::temp_dir().join(format!("pb2-launcher-no-settings-{}.json", std::process::id()));
+
+    assert_eq!(Settings::load(&path).unwrap(), Settings::default());
+}
+
+#[test]
+fn settings_survive_a_round_trip() {
+    let path = std::env::temp_dir().join(format!("pb2-launcher-settings-{}.json", std::process::id()));
+    let mut settings = Settings::default();
+    settings.apply(Setting::Theme("pb2".to_string()));
+    settings.apply(Setting::ScrollbarWidth(4));
+    settings.apply(Setting::RememberPassword(false));
+    settings.apply(Setting::TrayIcon(true));
+    settings.apply(Setting::HideToTray(true));
+    settings.apply(Setting::Language(Some(Language::Russian)));
+
+    settings.save(&path).unwrap();
+
+    assert_eq!(Settings::load(&path).unwrap(), settings);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn unversioned_file_is_migrated() {
+    let settings = Settings::from_json(r#"{ "theme": "Nord", "scroller_width": 6 }"#).unwrap();
+
+    assert_eq!(settings.version, SETTINGS_VERSION);
+    assert_eq!(settings.theme, "dark");
+    assert_eq!(settings.scroller_width, 6);
+    assert_eq!(settings.scrollbar_width, Settings::default().scrollbar_width);
+}
+
+#[test]
+fn iced_theme_names_are_migrated_to_palettes() {
+    let light = Settings::from_json(r#"{ "version": 1, "theme": "Light" }"#).unwrap();
+    let dark = Settings::from_json(r#"{ "version": 1, "theme": "Dark" }"#).unwrap();
+
+    assert_eq!(light.theme, "light");
+    assert_eq!(dark.theme, "dark");
+}
+
+#[test]
+fn current_version_is_not_migrated() {
+    let json = format!(r#"{{ "version": {}, "theme": "my palette" }}"#, SETTINGS_VERSION);
+
+    assert_eq!(Settings::from_json(&json).unwrap().theme, "my palette");
+}
+
+#[test]
+fn file_from_newer_launcher_is_rejected() {
+    let json = format!(r#"{{ "version": {} }}"#, SETTINGS_VERSION + 1);
+
+    assert!(Settings::from_json(&json).is_err());
+    // Not wrapped around to an old version
+    let json = format!(r#"{{ "version": {} }}"#, (1u64 << 32) + 1);
+    assert!(Settings::from_json(&json).is_err());
+}
+
+#[test]
+fn damaged_file_is_an_error() {
+    assert!(Settings::from_json("{ theme: ").is_err());
+}
+
+#[test]
+fn typed_endpoints_are_normalized() {
+    let mut settings = Settings::default();
+    settings.apply(Setting::Website(" http://127.0.0.1:8080/ ".to_string()));
+    settings.apply(Setting::PlayerMirror(String::new()));
+
+    settings.normalize();
+
+    assert_eq!(settings.endpoints.website, "http://127.0.0.1:8080");
+    assert_eq!(settings.endpoints.player_mirror, Settings::default().endpoints.player_mirror);
+}
+
+#[test]
+fn website_without_scheme_is_rejected() {
+    for website in ["pb2.example", "localhost:8080", "ftp://pb2.example"] {
+        let mut settings = Settings::default();
+        settings.apply(Setting::Website(website.to_string()));
+
+        settings.normalize();
+
+        assert!(settings.endpoints.validate().is_err(), "{} was accepted", website);
+        assert!(settings.endpoints.profile_page("Player").is_err());
+        assert!(settings.endpoints.maps_page("", 0).is_err());
+    }
+    assert!(Settings::default().endpoints.validate().is_ok());
+}
+
+#[test]
+fn interface_scale_stays_in_range() {
+    let mut settings = Settings::default();
+    settings.apply(Setting::UiScale(1000));
+
+    settings.normalize();
+
+    assert_eq!(settings.ui_scale, *UI_SCALES.end());
+}
+
+#[test]
+fn connection_settings_default_when_missing() {
+    let json = format!(r#"{{ "version": {}, "http": {{ "proxy": "socks5://127.0.0.1:1080" }} }}"#, SETTINGS_VERSION);
+
+    let settings = Settings::from_json(&json).unwrap();
+
+    assert_eq!(settings.http.proxy, "socks5://127.0.0.1:1080");
+    assert_eq!(settings.http.read_timeout, Settings::default().http.read_timeout);
+    assert_eq!(settings.http.retries, Settings::default().http.retries);
+}
@@ -0,0 +1,46 @@
+//! Keyboard shortcuts.
+
+use iced::keyboard::key::Named;
+use iced::keyboard::{Key, Modifiers};
+
+use crate::shortcuts::{shortcut, Shortcut};
+
+fn character(character: &str) -> Key {
+    Key::Character(character.into())
+}
+
+#[test]
+fn tab_moves_the_focus_both_ways() {
+    let tab = Key::Named(Named::Tab);
+
+    assert_eq!(shortcut(&tab, Modifiers::empty(), false), Some(Shortcut::FocusNext));
+    assert_eq!(shortcut(&tab, Modifiers::SHIFT, true), Some(Shortcut::FocusPrevious));
+}
+
+#[test]
+fn pages_switch_unless_a_text_field_uses_the_key() {
+    for key in [Named::PageDown, Named::ArrowRight] {
+        assert_eq!(shortcut(&Key::Named(key), Modifiers::empty(), false), Some(Shortcut::NextPage));
+        assert_eq!(shortcut(&Key::Named(key), Modifiers::empty(), true), None);
+    }
+    for key in [Named::PageUp, Named::ArrowLeft] {
+        assert_eq!(shortcut(&Key::Named(key), Modifiers::empty(), false), Some(Shortcut::PreviousPage));
+    }
+}
+
+#[test]
+fn escape_and_f5_work_while_typing() {
+    assert_eq!(shortcut(&Key::Named(Named::Escape), Modifiers::empty(), true), Some(Shortcut::Cancel));
+    assert_eq!(shortcut(&Key::Named(Named::F5), Modifiers::empty(), true), Some(Shortcut::Refresh));
+}
+
+#[test]
+fn command_shortcuts_ignore_letter_case() {
+    assert_eq!(shortcut(&character("l"), Modifiers::COMMAND, true), Some(Shortcut::OpenLogin));
+    assert_eq!(shortcut(&character("L"), Modifiers::COMMAND | Modifiers::SHIFT, false), Some(Shortcut::OpenLogin));
+    assert_eq!(shortcut(&character("+"), Modifiers::COMMAND | Modifiers::SHIFT, false), Some(Shortcut::ZoomIn));
+    assert_eq!(shortcut(&character("-"), Modifiers::COMMAND, false), Some(Shortcut::ZoomOut));
+    assert_eq!(shortcut(&character("0"), Modifiers::COMMAND, false), Some(Shortcut::ResetZoom));
+    // Typing the letter alone is text
+    assert_eq!(shortcut(&character("l"), Modifiers::empty(), false), None);
+}
@@ -0,0 +1,61 @@
+//! Friends and messages.
+
+use super::mock_server::MockServer;
+use super::*;
+use crate::social::{fetch_social, parse_friends, parse_inbox, Friend, Inbox};
+
+#[test]
+fn friends_are_parsed_online_first() {
+    let friends = parse_friends(FRIENDS_PAGE);
+
+    assert_eq!(
+        friends,
+        vec![
+            Friend { login: "Eric Gurt".to_string(), online: true, last_seen: None },
+            Friend { login: "Marine".to_string(), online: false, last_seen: Some("2 days ago".to_string()) },
+            Friend { login: "Proxy".to_string(), online: false, last_seen: None },
+        ]
+    );
+}
+
+#[test]
+fn inbox_is_parsed_with_unread_marks() {
+    let inbox = parse_inbox(INBOX_PAGE);
+
+    assert_eq!(inbox.messages.len(), 3);
+    assert_eq!(inbox.unread_count(), 2);
+    let first = &inbox.messages[0];
+    assert_eq!((first.id.as_str(), first.from.as_str(), first.subject.as_str()), ("102", "Eric Gurt", "Map of the Week"));
+    assert_eq!(first.date.as_deref(), Some("July 6, 2024"));
+    assert!(!inbox.messages[2].unread);
+}
+
+#[test]
+fn only_unseen_unread_messages_are_new() {
+    let inbox = parse_inbox(INBOX_PAGE);
+    let previous = Inbox { messages: inbox.messages[1..].to_vec() };
+
+    let new: Vec<&str> = inbox.new_since(&previous).iter().map(|message| message.id.as_str()).collect();
+
+    assert_eq!(new, ["102"]);
+    assert!(inbox.new_since(&inbox).is_empty());
+}
+
+#[tokio::test]
+async fn social_is_loaded_through_the_signed_in_session() {
+    let server = MockServer::start(pb2_site);
+    let session = server.session();
+    crate::login_website_http_post(&session, &WEBSITE_USER.to_string(), &WEBSITE_PASSWORD.to_string()).await.unwrap();
+
+    let social = fetch_social(&session).await.unwrap().unwrap();
+
+    assert_eq!(social.friends.len(), 3);
+    assert_eq!(social.inbox.unread_count(), 2);
+}
+
+#[tokio::test]
+async fn guest_session_has_no_social() {
+    let server = MockServer::start(pb2_site);
+
+    assert_eq!(fetch_social(&server.session()).await.unwrap(), None);
+}
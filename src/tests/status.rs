@@ -0,0 +1,64 @@
+//! Server status probes and activity figures.
+
+use std::time::Duration;
+
+use super::mock_server::{MockServer, Response};
+use super::*;
+use crate::status::{self, parse_server_stats, Health, ServerStats, Service};
+
+#[test]
+fn activity_is_read_from_the_front_page() {
+    let stats = parse_server_stats(FRONT_PAGE);
+
+    assert_eq!(stats, ServerStats { players_online: Some(1024), active_servers: Some(37) });
+}
+
+#[test]
+fn pages_without_activity_give_no_figures() {
+    assert_eq!(parse_server_stats(LOGIN_ALERT), ServerStats::default());
+}
+
+#[test]
+fn activity_outside_the_online_box_is_ignored() {
+    let page = "<html><body><p>Patch notes: 3 servers were moved, 12 players online yesterday</p></body></html>";
+
+    assert_eq!(parse_server_stats(page), ServerStats::default());
+}
+
+#[tokio::test]
+async fn every_service_is_probed() {
+    let server = MockServer::start(pb2_site);
+
+    let report = status::check(&server.session()).await;
+
+    assert!(report.all_up());
+    assert_eq!(report.services.len(), Service::ALL.len());
+    assert_eq!(report.stats.players_online, Some(1024));
+    assert_eq!(server.hits("/"), 1);
+    assert_eq!(server.hits("/pb2/server.php"), 1);
+    assert_eq!(server.hits("/launcher/time.php"), 1);
+}
+
+#[tokio::test]
+async fn server_errors_mark_a_service_down() {
+    let server = MockServer::start(|request| match request.path.as_str() {
+        "/pb2/server.php" => Response { status: 502, ..Response::text("Bad Gateway") },
+        _ => pb2_site(request),
+    });
+
+    let report = status::check(&server.session()).await;
+
+    assert!(!report.all_up());
+    assert_eq!(report.health(Service::GameServer), Some(&Health::Down("HTTP 502".to_string())));
+    assert!(matches!(report.health(Service::Website), Some(Health::Up(latency)) if *latency < Duration::from_secs(5)));
+}
+
+#[tokio::test]
+async fn unreachable_site_is_down_everywhere() {
+    let report = status::check(&unreachable_session()).await;
+
+    for service in Service::ALL {
+        assert_eq!(report.health(service), Some(&Health::Down("unreachable".to_string())));
+    }
+    assert_eq!(report.stats, ServerStats::default());
+}
@@ -0,0 +1,73 @@
+//! Themes and custom colors.
+
+use std::fs;
+
+use iced::Color;
+
+use crate::theme::{contrast_ratio, load_custom_palettes, Palette};
+
+#[test]
+fn contrast_ratio_matches_wcag() {
+    assert!((contrast_ratio(Color::BLACK, Color::WHITE) - 21.0).abs() < 0.01);
+    assert!((contrast_ratio(Color::WHITE, Color::WHITE) - 1.0).abs() < 0.01);
+    assert_eq!(contrast_ratio(Color::BLACK, Color::WHITE), contrast_ratio(Color::WHITE, Color::BLACK));
+}
+
+#[test]
+fn built_in_palettes_are_readable() {
+    for palette in Palette::built_in() {
+        assert_eq!(palette.contrast_issues(), [], "{}", palette.name);
+    }
+}
+
+#[test]
+fn poor_contrast_is_reported() {
+    let mut palette = Palette::dark();
+    palette.hyperlink = palette.background;
+
+    let issues = palette.contrast_issues();
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].0, "links");
+}
+
+#[test]
+fn custom_palettes_are_read_from_file() {
+    let path = std::env::temp_dir().join(format!("pb2-launcher-themes-{}.json", std::process::id()));
+    let mut palette = Palette::pb2();
+    palette.name = "midnight".to_string();
+    palette.background = Color::from_rgb8(0x01, 0x02, 0x03);
+    fs::write(&path, serde_json::to_string(&[&palette]).unwrap()).unwrap();
+
+    let palettes = load_custom_palettes(&path).unwrap();
+
+    assert_eq!(palettes, [palette]);
+    assert!(fs::read_to_string(&path).unwrap().contains("\"background\":\"#010203\""));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn malformed_color_is_rejected() {
+    let path = std::env::temp_dir().join(format!("pb2-launcher-bad-themes-{}.json", std::process::id()));
+    let json = serde_json::to_string(&[Palette::light()]).unwrap().replacen("#ffffff", "white", 1);
+    fs::write(&path, json).unwrap();
+
+    assert!(load_custom_palettes(&path).is_err());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn non_hex_colors_are_rejected() {
+    for color in ["#fé000", "#+f+f+f", "#ff ff0"] {
+        let json = serde_json::to_string(&[Palette::light()]).unwrap().replacen("#ffffff", color, 1);
+
+        assert!(serde_json::from_str::<Vec<Palette>>(&json).is_err(), "{} was accepted", color);
+    }
+}
+
+#[test]
+fn missing_themes_file_means_no_custom_palettes() {
+    let path = std::env::temp_dir().join("pb2-launcher-no-themes.json");
+
+    assert!(load_custom_palettes(&path).unwrap().is_empty());
+}
@@ -0,0 +1,21 @@
+//! The tray icon.
+
+use crate::tray::{self, TrayAction};
+
+#[test]
+fn tray_menu_lists_the_quick_actions() {
+    let labels: Vec<String> = TrayAction::MENU.iter().map(ToString::to_string).collect();
+
+    assert_eq!(labels, ["Play", "Play as guest", "Check for updates", "Latest news", "Quit"]);
+}
+
+#[test]
+fn tray_icon_is_drawn_as_argb() {
+    let pixels = tray::icon_pixels(32);
+
+    assert_eq!(pixels.len(), 32 * 32 * 4);
+    // Corners are transparent, the middle is the opaque background
+    assert_eq!(pixels[..4], [0, 0, 0, 0]);
+    let middle = (16 * 32 + 16) * 4;
+    assert_eq!(pixels[middle..middle + 4], [255, 0x14, 0x20, 0x2e]);
+}
@@ -0,0 +1,112 @@
+//! Installed game builds.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+use super::{temp_dir, GAME_SWF};
+use crate::versions::{self, GameVersion, UpdateStatus, GAME_FILE_NAME, TIME_FILE_NAME};
+
+fn install(dir: &Path, id: &str) {
+    let build = versions::versions_dir(dir).join(id);
+    fs::create_dir_all(&build).unwrap();
+    fs::write(build.join(GAME_FILE_NAME), id).unwrap();
+    fs::write(build.join(TIME_FILE_NAME), id).unwrap();
+}
+
+fn ids(dir: &Path) -> Vec<String> {
+    versions::installed(dir).unwrap().into_iter().map(|version| version.id).collect()
+}
+
+#[test]
+fn builds_are_listed_newest_first() {
+    let dir = temp_dir("versions", "listed");
+    install(&dir, "1720180000");
+    install(&dir, "999");
+    install(&dir, "1720999999");
+    // Interrupted download, the time stamp is written last
+    fs::create_dir_all(versions::versions_dir(&dir).join("1721000000")).unwrap();
+    fs::write(versions::versions_dir(&dir).join("1721000000").join(GAME_FILE_NAME), "").unwrap();
+
+    assert_eq!(ids(&dir), ["1720999999", "1720180000", "999"]);
+}
+
+#[test]
+fn pinned_build_is_started_instead_of_the_newest() {
+    let dir = temp_dir("versions", "pinned");
+    install(&dir, "1720180000");
+    install(&dir, "1720999999");
+
+    assert_eq!(versions::resolve(&dir, None).unwrap().id, "1720999999");
+    assert_eq!(versions::resolve(&dir, Some("1720180000")).unwrap().id, "1720180000");
+    assert!(versions::resolve(&dir, Some("1")).unwrap_err().context.contains("no longer installed"));
+}
+
+#[test]
+fn cleanup_keeps_the_newest_and_the_pinned_build() {
+    let dir = temp_dir("versions", "cleanup");
+    for id in ["1", "2", "3", "4"] {
+        install(&dir, id);
+    }
+
+    let removed = versions::cleanup(&dir, 2, Some("1")).unwrap();
+
+    assert_eq!(removed, ["2"]);
+    assert_eq!(ids(&dir), ["4", "3", "1"]);
+    assert!(versions::cleanup(&dir, 0, None).unwrap().is_empty());
+}
+
+#[test]
+fn single_build_install_is_moved_into_its_version_directory() {
+    let dir = temp_dir("versions", "legacy");
+    fs::write(dir.join(GAME_FILE_NAME), "swf").unwrap();
+    fs::write(dir.join(TIME_FILE_NAME), "1720180000").unwrap();
+
+    assert_eq!(ids(&dir), ["1720180000"]);
+    assert!(!dir.join(GAME_FILE_NAME).exists());
+    assert_eq!(fs::read_to_string(versions::versions_dir(&dir).join("1720180000").join(GAME_FILE_NAME)).unwrap(), "swf");
+}
+
+#[test]
+fn cut_short_game_files_are_damaged() {
+    let dir = temp_dir("versions", "verify");
+    let version = GameVersion { id: "1720180000".to_string(), dir: dir.to_path_buf() };
+    let verify = |content: &[u8]| {
+        fs::write(version.game_file(), content).unwrap();
+        version.verify().is_ok()
+    };
+    // Same movie body, zlib compressed after the header
+    let mut compressed = GAME_SWF[..8].to_vec();
+    compressed[0] = b'C';
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&GAME_SWF[8..]).unwrap();
+    compressed.extend(encoder.finish().unwrap());
+
+    assert!(verify(GAME_SWF));
+    assert!(!verify(&GAME_SWF[..GAME_SWF.len() - 1]));
+    assert!(verify(&compressed));
+    assert!(!verify(&compressed[..compressed.len() - 4]));
+    assert!(!verify(b"<html>Not Found</html>"));
+}
+
+#[test]
+fn time_stamp_is_made_safe_for_a_directory_name() {
+    assert_eq!(versions::version_id(" 1720180000\n"), "1720180000");
+    assert_eq!(versions::version_id("../x"), ".._x");
+    assert_eq!(versions::version_id(""), "unknown");
+}
+
+#[test]
+fn update_status_compares_the_published_build() {
+    let dir = temp_dir("versions", "status");
+    assert_eq!(versions::update_status(&versions::installed(&dir).unwrap(), "1720180000"), UpdateStatus::NotInstalled);
+
+    install(&dir, "1720180000");
+    let installed = versions::installed(&dir).unwrap();
+
+    assert_eq!(versions::update_status(&installed, "1720180000"), UpdateStatus::UpToDate);
+    assert_eq!(versions::update_status(&installed, "1720999999"), UpdateStatus::Available("1720999999".to_string()));
+}
@@ -0,0 +1,219 @@
+use std::fs;
+use std::path::Path;
+
+use iced::{Color, Theme};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Context, ErrorKind, LauncherError};
+
+pub const THEMES_FILE_NAME: &str = "launcher_themes.json";
+
+pub const DARK: &str = "dark";
+pub const LIGHT: &str = "light";
+pub const PB2: &str = "pb2";
+
+// WCAG AA minimum for normal sized text
+const MIN_CONTRAST: f32 = 4.5;
+
+/// Every color the launcher draws with. Styles in `styles.rs` are derived from it.
+///
+/// Colors are written as `#rrggbb` in the themes file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Palette {
+    pub name: String,
+    #[serde(with = "hex")]
+    pub background: Color,
+    #[serde(with = "hex")]
+    pub text: Color,
+    #[serde(with = "hex")]
+    pub primary: Color,
+    #[serde(with = "hex")]
+    pub success: Color,
+    #[serde(with = "hex")]
+    pub warning: Color,
+    #[serde(with = "hex")]
+    pub danger: Color,
+    #[serde(with = "hex")]
+    pub info: Color,
+    /// Text drawn on top of the success, warning, danger and info colors.
+    #[serde(with = "hex")]
+    pub on_status: Color,
+    #[serde(with = "hex")]
+    pub news_date: Color,
+    #[serde(with = "hex")]
+    pub hyperlink: Color,
+    #[serde(with = "hex")]
+    pub page_button: Color,
+    #[serde(with = "hex")]
+    pub page_button_text: Color,
+    #[serde(with = "hex")]
+    pub selected_page_button: Color,
+    #[serde(with = "hex")]
+    pub selected_page_button_text: Color,
+}
+
+impl Palette {
+    pub fn dark() -> Self {
+        Palette {
+            name: DARK.to_string(),
+            background: rgb(0x20, 0x22, 0x25),
+            text: Color::WHITE,
+            primary: rgb(0x5E, 0x7C, 0xE2),
+            success: rgb(0x12, 0x66, 0x4F),
+            warning: rgb(0x8A, 0x55, 0x00),
+            danger: rgb(0x8B, 0x00, 0x00),
+            info: rgb(0x00, 0x4E, 0x8A),
+            on_status: Color::WHITE,
+            news_date: Color::from_rgb(0.58, 0.75, 0.95),
+            hyperlink: rgb(0xFF, 0xCC, 0x00),
+            page_button: rgb(0xFF, 0xA5, 0x00),
+            page_button_text: rgb(0x00, 0x00, 0x8B),
+            selected_page_button: rgb(0xFF, 0x00, 0x00),
+            selected_page_button_text: Color::BLACK,
+        }
+    }
+
+    pub fn light() -> Self {
+        Palette {
+            name: LIGHT.to_string(),
+            background: Color::WHITE,
+            text: Color::BLACK,
+            primary: rgb(0x3A, 0x5B, 0xC7),
+            success: rgb(0x12, 0x66, 0x4F),
+            warning: rgb(0x8A, 0x55, 0x00),
+            danger: rgb(0xA6, 0x1B, 0x1B),
+            info: rgb(0x00, 0x4E, 0x8A),
+            on_status: Color::WHITE,
+            news_date: rgb(0x1F, 0x4E, 0x8C),
+            hyperlink: rgb(0x8A, 0x4B, 0x00),
+            page_button: rgb(0xFF, 0xC0, 0x4D),
+            page_button_text: rgb(0x00, 0x00, 0x8B),
+            selected_page_button: rgb(0x00, 0x00, 0x8B),
+            selected_page_button_text: Color::WHITE,
+        }
+    }
+
+    /// Colors of the game website: dark blue panels with orange accents.
+    pub fn pb2() -> Self {
+        Palette {
+            name: PB2.to_string(),
+            background: rgb(0x0B, 0x14, 0x26),
+            text: rgb(0xE6, 0xEC, 0xF5),
+            primary: rgb(0xE0, 0x7B, 0x00),
+            success: rgb(0x1E, 0x6B, 0x3A),
+            warning: rgb(0x8A, 0x55, 0x00),
+            danger: rgb(0x9E, 0x1C, 0x1C),
+            info: rgb(0x1D, 0x4E, 0x89),
+            on_status: Color::WHITE,
+            news_date: rgb(0xFF, 0xB3, 0x47),
+            hyperlink: rgb(0x6C, 0xC4, 0xFF),
+            page_button: rgb(0x1D, 0x35, 0x5E),
+            page_button_text: rgb(0xE6, 0xEC, 0xF5),
+            selected_page_button: rgb(0xFF, 0x8C, 0x00),
+            selected_page_button_text: rgb(0x0B, 0x14, 0x26),
+        }
+    }
+
+    pub fn built_in() -> Vec<Palette> {
+        vec![Palette::dark(), Palette::light(), Palette::pb2()]
+    }
+
+    /// The iced theme used for widgets that have no launcher specific style.
+    pub fn to_theme(&self) -> Theme {
+        Theme::custom(
+            self.name.clone(),
+            iced::theme::Palette {
+                background: self.background,
+                text: self.text,
+                primary: self.primary,
+                success: self.success,
+                danger: self.danger,
+            },
+        )
+    }
+
+    /// Foreground/background pairs whose contrast is below the WCAG AA level, with their ratio.
+    pub fn contrast_issues(&self) -> Vec<(&'static str, f32)> {
+        let pairs = [
+            ("text on background", self.text, self.background),
+            ("news dates", self.news_date, self.background),
+            ("links", self.hyperlink, self.background),
+            ("page buttons", self.page_button_text, self.page_button),
+            ("selected page button", self.selected_page_button_text, self.selected_page_button),
+            ("success notifications", self.on_status, self.success),
+            ("warning notifications", self.on_status, self.warning),
+            ("error notifications", self.on_status, self.danger),
+            ("info notifications", self.on_status, self.info),
+        ];
+        pairs
+            .into_iter()
+            .map(|(name, foreground, background)| (name, contrast_ratio(foreground, background)))
+            .filter(|(_, ratio)| *ratio < MIN_CONTRAST)
+            .collect()
+    }
+}
+
+/// Reads user defined palettes. A missing file means there are none.
+pub fn load_custom_palettes(path: &Path) -> Result<Vec<Palette>, LauncherError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path).context(t!("error-read-themes"))?;
+    serde_json::from_str(&content)
+        .map_err(|err| LauncherError::new(ErrorKind::Io, t!("error-themes-damaged")).with_cause(err))
+}
+
+/// WCAG contrast ratio between two colors, from 1 (same luminance) to 21 (black on white).
+pub fn contrast_ratio(a: Color, b: Color) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+fn relative_luminance(color: Color) -> f32 {
+    let channel = |value: f32| {
+        if value <= 0.03928 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(color.r) + 0.7152 * channel(color.g) + 0.0722 * channel(color.b)
+}
+
+/// Same color with every channel scaled towards black by `amount`.
+pub fn darken(color: Color, amount: f32) -> Color {
+    Color {
+        r: color.r * (1.0 - amount),
+        g: color.g * (1.0 - amount),
+        b: color.b * (1.0 - amount),
+        a: color.a,
+    }
+}
+
+fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::from_rgb8(r, g, b)
+}
+
+mod hex {
+    use iced::Color;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        let [r, g, b, _] = color.into_rgba8();
+        serializer.serialize_str(&format!("#{:02x}{:02x}{:02x}", r, g, b))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        let digits = value.strip_prefix('#').unwrap_or(&value);
+        // Checked byte by byte first: slicing a non-ASCII string could split a character,
+        // and from_str_radix would take a "+" sign
+        if digits.len() != 6 || !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(D::Error::custom(format!("expected a #rrggbb color, got {}", value)));
+        }
+        let channel = |index: usize| u8::from_str_radix(&digits[index..index + 2], 16).expect("digits are hexadecimal");
+        Ok(Color::from_rgb8(channel(0), channel(2), channel(4)))
+    }
+}
@@ -0,0 +1,159 @@
+//! Tray icon with the launcher's quick actions, shown as a StatusNotifierItem on Linux desktops.
+
+use std::fmt;
+
+use iced::futures::channel::mpsc;
+use iced::futures::stream::BoxStream;
+
+use crate::error::LauncherError;
+use crate::receiver::SharedReceiver;
+
+/// Entry of the tray menu. Clicking the icon itself shows the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayAction {
+    ShowWindow,
+    Play,
+    PlayAsGuest,
+    CheckForUpdates,
+    LatestNews,
+    Quit,
+}
+
+impl TrayAction {
+    /// Menu entries, top to bottom.
+    pub const MENU: [TrayAction; 5] = [
+        TrayAction::Play,
+        TrayAction::PlayAsGuest,
+        TrayAction::CheckForUpdates,
+        TrayAction::LatestNews,
+        TrayAction::Quit,
+    ];
+}
+
+impl fmt::Display for TrayAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            TrayAction::ShowWindow => t!("tray-show-window"),
+            TrayAction::Play => t!("tray-play"),
+            TrayAction::PlayAsGuest => t!("tray-play-as-guest"),
+            TrayAction::CheckForUpdates => t!("tray-check-for-updates"),
+            TrayAction::LatestNews => t!("tray-latest-news"),
+            TrayAction::Quit => t!("tray-quit"),
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// The tray icon while it is shown, removed again when dropped.
+pub struct TrayIcon {
+    actions: SharedReceiver<TrayAction>,
+    #[cfg(target_os = "linux")]
+    handle: ksni::blocking::Handle<StatusNotifier>,
+}
+
+impl TrayIcon {
+    /// Actions picked in the tray menu. Only the first stream taken gets them.
+    pub fn actions(&self) -> BoxStream<'static, TrayAction> {
+        self.actions.stream()
+    }
+}
+
+impl Drop for TrayIcon {
+    fn drop(&mut self) {
+        #[cfg(target_os = "linux")]
+        let _ = self.handle.shutdown();
+    }
+}
+
+impl fmt::Debug for TrayIcon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrayIcon").finish_non_exhaustive()
+    }
+}
+
+/// Shows the tray icon. Fails on desktops without a tray and outside Linux.
+#[cfg(target_os = "linux")]
+pub fn show() -> Result<TrayIcon, LauncherError> {
+    use ksni::blocking::TrayMethods;
+
+    let (sender, receiver) = mpsc::unbounded();
+    // The blocking tray service runs its own runtime, which must not start inside iced's
+    let spawned = std::thread::spawn(move || StatusNotifier { sender }.spawn())
+        .join()
+        .map_err(|_| LauncherError::platform(t!("error-tray-icon")))?;
+    let handle = spawned.map_err(|err| LauncherError::platform(t!("error-tray-icon")).with_cause(err))?;
+    Ok(TrayIcon { actions: SharedReceiver::new(receiver), handle })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn show() -> Result<TrayIcon, LauncherError> {
+    Err(LauncherError::platform(t!("error-tray-linux-only")))
+}
+
+/// Icon drawn for trays that cannot find the installed theme icon, `size` pixels square, ARGB.
+pub fn icon_pixels(size: usize) -> Vec<u8> {
+    let center = (size as f32 - 1.0) / 2.0;
+    let mut pixels = Vec::with_capacity(size * size * 4);
+    for y in 0..size {
+        for x in 0..size {
+            let distance = ((x as f32 - center).powi(2) + (y as f32 - center).powi(2)).sqrt() / (size as f32 / 2.0);
+            // The colors of the app menu icon: a light ring on the dark background
+            let pixel = match distance {
+                d if d > 0.95 => [0, 0, 0, 0],
+                d if d > 0.6 && d < 0.8 => [255, 0x3f, 0xa9, 0xf5],
+                _ => [255, 0x14, 0x20, 0x2e],
+            };
+            pixels.extend_from_slice(&pixel);
+        }
+    }
+    pixels
+}
+
+#[cfg(target_os = "linux")]
+struct StatusNotifier {
+    sender: mpsc::UnboundedSender<TrayAction>,
+}
+
+#[cfg(target_os = "linux")]
+impl ksni::Tray for StatusNotifier {
+    fn id(&self) -> String {
+        crate::desktop::ICON_NAME.to_string()
+    }
+
+    fn title(&self) -> String {
+        "Plazma Burst 2 Launcher".to_string()
+    }
+
+    fn icon_name(&self) -> String {
+        crate::desktop::ICON_NAME.to_string()
+    }
+
+    fn icon_pixmap(&self) -> Vec<ksni::Icon> {
+        vec![ksni::Icon { width: 32, height: 32, data: icon_pixels(32) }]
+    }
+
+    fn activate(&mut self, _x: i32, _y: i32) {
+        let _ = self.sender.unbounded_send(TrayAction::ShowWindow);
+    }
+
+    fn menu(&self) -> Vec<ksni::MenuItem<Self>> {
+        use ksni::menu::StandardItem;
+
+        let mut menu = Vec::new();
+        for action in TrayAction::MENU {
+            let item = StandardItem {
+                label: action.to_string(),
+                activate: Box::new(move |tray: &mut Self| {
+                    // The launcher is gone once the receiver is, nothing left to tell
+                    let _ = tray.sender.unbounded_send(action);
+                }),
+                ..Default::default()
+            };
+            if action == TrayAction::Quit {
+                menu.push(ksni::MenuItem::Separator);
+            }
+            menu.push(item.into());
+        }
+        menu
+    }
+}
@@ -0,0 +1,229 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use flate2::read::ZlibDecoder;
+
+use crate::error::{Context, ErrorKind, LauncherError};
+
+/// Directory inside the install directory holding one subdirectory per game build.
+pub const VERSIONS_DIR_NAME: &str = "versions";
+
+pub const GAME_FILE_NAME: &str = "pb2_re34_alt.swf";
+
+/// Time stamp of the build as served by `time.php`, written last so an interrupted download is not listed.
+pub const TIME_FILE_NAME: &str = "last_update.v";
+
+/// How many builds are kept after a download unless the user changes it.
+pub const DEFAULT_KEEP_VERSIONS: u16 = 3;
+
+/// A game build installed in its own directory, named after its time stamp.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameVersion {
+    pub id: String,
+    pub dir: PathBuf,
+}
+
+impl GameVersion {
+    pub fn game_file(&self) -> PathBuf {
+        self.dir.join(GAME_FILE_NAME)
+    }
+
+    /// Checks the game file is a complete Flash movie, not cut short or an error page saved in its place.
+    pub fn verify(&self) -> Result<(), LauncherError> {
+        let damaged = || LauncherError::new(ErrorKind::Io, t!("error-build-damaged", build = self.id.as_str()));
+        let mut file = File::open(self.game_file()).map_err(|err| damaged().with_cause(err))?;
+        let size = file.metadata().map_err(|err| damaged().with_cause(err))?.len();
+        let mut header = [0u8; 8];
+        file.read_exact(&mut header).map_err(|_| damaged())?;
+        // The header ends with the length of the uncompressed movie, header included
+        let length = u64::from(u32::from_le_bytes([header[4], header[5], header[6], header[7]]));
+        let complete = match &header[..3] {
+            b"FWS" => size >= length,
+            // zlib stream after the header, one cut short fails to decode
+            b"CWS" => io::copy(&mut ZlibDecoder::new(file), &mut io::sink()).is_ok_and(|body| body + 8 == length),
+            // LZMA: the compressed length and 5 bytes of properties come before the data
+            b"ZWS" => {
+                let mut compressed = [0u8; 4];
+                file.read_exact(&mut compressed).is_ok() && size >= 17 + u64::from(u32::from_le_bytes(compressed))
+            }
+            _ => false,
+        };
+        if !complete {
+            return Err(damaged());
+        }
+        Ok(())
+    }
+}
+
+/// How the installed builds compare with the one the website publishes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateStatus {
+    NotInstalled,
+    UpToDate,
+    /// A newer build is published, by its id.
+    Available(String),
+}
+
+/// Entry of the version selector next to Play.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionChoice {
+    Latest,
+    Pinned(String),
+}
+
+impl VersionChoice {
+    pub fn from_setting(version: Option<&str>) -> VersionChoice {
+        match version {
+            Some(id) => VersionChoice::Pinned(id.to_string()),
+            None => VersionChoice::Latest,
+        }
+    }
+
+    pub fn into_setting(self) -> Option<String> {
+        match self {
+            VersionChoice::Latest => None,
+            VersionChoice::Pinned(id) => Some(id),
+        }
+    }
+}
+
+impl fmt::Display for VersionChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionChoice::Latest => write!(f, "{}", t!("latest-build")),
+            VersionChoice::Pinned(id) => write!(f, "{}", t!("pinned-build", build = id)),
+        }
+    }
+}
+
+pub fn versions_dir(install_dir: &Path) -> PathBuf {
+    install_dir.join(VERSIONS_DIR_NAME)
+}
+
+/// Directory name for the build with the given time stamp.
+pub fn version_id(time: &str) -> String {
+    let id: String = time
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+        .collect();
+    if id.is_empty() {
+        "unknown".to_string()
+    } else {
+        id
+    }
+}
+
+/// Status of the `installed` builds when the website publishes build `latest`.
+pub fn update_status(installed: &[GameVersion], latest: &str) -> UpdateStatus {
+    if installed.is_empty() {
+        UpdateStatus::NotInstalled
+    } else if installed.iter().any(|version| version.id == latest) {
+        UpdateStatus::UpToDate
+    } else {
+        UpdateStatus::Available(latest.to_string())
+    }
+}
+
+/// Builds that finished downloading, newest first.
+///
+/// A build installed before versioned directories is moved into its own directory first.
+pub fn installed(install_dir: &Path) -> Result<Vec<GameVersion>, LauncherError> {
+    migrate_legacy(install_dir)?;
+    let dir = versions_dir(install_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut versions = Vec::new();
+    for entry in fs::read_dir(&dir).context(t!("error-list-builds"))? {
+        let entry = entry.context(t!("error-list-builds"))?;
+        let path = entry.path();
+        if !path.join(TIME_FILE_NAME).exists() || !path.join(GAME_FILE_NAME).exists() {
+            continue;
+        }
+        versions.push(GameVersion {
+            id: entry.file_name().to_string_lossy().to_string(),
+            dir: path,
+        });
+    }
+    // Time stamps are numbers, anything else sorts after them by name
+    versions.sort_by(|a, b| {
+        let key = |version: &GameVersion| (version.id.parse::<u64>().ok(), version.id.clone());
+        key(b).cmp(&key(a))
+    });
+    Ok(versions)
+}
+
+/// Build directories a download left unfinished, they hold no time stamp.
+pub fn unfinished(install_dir: &Path) -> Result<Vec<PathBuf>, LauncherError> {
+    let dir = versions_dir(install_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut unfinished = Vec::new();
+    for entry in fs::read_dir(&dir).context(t!("error-list-builds"))? {
+        let path = entry.context(t!("error-list-builds"))?.path();
+        if path.is_dir() && !path.join(TIME_FILE_NAME).exists() {
+            unfinished.push(path);
+        }
+    }
+    Ok(unfinished)
+}
+
+/// The build Play starts: the pinned one, or the newest when nothing is pinned.
+pub fn resolve(install_dir: &Path, pinned: Option<&str>) -> Result<GameVersion, LauncherError> {
+    let versions = installed(install_dir)?;
+    match pinned {
+        Some(id) => versions.into_iter().find(|version| version.id == id).ok_or_else(|| {
+            LauncherError::new(ErrorKind::Io, t!("error-build-gone", build = id))
+        }),
+        None => versions
+            .into_iter()
+            .next()
+            .ok_or_else(|| LauncherError::new(ErrorKind::Io, t!("error-not-installed"))),
+    }
+}
+
+/// Removes all but the newest `keep` builds, never the pinned one. `keep` 0 keeps everything.
+///
+/// Returns the ids of the removed builds.
+pub fn cleanup(install_dir: &Path, keep: u16, pinned: Option<&str>) -> Result<Vec<String>, LauncherError> {
+    if keep == 0 {
+        return Ok(Vec::new());
+    }
+    let mut removed = Vec::new();
+    for version in installed(install_dir)?.into_iter().skip(keep as usize) {
+        if pinned == Some(version.id.as_str()) {
+            continue;
+        }
+        fs::remove_dir_all(&version.dir).context(t!("error-remove-build", build = version.id.as_str()))?;
+        removed.push(version.id);
+    }
+    Ok(removed)
+}
+
+// Launchers before versioned directories kept a single build next to the Flashplayer
+fn migrate_legacy(install_dir: &Path) -> Result<(), LauncherError> {
+    let game_file = install_dir.join(GAME_FILE_NAME);
+    let time_file = install_dir.join(TIME_FILE_NAME);
+    if !game_file.exists() {
+        return Ok(());
+    }
+    let id = match fs::read_to_string(&time_file) {
+        Ok(time) => version_id(&time),
+        Err(_) => "legacy".to_string(),
+    };
+    let dir = versions_dir(install_dir).join(&id);
+    fs::create_dir_all(&dir).context(t!("error-move-build"))?;
+    // Time file last, so the build only shows up once the game file is in place
+    fs::rename(&game_file, dir.join(GAME_FILE_NAME)).context(t!("error-move-build"))?;
+    if time_file.exists() {
+        fs::rename(&time_file, dir.join(TIME_FILE_NAME)).context(t!("error-move-build"))?;
+    } else {
+        fs::write(dir.join(TIME_FILE_NAME), &id).context(t!("error-move-build"))?;
+    }
+    Ok(())
+}